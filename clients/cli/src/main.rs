@@ -1,15 +1,18 @@
 use solana_client::rpc_config::RpcSendTransactionConfig;
-use solana_sdk::program_option::COption;
-use solana_sdk::program_pack::Pack;
 use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
 use spl_associated_token_account_interface::instruction::create_associated_token_account;
 use spl_token_2022_interface::{
     extension::{BaseStateWithExtensions, PodStateWithExtensions, StateWithExtensions},
     pod::PodMint,
-    state::{Account, AccountState},
+    state::Account,
 };
 use spl_token_metadata_interface::state::TokenMetadata;
-use token_acl_client::set_mint_tacl_metadata_ix;
+use token_acl_client::{
+    add_compute_budget_instructions, create_lookup_table_instructions, create_v0_message,
+    extend_lookup_table_instructions, get_lookup_table_account, get_mint_lookup_table_addresses,
+    set_mint_tacl_metadata_ix, simulate_transaction, AccountFetcher, OverlayAccountFetcher,
+    Preflight, PriorityFee, RpcAccountFetcher, DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT,
+};
 use {
     clap::{crate_description, crate_name, crate_version, Arg, ArgGroup, Command},
     solana_clap_v3_utils::{
@@ -24,12 +27,13 @@ use {
     solana_commitment_config::CommitmentConfig,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_sdk::{
+        instruction::Instruction,
         message::Message,
         pubkey::Pubkey,
         signature::{Signature, Signer},
-        transaction::Transaction,
+        transaction::{Transaction, VersionedTransaction},
     },
-    std::{error::Error, process::exit, rc::Rc, sync::Arc},
+    std::{error::Error, fmt, process::exit, rc::Rc, sync::Arc},
 };
 
 struct Config {
@@ -37,16 +41,127 @@ struct Config {
     payer: Arc<dyn Signer>,
     json_rpc_url: String,
     verbose: bool,
+    dry_run: bool,
+    lookup_tables: Vec<Pubkey>,
+    compute_unit_price: Option<u64>,
+    auto_compute_limit: bool,
+}
+
+/// What a command did with its transaction.
+enum TransactionOutcome {
+    Sent(Signature),
+    /// Only simulated, with `--dry-run`.
+    Simulated(Preflight),
+}
+
+impl fmt::Display for TransactionOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionOutcome::Sent(signature) => write!(f, "{}", signature),
+            TransactionOutcome::Simulated(preflight) => write!(f, "{}", preflight),
+        }
+    }
+}
+
+/// Signs `instructions` with `signers` and sends them, or only simulates them
+/// with `--dry-run` and fails with the diagnosis when the simulation fails.
+///
+/// The transaction is a v0 one loading accounts from the `--lookup-table`
+/// tables when any is given, and a legacy one otherwise. It sets the
+/// `--compute-unit-price`, and with `--auto-compute-limit` a compute unit
+/// limit simulated beforehand.
+async fn send_transaction(
+    rpc_client: &Arc<RpcClient>,
+    config: &Config,
+    instructions: &[Instruction],
+    signers: &[&dyn Signer],
+    skip_preflight: bool,
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer_pubkey = config.payer.pubkey();
+
+    let rpc_fetcher = RpcAccountFetcher::new(rpc_client);
+    let mut lookup_tables = Vec::with_capacity(config.lookup_tables.len());
+    for lookup_table in &config.lookup_tables {
+        lookup_tables.push(
+            get_lookup_table_account(lookup_table, &rpc_fetcher)
+                .await
+                .map_err(|err| {
+                    format!(
+                        "error: unable to get lookup table {}: {}",
+                        lookup_table, err
+                    )
+                })?,
+        );
+    }
+
+    let instructions = add_compute_budget_instructions(
+        rpc_client,
+        &payer_pubkey,
+        instructions,
+        &lookup_tables,
+        config
+            .auto_compute_limit
+            .then_some(DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT),
+        config
+            .compute_unit_price
+            .map_or(PriorityFee::None, PriorityFee::Fixed),
+    )
+    .await
+    .map_err(|err| format!("error: unable to set compute budget: {}", err))?;
+
+    let blockhash = rpc_client
+        .get_latest_blockhash()
+        .await
+        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
+
+    let transaction = if lookup_tables.is_empty() {
+        let mut transaction =
+            Transaction::new_unsigned(Message::new(&instructions, Some(&payer_pubkey)));
+        transaction
+            .try_sign(signers, blockhash)
+            .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
+        VersionedTransaction::from(transaction)
+    } else {
+        let message = create_v0_message(&payer_pubkey, &instructions, &lookup_tables, blockhash)
+            .map_err(|err| format!("error: unable to compile v0 message: {}", err))?;
+        VersionedTransaction::try_new(message, signers)
+            .map_err(|err| format!("error: failed to sign transaction: {}", err))?
+    };
+
+    if config.dry_run {
+        let preflight = simulate_transaction(rpc_client, &transaction)
+            .await
+            .map_err(|err| format!("error: simulate transaction: {}", err))?;
+        if preflight.failure.is_some() {
+            return Err(preflight.to_string().into());
+        }
+        return Ok(TransactionOutcome::Simulated(preflight));
+    }
+
+    let signature = rpc_client
+        .send_and_confirm_transaction_with_spinner_and_config(
+            &transaction,
+            config.commitment_config,
+            RpcSendTransactionConfig {
+                skip_preflight,
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|err| format!("error: send transaction: {}", err))?;
+
+    Ok(TransactionOutcome::Sent(signature))
 }
 
 async fn process_create_config(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     freeze_authority: Option<(Box<dyn Signer>, Pubkey)>,
     mint: &Pubkey,
     gating_program: Option<&Pubkey>,
-) -> Result<Signature, Box<dyn Error>> {
-    let config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
+    let mint_config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
 
     let ix = token_acl_client::instructions::CreateConfigBuilder::new()
         .authority(
@@ -57,7 +172,7 @@ async fn process_create_config(
         )
         .payer(payer.pubkey())
         .mint(*mint)
-        .mint_config(config)
+        .mint_config(mint_config)
         .gating_program(gating_program.cloned().unwrap_or(Pubkey::default()))
         .instruction();
 
@@ -65,7 +180,7 @@ async fn process_create_config(
 
     if let Some(gating_program) = gating_program {
         let mint_data = rpc_client
-            .get_account_data(&mint)
+            .get_account_data(mint)
             .await
             .map_err(|err| format!("error: unable to get mint data: {}", err))?;
         let mint_unpacked: PodStateWithExtensions<'_, PodMint> =
@@ -89,7 +204,7 @@ async fn process_create_config(
                 .await
                 .map_err(|err| format!("error: unable to get rent: {}", err))?;
             let transfer_ix =
-                solana_system_interface::instruction::transfer(&payer.pubkey(), &mint, rent);
+                solana_system_interface::instruction::transfer(&payer.pubkey(), mint, rent);
             instructions.push(transfer_ix);
         }
 
@@ -97,118 +212,74 @@ async fn process_create_config(
         instructions.push(set_metadata_ix);
     }
 
-    let mut transaction = Transaction::new_unsigned(Message::new(
-        &instructions.as_slice(),
-        Some(&payer.pubkey()),
-    ));
-
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    if let Some((signer, _)) = freeze_authority {
-        let signer: Arc<dyn Signer> = Arc::new(signer);
-        transaction
-            .try_sign(&[payer, &signer], blockhash)
-            .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
-    } else {
-        transaction
-            .try_sign(&[payer], blockhash)
-            .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
-    };
+    let mut signers = vec![payer.as_ref()];
+    if let Some((signer, _)) = &freeze_authority {
+        signers.push(signer.as_ref());
+    }
 
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
+    let outcome = send_transaction(rpc_client, config, &instructions, &signers, false).await?;
 
-    println!("config: {:?}", config);
+    println!("config: {:?}", mint_config);
 
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_delete_config(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     mint: &Pubkey,
     receiver: Option<&Pubkey>,
-) -> Result<Signature, Box<dyn Error>> {
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
     let payer_pk = payer.pubkey();
     let receiver = receiver.unwrap_or(&payer_pk);
-    let config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
+    let mint_config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
 
     let ix = token_acl_client::instructions::DeleteConfigBuilder::new()
         .authority(payer.pubkey())
         .receiver(*receiver)
         .mint(*mint)
-        .mint_config(config)
+        .mint_config(mint_config)
         .instruction();
 
-    let mut transaction = Transaction::new_unsigned(Message::new(&[ix], Some(&payer.pubkey())));
-
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
-
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
+    let outcome = send_transaction(rpc_client, config, &[ix], &[payer.as_ref()], false).await?;
 
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_set_authority(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     mint: &Pubkey,
     new_authority: &Pubkey,
-) -> Result<Signature, Box<dyn Error>> {
-    let config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
+    let mint_config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
 
     let ix = token_acl_client::instructions::SetAuthorityBuilder::new()
         .authority(payer.pubkey())
         .new_authority(*new_authority)
-        .mint_config(config)
+        .mint_config(mint_config)
         .instruction();
 
-    let mut transaction = Transaction::new_unsigned(Message::new(&[ix], Some(&payer.pubkey())));
+    let outcome = send_transaction(rpc_client, config, &[ix], &[payer.as_ref()], false).await?;
 
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
-
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
-
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_set_gating_program(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     mint: &Pubkey,
     new_gating_program: &Pubkey,
-) -> Result<Signature, Box<dyn Error>> {
-    let config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
+    let mint_config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
 
     let ix = token_acl_client::instructions::SetGatingProgramBuilder::new()
         .authority(payer.pubkey())
         .new_gating_program(*new_gating_program)
-        .mint_config(config)
+        .mint_config(mint_config)
         .instruction();
 
     let set_metadata_ix = set_mint_tacl_metadata_ix(mint, &payer.pubkey(), new_gating_program);
@@ -216,7 +287,7 @@ async fn process_set_gating_program(
     let mut instructions = vec![ix, set_metadata_ix];
 
     let mint_data = rpc_client
-        .get_account_data(&mint)
+        .get_account_data(mint)
         .await
         .map_err(|err| format!("error: unable to get mint data: {}", err))?;
     let mint_unpacked = PodStateWithExtensions::<PodMint>::unpack(&mint_data)
@@ -239,379 +310,261 @@ async fn process_set_gating_program(
             .await
             .map_err(|err| format!("error: unable to get rent: {}", err))?;
         let transfer_ix =
-            solana_system_interface::instruction::transfer(&payer.pubkey(), &mint, rent);
+            solana_system_interface::instruction::transfer(&payer.pubkey(), mint, rent);
         instructions.push(transfer_ix);
     }
 
-    let mut transaction =
-        Transaction::new_unsigned(Message::new(instructions.as_slice(), Some(&payer.pubkey())));
-
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
+    let outcome =
+        send_transaction(rpc_client, config, &instructions, &[payer.as_ref()], false).await?;
 
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
-
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_set_instructions(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     mint: &Pubkey,
     enable_thaw: bool,
     enable_freeze: bool,
-) -> Result<Signature, Box<dyn Error>> {
-    let config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
+    let mint_config = token_acl_client::accounts::MintConfig::find_pda(mint).0;
 
     let ix = token_acl_client::instructions::TogglePermissionlessInstructionsBuilder::new()
         .authority(payer.pubkey())
         .thaw_enabled(enable_thaw)
         .freeze_enabled(enable_freeze)
-        .mint_config(config)
+        .mint_config(mint_config)
         .instruction();
 
-    let mut transaction = Transaction::new_unsigned(Message::new(&[ix], Some(&payer.pubkey())));
-
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
-
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
+    let outcome = send_transaction(rpc_client, config, &[ix], &[payer.as_ref()], false).await?;
 
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_freeze(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     token_account: Pubkey,
-) -> Result<Signature, Box<dyn Error>> {
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
     let token_account_data = rpc_client.get_account(&token_account).await.unwrap();
     let ta = StateWithExtensions::<Account>::unpack(token_account_data.data.as_ref()).unwrap();
 
-    let config = token_acl_client::accounts::MintConfig::find_pda(&ta.base.mint).0;
+    let mint_config = token_acl_client::accounts::MintConfig::find_pda(&ta.base.mint).0;
 
     let ix = token_acl_client::instructions::FreezeBuilder::new()
         .authority(payer.pubkey())
         .mint(ta.base.mint)
         .token_account(token_account)
-        .mint_config(config)
+        .mint_config(mint_config)
         .token_program(spl_token_2022_interface::ID)
         .instruction();
 
-    let mut transaction = Transaction::new_unsigned(Message::new(&[ix], Some(&payer.pubkey())));
+    let outcome = send_transaction(rpc_client, config, &[ix], &[payer.as_ref()], false).await?;
 
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
-
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
-
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_freeze_permissionless(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     mint: Option<Pubkey>,
     token_account_pk: Option<Pubkey>,
     token_account_owner_pk: Option<Pubkey>,
-) -> Result<Signature, Box<dyn Error>> {
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
     let mut instructions = Vec::new();
+    let rpc_fetcher = RpcAccountFetcher::new(rpc_client);
 
-    let (mint, token_account_pk, token_account_owner_pk, new_ata, ata_data) =
-        match (mint, token_account_pk, token_account_owner_pk) {
-            (None, Some(token_account_pk), None) => {
-                let token_account_data = rpc_client.get_account(&token_account_pk).await.unwrap();
-                let token_account =
-                    StateWithExtensions::<Account>::unpack(token_account_data.data.as_ref())
-                        .unwrap();
-                (
-                    token_account.base.mint,
-                    token_account_pk,
-                    token_account.base.owner,
-                    false,
-                    Vec::new(),
-                )
-            }
-            (Some(mint), None, Some(token_account_owner_pk)) => {
-                let token_account = get_associated_token_address_with_program_id(
-                    &token_account_owner_pk,
-                    &mint,
-                    &spl_token_2022_interface::ID,
-                );
-
-                let ix = create_associated_token_account(
-                    &payer.pubkey(),
-                    &token_account_owner_pk,
-                    &mint,
-                    &spl_token_2022_interface::ID,
-                );
-                instructions.push(ix);
-
-                let acc = Account {
-                    mint,
-                    owner: token_account_owner_pk,
-                    amount: 0,
-                    delegate: COption::None,
-                    state: AccountState::Frozen,
-                    is_native: COption::None,
-                    delegated_amount: 0,
-                    close_authority: COption::None,
-                };
-
-                let mut data = vec![0u8; Account::LEN];
-                Account::pack(acc, &mut data)?;
-
-                (mint, token_account, token_account_owner_pk, true, data)
-            }
-            _ => {
-                return Err(
-                    "error: token_account or token_account_owner and mint must be provided".into(),
-                )
-            }
-        };
-
-    let config = token_acl_client::accounts::MintConfig::find_pda(&mint).0;
-
-    println!("mint: {:?}", mint);
-    println!("token_account_pk: {:?}", token_account_pk);
-    println!("token_account_owner_pk: {:?}", token_account_owner_pk);
+    let ix = match (mint, token_account_pk, token_account_owner_pk) {
+        (None, Some(token_account_pk), None) => {
+            println!("token_account_pk: {:?}", token_account_pk);
 
-    let ix = token_acl_client::create_freeze_permissionless_instruction_with_extra_metas(
-        &payer.pubkey(),
-        &token_account_pk,
-        &mint,
-        &config,
-        &spl_token_2022_interface::ID,
-        &token_account_owner_pk,
-        false,
-        |pubkey| {
-            let data = ata_data.clone();
-            async move {
-                if new_ata && pubkey == token_account_pk {
-                    return Ok(Some(data));
-                }
-                let data = rpc_client.get_account(&pubkey).await.map(|a| a.data).ok();
-                Ok(data)
-            }
-        },
-    )
-    .await
-    .unwrap();
+            token_acl_client::create_freeze_permissionless_instruction_for_token_account(
+                &payer.pubkey(),
+                &token_account_pk,
+                false,
+                &rpc_fetcher,
+            )
+            .await
+            .map_err(|err| format!("error: unable to build freeze instruction: {}", err))?
+        }
+        (Some(mint), None, Some(token_account_owner_pk)) => {
+            let token_program = rpc_fetcher
+                .get_account_owner(mint)
+                .await
+                .map_err(|err| format!("error: unable to fetch mint: {}", err))?
+                .ok_or("error: mint not found")?;
+            let token_account = get_associated_token_address_with_program_id(
+                &token_account_owner_pk,
+                &mint,
+                &token_program,
+            );
+
+            println!("mint: {:?}", mint);
+            println!("token_account_pk: {:?}", token_account);
+            println!("token_account_owner_pk: {:?}", token_account_owner_pk);
+
+            let ix = create_associated_token_account(
+                &payer.pubkey(),
+                &token_account_owner_pk,
+                &mint,
+                &token_program,
+            );
+            instructions.push(ix);
+
+            let mint_data = rpc_fetcher
+                .get_account_data(mint)
+                .await
+                .map_err(|err| format!("error: unable to fetch mint: {}", err))?
+                .ok_or("error: mint not found")?;
+            let data = token_acl_client::mock_associated_token_account_data(
+                &mint,
+                &mint_data,
+                &token_account_owner_pk,
+            )?;
+
+            token_acl_client::create_freeze_permissionless_instruction_for_mint_and_owner(
+                &payer.pubkey(),
+                &mint,
+                &token_account_owner_pk,
+                false,
+                OverlayAccountFetcher::new(&rpc_fetcher).with_account(token_account, data),
+            )
+            .await
+            .map_err(|err| format!("error: unable to build freeze instruction: {}", err))?
+        }
+        _ => {
+            return Err(
+                "error: token_account or token_account_owner and mint must be provided".into(),
+            )
+        }
+    };
 
     instructions.push(ix);
 
-    let mut transaction =
-        Transaction::new_unsigned(Message::new(&instructions, Some(&payer.pubkey())));
-
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
+    let outcome =
+        send_transaction(rpc_client, config, &instructions, &[payer.as_ref()], false).await?;
 
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
-
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_thaw(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     token_account: Pubkey,
-) -> Result<Signature, Box<dyn Error>> {
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
     let token_account_data = rpc_client.get_account(&token_account).await.unwrap();
     let ta = StateWithExtensions::<Account>::unpack(token_account_data.data.as_ref()).unwrap();
 
-    let config = token_acl_client::accounts::MintConfig::find_pda(&ta.base.mint).0;
+    let mint_config = token_acl_client::accounts::MintConfig::find_pda(&ta.base.mint).0;
 
     let ix = token_acl_client::instructions::ThawBuilder::new()
         .authority(payer.pubkey())
         .mint(ta.base.mint)
         .token_account(token_account)
-        .mint_config(config)
+        .mint_config(mint_config)
         .token_program(spl_token_2022_interface::ID)
         .instruction();
 
-    let mut transaction = Transaction::new_unsigned(Message::new(&[ix], Some(&payer.pubkey())));
-
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
-
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner(&transaction)
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
+    let outcome = send_transaction(rpc_client, config, &[ix], &[payer.as_ref()], false).await?;
 
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_thaw_permissionless(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     mint: Option<Pubkey>,
     token_account_pk: Option<Pubkey>,
     token_account_owner_pk: Option<Pubkey>,
-) -> Result<Signature, Box<dyn Error>> {
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
     let mut instructions = Vec::new();
+    let rpc_fetcher = RpcAccountFetcher::new(rpc_client);
 
-    let (mint, token_account_pk, token_account_owner_pk, new_ata, ata_data) =
-        match (mint, token_account_pk, token_account_owner_pk) {
-            (None, Some(token_account_pk), None) => {
-                let token_account_data = rpc_client.get_account(&token_account_pk).await.unwrap();
-                let token_account =
-                    StateWithExtensions::<Account>::unpack(token_account_data.data.as_ref())
-                        .unwrap();
-                (
-                    token_account.base.mint,
-                    token_account_pk,
-                    token_account.base.owner,
-                    false,
-                    Vec::new(),
-                )
-            }
-            (Some(mint), None, Some(token_account_owner_pk)) => {
-                let token_account = get_associated_token_address_with_program_id(
-                    &token_account_owner_pk,
-                    &mint,
-                    &spl_token_2022_interface::ID,
-                );
-
-                let ix = create_associated_token_account(
-                    &payer.pubkey(),
-                    &token_account_owner_pk,
-                    &mint,
-                    &spl_token_2022_interface::ID,
-                );
-                instructions.push(ix);
-
-                let acc = Account {
-                    mint,
-                    owner: token_account_owner_pk,
-                    amount: 0,
-                    delegate: COption::None,
-                    state: AccountState::Frozen,
-                    is_native: COption::None,
-                    delegated_amount: 0,
-                    close_authority: COption::None,
-                };
-
-                let mut data = vec![0u8; Account::LEN];
-                Account::pack(acc, &mut data)?;
-
-                (mint, token_account, token_account_owner_pk, true, data)
-            }
-            _ => {
-                return Err(
-                    "error: token_account or token_account_owner and mint must be provided".into(),
-                )
-            }
-        };
-
-    println!("mint: {:?}", mint);
-    println!("token_account_pk: {:?}", token_account_pk);
-    println!("token_account_owner_pk: {:?}", token_account_owner_pk);
-
-    let config = token_acl_client::accounts::MintConfig::find_pda(&mint).0;
+    let ix = match (mint, token_account_pk, token_account_owner_pk) {
+        (None, Some(token_account_pk), None) => {
+            println!("token_account_pk: {:?}", token_account_pk);
 
-    let ix = token_acl_client::create_thaw_permissionless_instruction_with_extra_metas(
-        &payer.pubkey(),
-        &token_account_pk,
-        &mint,
-        &config,
-        &spl_token_2022_interface::ID,
-        &token_account_owner_pk,
-        false,
-        |pubkey| {
-            let data = ata_data.clone();
-            async move {
-                if new_ata && pubkey == token_account_pk {
-                    return Ok(Some(data));
-                }
-                let data = rpc_client.get_account(&pubkey).await.map(|a| a.data).ok();
-                Ok(data)
-            }
-        },
-    )
-    .await
-    .unwrap();
+            token_acl_client::create_thaw_permissionless_instruction_for_token_account(
+                &payer.pubkey(),
+                &token_account_pk,
+                false,
+                &rpc_fetcher,
+            )
+            .await
+            .map_err(|err| format!("error: unable to build thaw instruction: {}", err))?
+        }
+        (Some(mint), None, Some(token_account_owner_pk)) => {
+            let token_program = rpc_fetcher
+                .get_account_owner(mint)
+                .await
+                .map_err(|err| format!("error: unable to fetch mint: {}", err))?
+                .ok_or("error: mint not found")?;
+            let token_account = get_associated_token_address_with_program_id(
+                &token_account_owner_pk,
+                &mint,
+                &token_program,
+            );
+
+            println!("mint: {:?}", mint);
+            println!("token_account_pk: {:?}", token_account);
+            println!("token_account_owner_pk: {:?}", token_account_owner_pk);
+
+            let ix = create_associated_token_account(
+                &payer.pubkey(),
+                &token_account_owner_pk,
+                &mint,
+                &token_program,
+            );
+            instructions.push(ix);
+
+            let mint_data = rpc_fetcher
+                .get_account_data(mint)
+                .await
+                .map_err(|err| format!("error: unable to fetch mint: {}", err))?
+                .ok_or("error: mint not found")?;
+            let data = token_acl_client::mock_associated_token_account_data(
+                &mint,
+                &mint_data,
+                &token_account_owner_pk,
+            )?;
+
+            token_acl_client::create_thaw_permissionless_instruction_for_mint_and_owner(
+                &payer.pubkey(),
+                &mint,
+                &token_account_owner_pk,
+                false,
+                OverlayAccountFetcher::new(&rpc_fetcher).with_account(token_account, data),
+            )
+            .await
+            .map_err(|err| format!("error: unable to build thaw instruction: {}", err))?
+        }
+        _ => {
+            return Err(
+                "error: token_account or token_account_owner and mint must be provided".into(),
+            )
+        }
+    };
 
     instructions.push(ix);
 
-    let mut transaction =
-        Transaction::new_unsigned(Message::new(&instructions, Some(&payer.pubkey())));
+    let outcome =
+        send_transaction(rpc_client, config, &instructions, &[payer.as_ref()], true).await?;
 
-    let blockhash = rpc_client
-        .get_latest_blockhash()
-        .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
-
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
-
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner_and_config(
-            &transaction,
-            CommitmentConfig {
-                commitment: solana_commitment_config::CommitmentLevel::Confirmed,
-            },
-            RpcSendTransactionConfig {
-                skip_preflight: true,
-                ..Default::default()
-            },
-        )
-        //.send_and_confirm_transaction_with_spinner_and_config(&transaction, CommitmentConfig { commitment: solana_sdk::commitment_config::CommitmentLevel::Confirmed }, RpcSendTransactionConfig { skip_preflight: true, ..Default::default()})
-        .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
-
-    Ok(signature)
+    Ok(outcome)
 }
 
 async fn process_create_ata_and_thaw_permissionless(
     rpc_client: &Arc<RpcClient>,
-    payer: &Arc<dyn Signer>,
+    config: &Config,
     mint: Pubkey,
     token_account_owner_pk: Pubkey,
-) -> Result<Signature, Box<dyn Error>> {
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
     let instructions = token_acl_client::create_ata_and_thaw_permissionless(
         &rpc_client.clone(),
         &payer.pubkey(),
@@ -622,44 +575,143 @@ async fn process_create_ata_and_thaw_permissionless(
     .await
     .map_err(|err| format!("error: create ata and thaw permissionless: {}", err))?;
 
+    let token_program = RpcAccountFetcher::new(rpc_client)
+        .get_account_owner(mint)
+        .await
+        .map_err(|err| format!("error: unable to fetch mint: {}", err))?
+        .ok_or("error: mint not found")?;
     let token_account_pk = get_associated_token_address_with_program_id(
         &token_account_owner_pk,
         &mint,
-        &spl_token_2022_interface::ID,
+        &token_program,
     );
 
     println!("mint: {:?}", mint);
     println!("token_account_pk: {:?}", token_account_pk);
     println!("token_account_owner_pk: {:?}", token_account_owner_pk);
 
-    let mut transaction =
-        Transaction::new_unsigned(Message::new(&instructions, Some(&payer.pubkey())));
+    let outcome =
+        send_transaction(rpc_client, config, &instructions, &[payer.as_ref()], true).await?;
 
-    let blockhash = rpc_client
-        .get_latest_blockhash()
+    Ok(outcome)
+}
+
+async fn process_create_lookup_table(
+    rpc_client: &Arc<RpcClient>,
+    config: &Config,
+    mint: Pubkey,
+) -> Result<Vec<TransactionOutcome>, Box<dyn Error>> {
+    let payer = &config.payer;
+
+    let addresses = get_mint_lookup_table_addresses(&mint, RpcAccountFetcher::new(rpc_client))
         .await
-        .map_err(|err| format!("error: unable to get latest blockhash: {}", err))?;
+        .map_err(|err| format!("error: unable to get the accounts of the mint: {}", err))?;
+    let recent_slot = rpc_client
+        .get_slot()
+        .await
+        .map_err(|err| format!("error: unable to get slot: {}", err))?;
+
+    let (lookup_table, instructions) =
+        create_lookup_table_instructions(&payer.pubkey(), &payer.pubkey(), recent_slot, &addresses);
+    println!("lookup table: {:?}", lookup_table);
+
+    // the first extension fits in the transaction creating the table
+    let (create, extend) = instructions.split_at(instructions.len().min(2));
+    let mut outcomes =
+        vec![send_transaction(rpc_client, config, create, &[payer.as_ref()], false).await?];
+    for ix in extend {
+        outcomes.push(
+            send_transaction(
+                rpc_client,
+                config,
+                std::slice::from_ref(ix),
+                &[payer.as_ref()],
+                false,
+            )
+            .await?,
+        );
+    }
 
-    transaction
-        .try_sign(&[payer], blockhash)
-        .map_err(|err| format!("error: failed to sign transaction: {}", err))?;
+    Ok(outcomes)
+}
 
-    let signature = rpc_client
-        .send_and_confirm_transaction_with_spinner_and_config(
-            &transaction,
-            CommitmentConfig {
-                commitment: solana_commitment_config::CommitmentLevel::Confirmed,
-            },
-            RpcSendTransactionConfig {
-                skip_preflight: true,
-                ..Default::default()
-            },
-        )
-        //.send_and_confirm_transaction_with_spinner_and_config(&transaction, CommitmentConfig { commitment: solana_sdk::commitment_config::CommitmentLevel::Confirmed }, RpcSendTransactionConfig { skip_preflight: true, ..Default::default()})
+async fn process_extend_lookup_table(
+    rpc_client: &Arc<RpcClient>,
+    config: &Config,
+    mint: Pubkey,
+    lookup_table: Pubkey,
+) -> Result<Vec<TransactionOutcome>, Box<dyn Error>> {
+    let payer = &config.payer;
+    let rpc_fetcher = RpcAccountFetcher::new(rpc_client);
+
+    let addresses = get_mint_lookup_table_addresses(&mint, &rpc_fetcher)
         .await
-        .map_err(|err| format!("error: send transaction: {}", err))?;
+        .map_err(|err| format!("error: unable to get the accounts of the mint: {}", err))?;
+    let lookup_table = get_lookup_table_account(&lookup_table, &rpc_fetcher)
+        .await
+        .map_err(|err| format!("error: unable to get lookup table: {}", err))?;
+
+    let instructions = extend_lookup_table_instructions(
+        &lookup_table,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &addresses,
+    );
+    if instructions.is_empty() {
+        println!("lookup table already holds every account of the mint");
+    }
 
-    Ok(signature)
+    let mut outcomes = Vec::with_capacity(instructions.len());
+    for ix in &instructions {
+        outcomes.push(
+            send_transaction(
+                rpc_client,
+                config,
+                std::slice::from_ref(ix),
+                &[payer.as_ref()],
+                false,
+            )
+            .await?,
+        );
+    }
+
+    Ok(outcomes)
+}
+
+async fn process_transfer(
+    rpc_client: &Arc<RpcClient>,
+    config: &Config,
+    mint: Pubkey,
+    recipient: Pubkey,
+    amount: u64,
+) -> Result<TransactionOutcome, Box<dyn Error>> {
+    let payer = &config.payer;
+    let instructions = token_acl_client::create_transfer(
+        rpc_client,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &mint,
+        &recipient,
+        amount,
+    )
+    .await
+    .map_err(|err| format!("error: unable to build transfer: {}", err))?;
+
+    println!("mint: {:?}", mint);
+    println!("recipient: {:?}", recipient);
+    println!(
+        "recipient token account: {:?}",
+        get_associated_token_address_with_program_id(
+            &recipient,
+            &mint,
+            &spl_token_2022_interface::ID,
+        )
+    );
+
+    let outcome =
+        send_transaction(rpc_client, config, &instructions, &[payer.as_ref()], false).await?;
+
+    Ok(outcome)
 }
 
 #[tokio::main]
@@ -701,6 +753,39 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 .global(true)
                 .help("Show additional information"),
         )
+        .arg(
+            Arg::new("dry_run")
+                .long("dry-run")
+                .takes_value(false)
+                .global(true)
+                .help("Simulate the transaction and explain why it fails instead of sending it"),
+        )
+        .arg(
+            Arg::new("lookup_table")
+                .long("lookup-table")
+                .value_name("LOOKUP_TABLE_ADDRESS")
+                .value_parser(SignerSourceParserBuilder::default().allow_pubkey().build())
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .global(true)
+                .help("Send a v0 transaction loading accounts from this address lookup table"),
+        )
+        .arg(
+            Arg::new("compute_unit_price")
+                .long("compute-unit-price")
+                .value_name("MICRO_LAMPORTS")
+                .value_parser(clap::value_parser!(u64))
+                .takes_value(true)
+                .global(true)
+                .help("Set the compute unit price of the transaction, in micro-lamports"),
+        )
+        .arg(
+            Arg::new("auto_compute_limit")
+                .long("auto-compute-limit")
+                .takes_value(false)
+                .global(true)
+                .help("Simulate the transaction to set its compute unit limit"),
+        )
         .arg(
             Arg::new("json_rpc_url")
                 .short('u')
@@ -986,6 +1071,76 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     .help("Specify the token account address"),
             )
         )
+        .subcommand(
+            Command::new("create-lookup-table")
+            .about("Creates an address lookup table holding the accounts every permissionless thaw and freeze of the mint uses.")
+            .arg(
+                Arg::new("mint_address")
+                    .value_name("MINT_ADDRESS")
+                    .value_parser(SignerSourceParserBuilder::default().allow_pubkey().build())
+                    .takes_value(true)
+                    .required(true)
+                    .index(1)
+                    .display_order(1)
+                    .help("Specify the mint address"),
+            )
+        )
+        .subcommand(
+            Command::new("extend-lookup-table")
+            .about("Adds the accounts of the mint that an address lookup table does not hold yet, e.g. after a gate change.")
+            .arg(
+                Arg::new("mint_address")
+                    .value_name("MINT_ADDRESS")
+                    .value_parser(SignerSourceParserBuilder::default().allow_pubkey().build())
+                    .takes_value(true)
+                    .required(true)
+                    .index(1)
+                    .display_order(1)
+                    .help("Specify the mint address"),
+            )
+            .arg(
+                Arg::new("lookup_table_address")
+                    .value_name("LOOKUP_TABLE_ADDRESS")
+                    .value_parser(SignerSourceParserBuilder::default().allow_pubkey().build())
+                    .takes_value(true)
+                    .required(true)
+                    .index(2)
+                    .display_order(2)
+                    .help("Specify the lookup table address"),
+            )
+        )
+        .subcommand(
+            Command::new("transfer")
+            .about("Transfers tokens from the payer, creating and thawing the recipient's associated token account if needed.")
+            .arg(
+                Arg::new("mint_address")
+                    .value_name("MINT_ADDRESS")
+                    .value_parser(SignerSourceParserBuilder::default().allow_pubkey().build())
+                    .takes_value(true)
+                    .long("mint")
+                    .required(true)
+                    .display_order(1)
+                    .help("Specify the mint address"),
+            )
+            .arg(
+                Arg::new("recipient")
+                    .value_name("RECIPIENT")
+                    .value_parser(SignerSourceParserBuilder::default().allow_pubkey().build())
+                    .takes_value(true)
+                    .long("recipient")
+                    .required(true)
+                    .help("Specify the recipient wallet address"),
+            )
+            .arg(
+                Arg::new("amount")
+                    .value_name("AMOUNT")
+                    .value_parser(clap::value_parser!(u64))
+                    .takes_value(true)
+                    .long("amount")
+                    .required(true)
+                    .help("Specify the amount to transfer, in base units"),
+            )
+        )
         .get_matches();
 
     let (command, matches) = app_matches.subcommand().unwrap();
@@ -1022,6 +1177,15 @@ async fn main() -> Result<(), Box<dyn Error>> {
             payer: Arc::from(payer),
             json_rpc_url,
             verbose: matches.try_contains_id("verbose")?,
+            dry_run: matches.try_contains_id("dry_run")?,
+            lookup_tables: SignerSource::try_get_pubkeys(
+                matches,
+                "lookup_table",
+                &mut wallet_manager,
+            )?
+            .unwrap_or_default(),
+            compute_unit_price: matches.get_one::<u64>("compute_unit_price").copied(),
+            auto_compute_limit: matches.try_contains_id("auto_compute_limit")?,
         }
     };
     solana_logger::setup_with_default("solana=info");
@@ -1048,7 +1212,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     .unwrap();
             let response = process_create_config(
                 &rpc_client,
-                &config.payer,
+                &config,
                 freeze_authority,
                 &mint_address,
                 gating_program.as_ref(),
@@ -1070,7 +1234,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     .unwrap();
             let response = process_delete_config(
                 &rpc_client,
-                &config.payer,
+                &config,
                 &mint_address,
                 receiver_address.as_ref(),
             )
@@ -1091,7 +1255,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     .unwrap()
                     .unwrap();
             let response =
-                process_set_authority(&rpc_client, &config.payer, &mint_address, &new_authority)
+                process_set_authority(&rpc_client, &config, &mint_address, &new_authority)
                     .await
                     .unwrap_or_else(|err| {
                         eprintln!("error: set-authority: {}", err);
@@ -1113,7 +1277,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
             .unwrap();
             let response = process_set_gating_program(
                 &rpc_client,
-                &config.payer,
+                &config,
                 &mint_address,
                 &new_gating_program,
             )
@@ -1137,7 +1301,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
             let response = process_set_instructions(
                 &rpc_client,
-                &config.payer,
+                &config,
                 &mint_address,
                 enable_thaw,
                 enable_freeze,
@@ -1164,7 +1328,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
             .unwrap();
             let response = process_thaw_permissionless(
                 &rpc_client,
-                &config.payer,
+                &config,
                 mint_address,
                 token_account,
                 token_account_owner,
@@ -1190,7 +1354,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
             .unwrap();
             let response = process_create_ata_and_thaw_permissionless(
                 &rpc_client,
-                &config.payer,
+                &config,
                 mint_address,
                 token_account_owner,
             )
@@ -1216,7 +1380,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
             .unwrap();
             let response = process_freeze_permissionless(
                 &rpc_client,
-                &config.payer,
+                &config,
                 mint_address,
                 token_account,
                 token_account_owner,
@@ -1233,7 +1397,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 SignerSource::try_get_pubkey(arg_matches, "token_account", &mut wallet_manager)
                     .unwrap()
                     .unwrap();
-            let response = process_freeze(&rpc_client, &config.payer, token_account)
+            let response = process_freeze(&rpc_client, &config, token_account)
                 .await
                 .unwrap_or_else(|err| {
                     eprintln!("error: freeze: {}", err);
@@ -1246,7 +1410,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 SignerSource::try_get_pubkey(arg_matches, "token_account", &mut wallet_manager)
                     .unwrap()
                     .unwrap();
-            let response = process_thaw(&rpc_client, &config.payer, token_account)
+            let response = process_thaw(&rpc_client, &config, token_account)
                 .await
                 .unwrap_or_else(|err| {
                     eprintln!("error: thaw: {}", err);
@@ -1254,6 +1418,66 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 });
             println!("{}", response);
         }
+        ("create-lookup-table", arg_matches) => {
+            let mint_address =
+                SignerSource::try_get_pubkey(arg_matches, "mint_address", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let response = process_create_lookup_table(&rpc_client, &config, mint_address)
+                .await
+                .unwrap_or_else(|err| {
+                    eprintln!("error: create-lookup-table: {}", err);
+                    exit(1);
+                });
+            for outcome in response {
+                println!("{}", outcome);
+            }
+        }
+        ("extend-lookup-table", arg_matches) => {
+            let mint_address =
+                SignerSource::try_get_pubkey(arg_matches, "mint_address", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let lookup_table_address = SignerSource::try_get_pubkey(
+                arg_matches,
+                "lookup_table_address",
+                &mut wallet_manager,
+            )
+            .unwrap()
+            .unwrap();
+            let response = process_extend_lookup_table(
+                &rpc_client,
+                &config,
+                mint_address,
+                lookup_table_address,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                eprintln!("error: extend-lookup-table: {}", err);
+                exit(1);
+            });
+            for outcome in response {
+                println!("{}", outcome);
+            }
+        }
+        ("transfer", arg_matches) => {
+            let mint_address =
+                SignerSource::try_get_pubkey(arg_matches, "mint_address", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let recipient =
+                SignerSource::try_get_pubkey(arg_matches, "recipient", &mut wallet_manager)
+                    .unwrap()
+                    .unwrap();
+            let amount = *arg_matches.get_one::<u64>("amount").unwrap();
+            let response = process_transfer(&rpc_client, &config, mint_address, recipient, amount)
+                .await
+                .unwrap_or_else(|err| {
+                    eprintln!("error: transfer: {}", err);
+                    exit(1);
+                });
+            println!("{}", response);
+        }
         _ => unreachable!(),
     };
 
@@ -0,0 +1,197 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use spl_token_2022_interface::{
+    extension::ExtensionType, instruction::TokenInstruction, ID as SPL_TOKEN_2022_ID,
+};
+use token_acl_interface::offchain::{
+    AccountFetchError, AccountFetcher, CachedAccountFetcher, OverlayAccountFetcher,
+};
+
+use crate::{
+    accounts::MintConfig, create_thaw_permissionless_instruction_with_extra_metas,
+    get_mint_acl_status, mock_token_account_data,
+};
+
+/// Token account an instruction creates or initializes.
+struct TokenAccountInit {
+    token_account: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+    /// Extensions initialized before `InitializeAccount`.
+    extension_types: Vec<ExtensionType>,
+}
+
+impl TokenAccountInit {
+    /// Recognizes the associated token account `Create` and `CreateIdempotent`
+    /// instructions and the token-2022 `InitializeAccount*` instructions.
+    ///
+    /// `immutable_owners` are the accounts an earlier `InitializeImmutableOwner`
+    /// of the same list targeted.
+    fn parse(ix: &Instruction, immutable_owners: &HashSet<Pubkey>) -> Option<Self> {
+        let key = |i: usize| ix.accounts.get(i).map(|meta| meta.pubkey);
+
+        if ix.program_id == spl_associated_token_account_interface::program::ID {
+            // [payer, token_account, owner, mint, system_program, token_program]
+            if !matches!(ix.data.as_slice(), [] | [0] | [1]) || key(5) != Some(SPL_TOKEN_2022_ID) {
+                return None;
+            }
+            return Some(Self {
+                token_account: key(1)?,
+                mint: key(3)?,
+                owner: key(2)?,
+                extension_types: vec![ExtensionType::ImmutableOwner],
+            });
+        }
+
+        if ix.program_id != SPL_TOKEN_2022_ID {
+            return None;
+        }
+        let owner = match TokenInstruction::unpack(&ix.data).ok()? {
+            TokenInstruction::InitializeAccount => key(2)?,
+            TokenInstruction::InitializeAccount2 { owner }
+            | TokenInstruction::InitializeAccount3 { owner } => owner,
+            _ => return None,
+        };
+        let token_account = key(0)?;
+        let extension_types = if immutable_owners.contains(&token_account) {
+            vec![ExtensionType::ImmutableOwner]
+        } else {
+            vec![]
+        };
+        Some(Self {
+            token_account,
+            mint: key(1)?,
+            owner,
+            extension_types,
+        })
+    }
+}
+
+/// Inserts a `ThawPermissionlessIdempotent` signed by `signer_pubkey` right
+/// after every instruction of `instructions` that creates or initializes a
+/// token account of a mint whose accounts start frozen and that enables
+/// permissionless thaw.
+///
+/// Token accounts that do not exist yet are mocked as Token-2022 would create
+/// them to resolve the gate's extra metas, and every account is fetched once
+/// for the whole list.
+pub async fn insert_permissionless_thaw_instructions<A>(
+    signer_pubkey: &Pubkey,
+    instructions: Vec<Instruction>,
+    fetcher: A,
+) -> Result<Vec<Instruction>, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    // only lives for this call, so nothing expires
+    let fetcher = CachedAccountFetcher::new(fetcher, Duration::MAX);
+
+    let mut immutable_owners = HashSet::new();
+    let mut inits = Vec::with_capacity(instructions.len());
+    for ix in &instructions {
+        if ix.program_id == SPL_TOKEN_2022_ID
+            && matches!(
+                TokenInstruction::unpack(&ix.data),
+                Ok(TokenInstruction::InitializeImmutableOwner)
+            )
+        {
+            immutable_owners.extend(ix.accounts.first().map(|meta| meta.pubkey));
+        }
+        inits.push(TokenAccountInit::parse(ix, &immutable_owners));
+    }
+
+    let mut pubkeys = inits
+        .iter()
+        .flatten()
+        .flat_map(|init| {
+            [
+                init.token_account,
+                init.mint,
+                MintConfig::find_pda(&init.mint).0,
+            ]
+        })
+        .collect::<Vec<_>>();
+    pubkeys.sort();
+    pubkeys.dedup();
+    fetcher.prefetch(&pubkeys).await?;
+
+    let mut thaw_required = HashMap::new();
+    let mut augmented = Vec::with_capacity(instructions.len());
+    for (ix, init) in instructions.into_iter().zip(inits) {
+        augmented.push(ix);
+        let Some(init) = init else {
+            continue;
+        };
+
+        let required = match thaw_required.get(&init.mint) {
+            Some(required) => *required,
+            None => {
+                let required = requires_permissionless_thaw(&init.mint, &fetcher).await?;
+                thaw_required.insert(init.mint, required);
+                required
+            }
+        };
+        if !required {
+            continue;
+        }
+
+        let mut overlay = OverlayAccountFetcher::new(&fetcher);
+        if fetcher
+            .get_account_data(init.token_account)
+            .await?
+            .is_none()
+        {
+            // the mint exists, otherwise no thaw would be required
+            let mint_data = fetcher
+                .get_account_data(init.mint)
+                .await?
+                .unwrap_or_default();
+            overlay = overlay.with_account(
+                init.token_account,
+                mock_token_account_data(
+                    &init.mint,
+                    &mint_data,
+                    &init.owner,
+                    &init.extension_types,
+                )?,
+            );
+        }
+
+        augmented.push(
+            create_thaw_permissionless_instruction_with_extra_metas(
+                signer_pubkey,
+                &init.token_account,
+                &init.mint,
+                &MintConfig::find_pda(&init.mint).0,
+                &SPL_TOKEN_2022_ID,
+                &init.owner,
+                true,
+                overlay,
+            )
+            .await?,
+        );
+    }
+
+    Ok(augmented)
+}
+
+async fn requires_permissionless_thaw<A>(
+    mint_pubkey: &Pubkey,
+    fetcher: &CachedAccountFetcher<A>,
+) -> Result<bool, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    // mints created in the same transaction cannot be token-acl mints yet
+    if fetcher.get_account_data(*mint_pubkey).await?.is_none() {
+        return Ok(false);
+    }
+
+    let status = get_mint_acl_status(mint_pubkey, fetcher).await?;
+    Ok(status.uses_token_acl() && status.requires_thaw() && status.permissionless_thaw_enabled)
+}
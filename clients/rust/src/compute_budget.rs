@@ -0,0 +1,244 @@
+#[cfg(feature = "fetch")]
+use std::collections::HashSet;
+
+#[cfg(feature = "fetch")]
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+use solana_compute_budget_interface::{ComputeBudgetInstruction, ID as COMPUTE_BUDGET_ID};
+use solana_instruction::Instruction;
+#[cfg(feature = "fetch")]
+use solana_message::{AddressLookupTableAccount, CompileError, Hash, Message, VersionedMessage};
+use solana_pubkey::Pubkey;
+#[cfg(feature = "fetch")]
+use solana_transaction::{versioned::VersionedTransaction, Signature};
+
+#[cfg(feature = "fetch")]
+use crate::{create_v0_message, simulate_transaction, PreflightFailure};
+
+/// Most compute units a transaction may request.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Compute units added on top of the simulated consumption, in percent, as
+/// gates may take another path once the transaction lands.
+pub const DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT: u64 = 20;
+
+/// Most writable accounts `getRecentPrioritizationFees` accepts.
+#[cfg(feature = "fetch")]
+const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// How to price the compute units of a transaction.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PriorityFee {
+    /// No priority fee.
+    #[default]
+    None,
+    /// Fixed price, in micro-lamports per compute unit.
+    Fixed(u64),
+    /// `percentile`, from 0 to 100, of the prices recently paid to lock the
+    /// writable accounts of the transaction, capped at `max`.
+    RecentPercentile { percentile: u8, max: u64 },
+}
+
+impl PriorityFee {
+    /// Compute unit price to set given the `recent_fees` paid, `None` when
+    /// there is nothing to pay.
+    pub fn compute_unit_price(&self, recent_fees: &[u64]) -> Option<u64> {
+        match *self {
+            PriorityFee::None => None,
+            PriorityFee::Fixed(price) => Some(price),
+            PriorityFee::RecentPercentile { percentile, max } => {
+                let mut fees = recent_fees.to_vec();
+                fees.sort_unstable();
+                let index = (fees.len().checked_sub(1)?) * percentile.min(100) as usize / 100;
+                Some(fees[index].min(max)).filter(|price| *price > 0)
+            }
+        }
+    }
+}
+
+/// Compute units a simulated transaction consumed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComputeUnitEstimate {
+    pub units_consumed: u64,
+    /// Units each instruction consumed, `None` for the builtin programs,
+    /// which do not log it.
+    pub instruction_units_consumed: Vec<Option<u64>>,
+}
+
+impl ComputeUnitEstimate {
+    /// Compute unit limit covering the consumption plus `margin_percent`.
+    pub fn compute_unit_limit(&self, margin_percent: u64) -> u32 {
+        compute_unit_limit_with_margin(self.units_consumed, margin_percent)
+    }
+}
+
+/// `units_consumed` plus `margin_percent`, capped at
+/// [`MAX_COMPUTE_UNIT_LIMIT`].
+pub fn compute_unit_limit_with_margin(units_consumed: u64, margin_percent: u64) -> u32 {
+    let units = units_consumed.saturating_add(units_consumed.saturating_mul(margin_percent) / 100);
+    units.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+}
+
+/// Units each top-level instruction calling `program_ids` consumed, read from
+/// the `logs` of its transaction.
+pub fn instruction_units_consumed(program_ids: &[Pubkey], logs: &[String]) -> Vec<Option<u64>> {
+    let instruction_logs = crate::preflight::split_instruction_logs(logs);
+
+    program_ids
+        .iter()
+        .enumerate()
+        .map(|(index, program_id)| {
+            let prefix = format!("Program {program_id} consumed ");
+            // inner calls to the same program log before the instruction does
+            instruction_logs.get(index)?.iter().rev().find_map(|log| {
+                log.strip_prefix(&prefix)?
+                    .split_once(" of ")?
+                    .0
+                    .parse()
+                    .ok()
+            })
+        })
+        .collect()
+}
+
+/// Sets the compute unit limit and price of `instructions`, replacing the
+/// `ComputeBudget` instructions that already set them and prepending the
+/// others.
+pub fn set_compute_budget_instructions(
+    instructions: &[Instruction],
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+) -> Vec<Instruction> {
+    let mut instructions = instructions.to_vec();
+    let budget_ixs = [
+        compute_unit_price.map(ComputeBudgetInstruction::set_compute_unit_price),
+        compute_unit_limit.map(ComputeBudgetInstruction::set_compute_unit_limit),
+    ];
+
+    for budget_ix in budget_ixs.into_iter().flatten() {
+        let existing = instructions.iter_mut().find(|ix| {
+            ix.program_id == COMPUTE_BUDGET_ID && ix.data.first() == budget_ix.data.first()
+        });
+        match existing {
+            Some(ix) => *ix = budget_ix,
+            None => instructions.insert(0, budget_ix),
+        }
+    }
+
+    instructions
+}
+
+/// Why the compute budget of a transaction could not be estimated.
+#[cfg(feature = "fetch")]
+#[derive(Debug, thiserror::Error)]
+pub enum ComputeBudgetError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error(transparent)]
+    Compile(#[from] CompileError),
+    #[error("{0}")]
+    Simulation(Box<PreflightFailure>),
+}
+
+/// Simulates a transaction of `instructions` paid by `payer_pubkey`, loading
+/// accounts from `lookup_tables`, with the highest compute unit limit to
+/// measure what it consumes.
+#[cfg(feature = "fetch")]
+pub async fn estimate_compute_units(
+    rpc: &RpcClient,
+    payer_pubkey: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<ComputeUnitEstimate, ComputeBudgetError> {
+    let simulated_ixs =
+        set_compute_budget_instructions(instructions, Some(MAX_COMPUTE_UNIT_LIMIT), None);
+    // the simulation replaces the blockhash
+    let message = if lookup_tables.is_empty() {
+        VersionedMessage::Legacy(Message::new(&simulated_ixs, Some(payer_pubkey)))
+    } else {
+        create_v0_message(payer_pubkey, &simulated_ixs, lookup_tables, Hash::default())?
+    };
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header().num_required_signatures as usize],
+        message,
+    };
+
+    let preflight = simulate_transaction(rpc, &transaction).await?;
+    if let Some(failure) = preflight.failure {
+        return Err(ComputeBudgetError::Simulation(Box::new(failure)));
+    }
+
+    let program_ids = simulated_ixs
+        .iter()
+        .map(|ix| ix.program_id)
+        .collect::<Vec<_>>();
+    let mut instruction_units_consumed = instruction_units_consumed(&program_ids, &preflight.logs);
+    // drop the limit prepended for the simulation
+    instruction_units_consumed.drain(..simulated_ixs.len() - instructions.len());
+
+    Ok(ComputeUnitEstimate {
+        units_consumed: preflight
+            .units_consumed
+            .unwrap_or_else(|| instruction_units_consumed.iter().flatten().sum()),
+        instruction_units_consumed,
+    })
+}
+
+/// Compute unit price of a transaction of `instructions` under
+/// `priority_fee`, looking up the prices recently paid to lock its writable
+/// accounts when needed.
+#[cfg(feature = "fetch")]
+pub async fn get_compute_unit_price(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    priority_fee: PriorityFee,
+) -> Result<Option<u64>, ClientError> {
+    let PriorityFee::RecentPercentile { .. } = priority_fee else {
+        return Ok(priority_fee.compute_unit_price(&[]));
+    };
+
+    let mut seen = HashSet::new();
+    let writable_accounts = instructions
+        .iter()
+        .flat_map(|ix| &ix.accounts)
+        .filter(|meta| meta.is_writable && seen.insert(meta.pubkey))
+        .map(|meta| meta.pubkey)
+        .take(MAX_PRIORITIZATION_FEE_ACCOUNTS)
+        .collect::<Vec<_>>();
+    let recent_fees = rpc
+        .get_recent_prioritization_fees(&writable_accounts)
+        .await?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect::<Vec<_>>();
+
+    Ok(priority_fee.compute_unit_price(&recent_fees))
+}
+
+/// Attaches the `ComputeBudget` instructions to `instructions`: a limit
+/// simulated with `compute_unit_margin_percent` on top, unless it is `None`,
+/// and the price `priority_fee` gives.
+#[cfg(feature = "fetch")]
+pub async fn add_compute_budget_instructions(
+    rpc: &RpcClient,
+    payer_pubkey: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    compute_unit_margin_percent: Option<u64>,
+    priority_fee: PriorityFee,
+) -> Result<Vec<Instruction>, ComputeBudgetError> {
+    let compute_unit_limit = match compute_unit_margin_percent {
+        Some(margin_percent) => Some(
+            estimate_compute_units(rpc, payer_pubkey, instructions, lookup_tables)
+                .await?
+                .compute_unit_limit(margin_percent),
+        ),
+        None => None,
+    };
+    let compute_unit_price = get_compute_unit_price(rpc, instructions, priority_fee).await?;
+
+    Ok(set_compute_budget_instructions(
+        instructions,
+        compute_unit_limit,
+        compute_unit_price,
+    ))
+}
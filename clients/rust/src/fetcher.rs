@@ -0,0 +1,65 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_pubkey::Pubkey;
+use token_acl_interface::offchain::{AccountDataResult, AccountFetchError, AccountFetcher};
+
+/// Most accounts a `getMultipleAccounts` request may ask for.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// [`AccountFetcher`] backed by an RPC node.
+///
+/// Missing accounts are reported as `None`, and batches go through
+/// `getMultipleAccounts`, split into requests of [`MAX_MULTIPLE_ACCOUNTS`].
+pub struct RpcAccountFetcher<'a> {
+    rpc: &'a RpcClient,
+}
+
+impl<'a> RpcAccountFetcher<'a> {
+    pub fn new(rpc: &'a RpcClient) -> Self {
+        Self { rpc }
+    }
+}
+
+impl AccountFetcher for RpcAccountFetcher<'_> {
+    async fn get_account_data(&self, pubkey: Pubkey) -> AccountDataResult {
+        let mut accounts = self.get_multiple_account_data(&[pubkey]).await?;
+        Ok(accounts.pop().flatten())
+    }
+
+    async fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, AccountFetchError> {
+        let mut accounts = Vec::with_capacity(pubkeys.len());
+        for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let chunk_accounts = self.rpc.get_multiple_accounts(chunk).await?;
+            accounts.extend(
+                chunk_accounts
+                    .into_iter()
+                    .map(|account| account.map(|account| account.data)),
+            );
+        }
+        Ok(accounts)
+    }
+
+    async fn get_account_owner(&self, pubkey: Pubkey) -> Result<Option<Pubkey>, AccountFetchError> {
+        let mut accounts = self.rpc.get_multiple_accounts(&[pubkey]).await?;
+        Ok(accounts.pop().flatten().map(|account| account.owner))
+    }
+}
+
+impl AccountFetcher for &RpcAccountFetcher<'_> {
+    async fn get_account_data(&self, pubkey: Pubkey) -> AccountDataResult {
+        (*self).get_account_data(pubkey).await
+    }
+
+    async fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, AccountFetchError> {
+        (*self).get_multiple_account_data(pubkeys).await
+    }
+
+    async fn get_account_owner(&self, pubkey: Pubkey) -> Result<Option<Pubkey>, AccountFetchError> {
+        (*self).get_account_owner(pubkey).await
+    }
+}
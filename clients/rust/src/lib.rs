@@ -1,29 +1,48 @@
+mod augment;
+mod compute_budget;
+#[cfg(feature = "fetch")]
+mod fetcher;
+#[allow(clippy::io_other_error)]
 mod generated;
+mod lookup_table;
 mod metadata;
-use std::future::Future;
+mod mock;
+mod preflight;
+mod status;
+mod transfer;
 
+pub use augment::*;
+pub use compute_budget::*;
+#[cfg(feature = "fetch")]
+pub use fetcher::*;
 pub use generated::*;
+pub use lookup_table::*;
 pub use metadata::*;
+pub use mock::*;
+pub use preflight::*;
+pub use status::*;
+pub use transfer::*;
 
 #[cfg(feature = "fetch")]
 use solana_client::nonblocking;
 use solana_instruction::Instruction;
 use solana_program_error::ProgramError;
-use solana_program_option::COption;
-use solana_program_pack::Pack;
 use solana_pubkey::Pubkey;
 use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
 use spl_associated_token_account_interface::instruction::{
     create_associated_token_account, create_associated_token_account_idempotent,
 };
 pub use spl_tlv_account_resolution::state::{AccountDataResult, AccountFetchError};
-use spl_token_2022_interface::state::{Account, AccountState};
-use spl_token_2022_interface::ID as SPL_TOKEN_2022_ID;
+use spl_token_2022_interface::extension::StateWithExtensions;
+use spl_token_2022_interface::state::Account;
+pub use token_acl_interface::offchain::{
+    AccountFetcher, CachedAccountFetcher, OverlayAccountFetcher,
+};
 
 use crate::generated::errors::token_acl::TokenAclError;
 
 #[allow(clippy::too_many_arguments)]
-pub async fn create_thaw_permissionless_instruction_with_extra_metas<F, Fut>(
+pub async fn create_thaw_permissionless_instruction_with_extra_metas<A>(
     signer_pubkey: &Pubkey,
     token_account_pubkey: &Pubkey,
     mint_pubkey: &Pubkey,
@@ -31,13 +50,13 @@ pub async fn create_thaw_permissionless_instruction_with_extra_metas<F, Fut>(
     token_program_pubkey: &Pubkey,
     token_account_owner_pubkey: &Pubkey,
     idempotent: bool,
-    fetch_account_data_fn: F,
+    fetcher: A,
 ) -> Result<Instruction, AccountFetchError>
 where
-    F: Fn(Pubkey) -> Fut,
-    Fut: Future<Output = AccountDataResult>,
+    A: AccountFetcher,
 {
-    let mint_config = fetch_account_data_fn(*mint_config_pubkey)
+    let mint_config = fetcher
+        .get_account_data(*mint_config_pubkey)
         .await?
         .and_then(|data| crate::accounts::MintConfig::from_bytes(&data).ok())
         .ok_or(ProgramError::InvalidAccountData)?;
@@ -83,7 +102,7 @@ where
             mint_pubkey,
             token_account_owner_pubkey,
             &flag_account,
-            fetch_account_data_fn,
+            fetcher,
         )
         .await?;
     }
@@ -92,7 +111,7 @@ where
 }
 
 #[allow(clippy::too_many_arguments)]
-pub async fn create_freeze_permissionless_instruction_with_extra_metas<F, Fut>(
+pub async fn create_freeze_permissionless_instruction_with_extra_metas<A>(
     signer_pubkey: &Pubkey,
     token_account_pubkey: &Pubkey,
     mint_pubkey: &Pubkey,
@@ -100,13 +119,13 @@ pub async fn create_freeze_permissionless_instruction_with_extra_metas<F, Fut>(
     token_program_pubkey: &Pubkey,
     token_account_owner_pubkey: &Pubkey,
     idempotent: bool,
-    fetch_account_data_fn: F,
+    fetcher: A,
 ) -> Result<Instruction, AccountFetchError>
 where
-    F: Fn(Pubkey) -> Fut,
-    Fut: Future<Output = AccountDataResult>,
+    A: AccountFetcher,
 {
-    let mint_config = fetch_account_data_fn(*mint_config_pubkey)
+    let mint_config = fetcher
+        .get_account_data(*mint_config_pubkey)
         .await?
         .and_then(|data| crate::accounts::MintConfig::from_bytes(&data).ok())
         .ok_or(ProgramError::InvalidAccountData)?;
@@ -115,7 +134,7 @@ where
         return Err(TokenAclError::PermissionlessFreezeNotEnabled.into());
     }
 
-    let flag_account = crate::accounts::FlagAccount::find_pda(&token_account_pubkey).0;
+    let flag_account = crate::accounts::FlagAccount::find_pda(token_account_pubkey).0;
 
     let mut ix = if idempotent {
         crate::instructions::FreezePermissionlessIdempotentBuilder::new()
@@ -152,7 +171,7 @@ where
             mint_pubkey,
             token_account_owner_pubkey,
             &flag_account,
-            fetch_account_data_fn,
+            fetcher,
         )
         .await?;
     }
@@ -160,6 +179,162 @@ where
     Ok(ix)
 }
 
+/// Builds a permissionless thaw of `token_account`, reading its mint, owner
+/// and token program from the account and deriving the mint config.
+pub async fn create_thaw_permissionless_instruction_for_token_account<A>(
+    signer_pubkey: &Pubkey,
+    token_account_pubkey: &Pubkey,
+    idempotent: bool,
+    fetcher: A,
+) -> Result<Instruction, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    let token_program_pubkey = fetcher
+        .get_account_owner(*token_account_pubkey)
+        .await?
+        .ok_or(ProgramError::UninitializedAccount)?;
+    let (mint_pubkey, owner_pubkey) =
+        get_token_account_mint_and_owner(&fetcher, token_account_pubkey).await?;
+
+    create_thaw_permissionless_instruction_with_extra_metas(
+        signer_pubkey,
+        token_account_pubkey,
+        &mint_pubkey,
+        &crate::accounts::MintConfig::find_pda(&mint_pubkey).0,
+        &token_program_pubkey,
+        &owner_pubkey,
+        idempotent,
+        fetcher,
+    )
+    .await
+}
+
+/// Builds a permissionless thaw of the associated token account of `owner`
+/// for `mint`, deriving everything else, the token program from the owner of
+/// the mint.
+///
+/// The token account is not fetched, so it may be created earlier in the same
+/// transaction, see [`create_ata_and_thaw_permissionless_instructions`].
+pub async fn create_thaw_permissionless_instruction_for_mint_and_owner<A>(
+    signer_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    owner_pubkey: &Pubkey,
+    idempotent: bool,
+    fetcher: A,
+) -> Result<Instruction, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    let token_program_pubkey = get_mint_token_program(&fetcher, mint_pubkey).await?;
+    create_thaw_permissionless_instruction_with_extra_metas(
+        signer_pubkey,
+        &get_associated_token_address_with_program_id(
+            owner_pubkey,
+            mint_pubkey,
+            &token_program_pubkey,
+        ),
+        mint_pubkey,
+        &crate::accounts::MintConfig::find_pda(mint_pubkey).0,
+        &token_program_pubkey,
+        owner_pubkey,
+        idempotent,
+        fetcher,
+    )
+    .await
+}
+
+/// Builds a permissionless freeze of `token_account`, reading its mint, owner
+/// and token program from the account and deriving the mint config.
+pub async fn create_freeze_permissionless_instruction_for_token_account<A>(
+    signer_pubkey: &Pubkey,
+    token_account_pubkey: &Pubkey,
+    idempotent: bool,
+    fetcher: A,
+) -> Result<Instruction, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    let token_program_pubkey = fetcher
+        .get_account_owner(*token_account_pubkey)
+        .await?
+        .ok_or(ProgramError::UninitializedAccount)?;
+    let (mint_pubkey, owner_pubkey) =
+        get_token_account_mint_and_owner(&fetcher, token_account_pubkey).await?;
+
+    create_freeze_permissionless_instruction_with_extra_metas(
+        signer_pubkey,
+        token_account_pubkey,
+        &mint_pubkey,
+        &crate::accounts::MintConfig::find_pda(&mint_pubkey).0,
+        &token_program_pubkey,
+        &owner_pubkey,
+        idempotent,
+        fetcher,
+    )
+    .await
+}
+
+/// Builds a permissionless freeze of the associated token account of `owner`
+/// for `mint`, deriving everything else, the token program from the owner of
+/// the mint.
+pub async fn create_freeze_permissionless_instruction_for_mint_and_owner<A>(
+    signer_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    owner_pubkey: &Pubkey,
+    idempotent: bool,
+    fetcher: A,
+) -> Result<Instruction, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    let token_program_pubkey = get_mint_token_program(&fetcher, mint_pubkey).await?;
+    create_freeze_permissionless_instruction_with_extra_metas(
+        signer_pubkey,
+        &get_associated_token_address_with_program_id(
+            owner_pubkey,
+            mint_pubkey,
+            &token_program_pubkey,
+        ),
+        mint_pubkey,
+        &crate::accounts::MintConfig::find_pda(mint_pubkey).0,
+        &token_program_pubkey,
+        owner_pubkey,
+        idempotent,
+        fetcher,
+    )
+    .await
+}
+
+async fn get_token_account_mint_and_owner<A>(
+    fetcher: &A,
+    token_account_pubkey: &Pubkey,
+) -> Result<(Pubkey, Pubkey), AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    let data = fetcher
+        .get_account_data(*token_account_pubkey)
+        .await?
+        .ok_or(ProgramError::UninitializedAccount)?;
+    let token_account = StateWithExtensions::<Account>::unpack(&data)?;
+
+    Ok((token_account.base.mint, token_account.base.owner))
+}
+
+async fn get_mint_token_program<A>(
+    fetcher: &A,
+    mint_pubkey: &Pubkey,
+) -> Result<Pubkey, AccountFetchError>
+where
+    A: AccountFetcher + ?Sized,
+{
+    Ok(fetcher
+        .get_account_owner(*mint_pubkey)
+        .await?
+        .ok_or(Into::<ProgramError>::into(TokenAclError::InvalidTokenMint))?)
+}
+
 #[cfg(feature = "fetch")]
 pub async fn create_ata_and_thaw_permissionless(
     rpc: &nonblocking::rpc_client::RpcClient,
@@ -168,76 +343,55 @@ pub async fn create_ata_and_thaw_permissionless(
     token_account_owner_pubkey: &Pubkey,
     idempotent: bool,
 ) -> Result<Vec<Instruction>, AccountFetchError> {
-    let fetch_account_data_fn = |pubkey: Pubkey| async move {
-        rpc.get_account_data(&pubkey)
-            .await
-            .map(|data| Some(data.to_vec()))
-            .map_err(Into::<AccountFetchError>::into)
-    };
-
+    let fetcher = RpcAccountFetcher::new(rpc);
     create_ata_and_thaw_permissionless_instructions(
         payer_pubkey,
         mint_pubkey,
-        &SPL_TOKEN_2022_ID,
+        &get_mint_token_program(&fetcher, mint_pubkey).await?,
         token_account_owner_pubkey,
         idempotent,
-        &fetch_account_data_fn,
+        &fetcher,
     )
     .await
 }
 
 #[allow(clippy::too_many_arguments)]
-pub async fn create_ata_and_thaw_permissionless_instructions<F, Fut>(
+pub async fn create_ata_and_thaw_permissionless_instructions<A>(
     payer_pubkey: &Pubkey,
     mint_pubkey: &Pubkey,
     token_program_pubkey: &Pubkey,
     token_account_owner_pubkey: &Pubkey,
     idempotent: bool,
-    fetch_account_data_fn: &F,
+    fetcher: &A,
 ) -> Result<Vec<Instruction>, AccountFetchError>
 where
-    F: Fn(Pubkey) -> Fut,
-    Fut: Future<Output = AccountDataResult>,
+    A: AccountFetcher + ?Sized,
 {
     let token_account = get_associated_token_address_with_program_id(
-        &token_account_owner_pubkey,
-        &mint_pubkey,
-        &SPL_TOKEN_2022_ID,
+        token_account_owner_pubkey,
+        mint_pubkey,
+        token_program_pubkey,
     );
 
     let ix = if idempotent {
         create_associated_token_account_idempotent(
-            &payer_pubkey,
-            &token_account_owner_pubkey,
-            &mint_pubkey,
-            &SPL_TOKEN_2022_ID,
+            payer_pubkey,
+            token_account_owner_pubkey,
+            mint_pubkey,
+            token_program_pubkey,
         )
     } else {
         create_associated_token_account(
-            &payer_pubkey,
-            &token_account_owner_pubkey,
-            &mint_pubkey,
-            &SPL_TOKEN_2022_ID,
+            payer_pubkey,
+            token_account_owner_pubkey,
+            mint_pubkey,
+            token_program_pubkey,
         )
     };
     let mut instructions = vec![ix];
 
-    // assume account doesn't exist, so we mock it
-    let acc = Account {
-        mint: *mint_pubkey,
-        owner: *token_account_owner_pubkey,
-        amount: 0,
-        delegate: COption::None,
-        state: AccountState::Frozen,
-        is_native: COption::None,
-        delegated_amount: 0,
-        close_authority: COption::None,
-    };
-
-    let mut data = vec![0u8; Account::LEN];
-    Account::pack(acc, &mut data)?;
-
-    let mint_data = fetch_account_data_fn(*mint_pubkey)
+    let mint_data = fetcher
+        .get_account_data(*mint_pubkey)
         .await?
         .ok_or(Into::<ProgramError>::into(TokenAclError::InvalidTokenMint))?;
 
@@ -280,16 +434,15 @@ where
             mint_pubkey,
             token_account_owner_pubkey,
             &flag_account,
-            |pubkey| {
-                let data = data.clone();
-                async move {
-                    if pubkey == token_account {
-                        return Ok(Some(data));
-                    }
-                    let data = fetch_account_data_fn(pubkey).await.unwrap_or(None);
-                    Ok(data)
-                }
-            },
+            // assume account doesn't exist, so we mock it
+            OverlayAccountFetcher::new(fetcher).with_account(
+                token_account,
+                mock_associated_token_account_data(
+                    mint_pubkey,
+                    &mint_data,
+                    token_account_owner_pubkey,
+                )?,
+            ),
         )
         .await?;
 
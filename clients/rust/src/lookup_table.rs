@@ -0,0 +1,193 @@
+use std::{collections::HashSet, time::Duration};
+
+use solana_address_lookup_table_interface::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::AddressLookupTable,
+};
+use solana_instruction::Instruction;
+use solana_message::{v0, AddressLookupTableAccount, CompileError, Hash, VersionedMessage};
+use solana_program_error::ProgramError;
+use solana_pubkey::Pubkey;
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_token_2022_interface::ID as SPL_TOKEN_2022_ID;
+use spl_type_length_value::state::TlvStateBorrowed;
+use token_acl_interface::{
+    error::ThawFreezeGateError,
+    get_freeze_extra_account_metas_address, get_thaw_extra_account_metas_address,
+    instruction::{CanFreezePermissionlessInstruction, CanThawPermissionlessInstruction},
+    offchain::{AccountFetchError, AccountFetcher, CachedAccountFetcher},
+};
+
+use crate::{accounts::MintConfig, programs::TOKEN_ACL_ID};
+
+/// Most addresses one `ExtendLookupTable` instruction adds, so that its
+/// transaction stays under the size limit.
+pub const MAX_EXTEND_LOOKUP_TABLE_ADDRESSES: usize = 20;
+
+/// Accounts of the permissionless thaws and freezes of `mint_pubkey` that are
+/// the same for every token account: the token-acl program, the mint, its
+/// `MintConfig`, the token and system programs, the gate, its extra metas
+/// accounts and the fixed addresses among their extra metas.
+pub async fn get_mint_lookup_table_addresses<A>(
+    mint_pubkey: &Pubkey,
+    fetcher: A,
+) -> Result<Vec<Pubkey>, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    // only lives for this call, so nothing expires
+    let fetcher = CachedAccountFetcher::new(fetcher, Duration::MAX);
+
+    let mint_config_pubkey = MintConfig::find_pda(mint_pubkey).0;
+    let mint_config_data = fetcher
+        .get_account_data(mint_config_pubkey)
+        .await?
+        .ok_or(ThawFreezeGateError::InvalidTokenMint)?;
+    let mint_config = MintConfig::from_bytes(&mint_config_data)
+        .map_err(|_| ThawFreezeGateError::InvalidTokenMint)?;
+
+    let mut addresses = vec![
+        TOKEN_ACL_ID,
+        *mint_pubkey,
+        mint_config_pubkey,
+        SPL_TOKEN_2022_ID,
+        solana_system_interface::program::ID,
+    ];
+    if mint_config.gating_program == Pubkey::default() {
+        return Ok(addresses);
+    }
+    addresses.push(mint_config.gating_program);
+
+    let thaw_extra_metas =
+        get_thaw_extra_account_metas_address(mint_pubkey, &mint_config.gating_program);
+    let freeze_extra_metas =
+        get_freeze_extra_account_metas_address(mint_pubkey, &mint_config.gating_program);
+    fetcher
+        .prefetch(&[thaw_extra_metas, freeze_extra_metas])
+        .await?;
+
+    if let Some(data) = fetcher.get_account_data(thaw_extra_metas).await? {
+        addresses.push(thaw_extra_metas);
+        addresses.extend(fixed_extra_metas::<CanThawPermissionlessInstruction>(
+            &data,
+        )?);
+    }
+    if let Some(data) = fetcher.get_account_data(freeze_extra_metas).await? {
+        addresses.push(freeze_extra_metas);
+        addresses.extend(fixed_extra_metas::<CanFreezePermissionlessInstruction>(
+            &data,
+        )?);
+    }
+
+    let mut seen = HashSet::new();
+    addresses.retain(|address| seen.insert(*address));
+    Ok(addresses)
+}
+
+/// Addresses of the extra metas of `data` given as a plain pubkey rather than
+/// derived from the instruction.
+fn fixed_extra_metas<T: SplDiscriminate>(data: &[u8]) -> Result<Vec<Pubkey>, ProgramError> {
+    let state = TlvStateBorrowed::unpack(data)?;
+    Ok(ExtraAccountMetaList::unpack_with_tlv_state::<T>(&state)?
+        .iter()
+        .filter(|meta| meta.discriminator == 0)
+        .map(|meta| Pubkey::new_from_array(meta.address_config))
+        .collect())
+}
+
+/// Reads the lookup table at `lookup_table_pubkey`.
+pub async fn get_lookup_table_account<A>(
+    lookup_table_pubkey: &Pubkey,
+    fetcher: A,
+) -> Result<AddressLookupTableAccount, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    let data = fetcher
+        .get_account_data(*lookup_table_pubkey)
+        .await?
+        .ok_or(ProgramError::UninitializedAccount)?;
+    let lookup_table =
+        AddressLookupTable::deserialize(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    Ok(AddressLookupTableAccount {
+        key: *lookup_table_pubkey,
+        addresses: lookup_table.addresses.to_vec(),
+    })
+}
+
+/// Instructions creating a lookup table of `authority_pubkey` that holds
+/// `addresses`, and the address of the table.
+///
+/// `recent_slot` must be a recent slot, it seeds the table address. The
+/// first instruction creates the table and each of the others adds up to
+/// [`MAX_EXTEND_LOOKUP_TABLE_ADDRESSES`] addresses. The creation and the first
+/// extension fit in one transaction, each further extension needs its own.
+pub fn create_lookup_table_instructions(
+    authority_pubkey: &Pubkey,
+    payer_pubkey: &Pubkey,
+    recent_slot: u64,
+    addresses: &[Pubkey],
+) -> (Pubkey, Vec<Instruction>) {
+    let (ix, lookup_table_pubkey) =
+        create_lookup_table(*authority_pubkey, *payer_pubkey, recent_slot);
+
+    let mut instructions = vec![ix];
+    instructions.extend(extend_lookup_table_instructions(
+        &AddressLookupTableAccount {
+            key: lookup_table_pubkey,
+            addresses: vec![],
+        },
+        authority_pubkey,
+        payer_pubkey,
+        addresses,
+    ));
+
+    (lookup_table_pubkey, instructions)
+}
+
+/// Instructions adding the `addresses` that `lookup_table` does not hold yet,
+/// up to [`MAX_EXTEND_LOOKUP_TABLE_ADDRESSES`] per instruction.
+pub fn extend_lookup_table_instructions(
+    lookup_table: &AddressLookupTableAccount,
+    authority_pubkey: &Pubkey,
+    payer_pubkey: &Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    let mut new_addresses = addresses
+        .iter()
+        .filter(|address| !lookup_table.addresses.contains(address))
+        .copied()
+        .collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    new_addresses.retain(|address| seen.insert(*address));
+
+    new_addresses
+        .chunks(MAX_EXTEND_LOOKUP_TABLE_ADDRESSES)
+        .map(|chunk| {
+            extend_lookup_table(
+                lookup_table.key,
+                *authority_pubkey,
+                Some(*payer_pubkey),
+                chunk.to_vec(),
+            )
+        })
+        .collect()
+}
+
+/// Compiles `instructions` into a v0 message paid by `payer_pubkey` that
+/// loads the accounts it can from `lookup_tables`.
+pub fn create_v0_message(
+    payer_pubkey: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedMessage, CompileError> {
+    Ok(VersionedMessage::V0(v0::Message::try_compile(
+        payer_pubkey,
+        instructions,
+        lookup_tables,
+        recent_blockhash,
+    )?))
+}
@@ -41,7 +41,7 @@ pub fn get_gating_program_from_mint_data(data: &[u8]) -> Result<Pubkey, ThawFree
         .map(|(_, val)| val)
         .ok_or(ThawFreezeGateError::InvalidTokenMint)?;
 
-    Pubkey::from_str(&gating_program).map_err(|_| ThawFreezeGateError::InvalidTokenMint)
+    Pubkey::from_str(gating_program).map_err(|_| ThawFreezeGateError::InvalidTokenMint)
 }
 
 #[cfg(feature = "fetch")]
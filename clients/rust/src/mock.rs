@@ -0,0 +1,66 @@
+use solana_program_error::ProgramError;
+use solana_pubkey::Pubkey;
+use spl_token_2022_interface::{
+    extension::{
+        default_account_state::DefaultAccountState, BaseStateWithExtensions,
+        BaseStateWithExtensionsMut, ExtensionType, StateWithExtensions, StateWithExtensionsMut,
+    },
+    state::{Account, AccountState, Mint},
+};
+
+/// Data of the token account Token-2022 would create for the mint of
+/// `mint_data`, to resolve extra metas before the account exists.
+///
+/// `extension_types` are the extensions initialized before `InitializeAccount`,
+/// e.g. `ImmutableOwner` for associated token accounts. They and the account
+/// extensions the mint requires are initialized in the order Token-2022 does,
+/// so extension offsets match the real account.
+pub fn mock_token_account_data(
+    mint_pubkey: &Pubkey,
+    mint_data: &[u8],
+    owner_pubkey: &Pubkey,
+    extension_types: &[ExtensionType],
+) -> Result<Vec<u8>, ProgramError> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    let required_extension_types =
+        ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+
+    let len = ExtensionType::try_calculate_account_len::<Account>(
+        &[extension_types, &required_extension_types].concat(),
+    )?;
+    let mut data = vec![0u8; len];
+    let mut account = StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut data)?;
+    for extension_type in extension_types.iter().chain(&required_extension_types) {
+        account.init_account_extension_from_type(*extension_type)?;
+    }
+
+    account.base = Account {
+        mint: *mint_pubkey,
+        owner: *owner_pubkey,
+        state: match mint.get_extension::<DefaultAccountState>() {
+            Ok(default_account_state) => AccountState::try_from(default_account_state.state)
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+            Err(_) => AccountState::Initialized,
+        },
+        ..Account::default()
+    };
+    account.pack_base();
+    account.init_account_type()?;
+
+    Ok(data)
+}
+
+/// Data of the associated token account of `owner_pubkey` for the mint of
+/// `mint_data`, as the associated token account program would create it.
+pub fn mock_associated_token_account_data(
+    mint_pubkey: &Pubkey,
+    mint_data: &[u8],
+    owner_pubkey: &Pubkey,
+) -> Result<Vec<u8>, ProgramError> {
+    mock_token_account_data(
+        mint_pubkey,
+        mint_data,
+        owner_pubkey,
+        &[ExtensionType::ImmutableOwner],
+    )
+}
@@ -0,0 +1,254 @@
+use std::fmt;
+
+use num_traits::FromPrimitive;
+#[cfg(feature = "fetch")]
+use solana_client::{
+    client_error::ClientError, nonblocking::rpc_client::RpcClient,
+    rpc_config::RpcSimulateTransactionConfig,
+};
+use solana_instruction::error::InstructionError;
+#[cfg(feature = "fetch")]
+use solana_instruction::Instruction;
+#[cfg(feature = "fetch")]
+use solana_message::Message;
+use solana_pubkey::Pubkey;
+#[cfg(feature = "fetch")]
+use solana_transaction::{versioned::VersionedTransaction, Transaction};
+use solana_transaction_error::TransactionError;
+use spl_token_2022_interface::{error::TokenError, ID as SPL_TOKEN_2022_ID};
+use token_acl_interface::error::GateRejection;
+
+use crate::{errors::TokenAclError, programs::TOKEN_ACL_ID};
+
+/// Program a simulated transaction failed in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FailedProgram {
+    TokenAcl,
+    /// Gate program token-acl called.
+    Gate(Pubkey),
+    Token2022,
+    Other(Pubkey),
+}
+
+impl fmt::Display for FailedProgram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailedProgram::TokenAcl => write!(f, "token-acl"),
+            FailedProgram::Gate(program_id) => write!(f, "gate {program_id}"),
+            FailedProgram::Token2022 => write!(f, "token-2022"),
+            FailedProgram::Other(program_id) => write!(f, "program {program_id}"),
+        }
+    }
+}
+
+/// Why a simulated transaction failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreflightFailure {
+    pub error: TransactionError,
+    /// Index of the failed instruction of the transaction.
+    pub instruction_index: Option<u8>,
+    /// Innermost program that failed, `None` when no instruction failed.
+    pub program: Option<FailedProgram>,
+    pub token_acl_error: Option<TokenAclError>,
+    pub gate_rejection: Option<GateRejection>,
+    pub token_error: Option<TokenError>,
+    /// Logs of the failed instruction, or of the whole transaction when no
+    /// instruction failed.
+    pub logs: Vec<String>,
+}
+
+impl PreflightFailure {
+    /// Diagnoses `error`, returned by simulating a transaction whose
+    /// instructions call `program_ids`, from the simulation `logs`.
+    pub fn new(program_ids: &[Pubkey], error: TransactionError, logs: &[String]) -> Self {
+        let TransactionError::InstructionError(index, ref instruction_error) = error else {
+            return Self {
+                error,
+                instruction_index: None,
+                program: None,
+                token_acl_error: None,
+                gate_rejection: None,
+                token_error: None,
+                logs: logs.to_vec(),
+            };
+        };
+
+        let instruction_program_id = program_ids.get(index as usize).copied();
+        let logs = instruction_logs(logs, index as usize);
+        // the first failure logged is the innermost one, e.g. the gate under token-acl
+        let failed_program_id = logs
+            .iter()
+            .find_map(|log| {
+                log.strip_prefix("Program ")?
+                    .split_once(" failed: ")?
+                    .0
+                    .parse::<Pubkey>()
+                    .ok()
+            })
+            .or(instruction_program_id);
+
+        let program = failed_program_id.map(|program_id| {
+            if program_id == TOKEN_ACL_ID {
+                FailedProgram::TokenAcl
+            } else if program_id == SPL_TOKEN_2022_ID {
+                FailedProgram::Token2022
+            } else if instruction_program_id == Some(TOKEN_ACL_ID) {
+                FailedProgram::Gate(program_id)
+            } else {
+                FailedProgram::Other(program_id)
+            }
+        });
+
+        let code = match instruction_error {
+            InstructionError::Custom(code) => Some(*code),
+            _ => None,
+        };
+        let (mut token_acl_error, mut gate_rejection, mut token_error) = (None, None, None);
+        match (program, code) {
+            (Some(FailedProgram::TokenAcl), Some(code)) => {
+                token_acl_error = TokenAclError::from_u32(code)
+            }
+            (Some(FailedProgram::Gate(_)), Some(code)) => gate_rejection = Some(code.into()),
+            (Some(FailedProgram::Token2022), Some(code)) => {
+                token_error = TokenError::from_u32(code)
+            }
+            _ => {}
+        }
+
+        Self {
+            error,
+            instruction_index: Some(index),
+            program,
+            token_acl_error,
+            gate_rejection,
+            token_error,
+            logs,
+        }
+    }
+}
+
+impl fmt::Display for PreflightFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(index) = self.instruction_index {
+            write!(f, "instruction {index} failed")?;
+        } else {
+            write!(f, "transaction failed")?;
+        }
+        if let Some(program) = self.program {
+            write!(f, " in {program}")?;
+        }
+
+        if let Some(error) = &self.token_acl_error {
+            write!(f, ": {error:?}: {error}")
+        } else if let Some(rejection) = self.gate_rejection {
+            write!(f, ": {rejection}")
+        } else if let Some(error) = &self.token_error {
+            write!(f, ": {error:?}: {error}")
+        } else {
+            write!(f, ": {}", self.error)
+        }
+    }
+}
+
+/// Logs between the invocation of the top-level instruction `index` and the
+/// next one.
+fn instruction_logs(logs: &[String], index: usize) -> Vec<String> {
+    split_instruction_logs(logs).get(index).map_or_else(
+        || logs.to_vec(),
+        |instruction_logs| instruction_logs.to_vec(),
+    )
+}
+
+/// Splits `logs` at the invocation of each top-level instruction.
+pub(crate) fn split_instruction_logs(logs: &[String]) -> Vec<&[String]> {
+    let starts = logs
+        .iter()
+        .enumerate()
+        .filter(|(_, log)| log.starts_with("Program ") && log.ends_with(" invoke [1]"))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| &logs[start..starts.get(n + 1).copied().unwrap_or(logs.len())])
+        .collect()
+}
+
+/// Result of simulating a transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preflight {
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+    /// `None` when the simulation succeeded.
+    pub failure: Option<PreflightFailure>,
+}
+
+impl fmt::Display for Preflight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.failure {
+            Some(failure) => {
+                writeln!(f, "simulation failed: {failure}")?;
+                for log in &failure.logs {
+                    writeln!(f, "  {log}")?;
+                }
+                Ok(())
+            }
+            None => match self.units_consumed {
+                Some(units) => write!(f, "simulation succeeded, {units} compute units consumed"),
+                None => write!(f, "simulation succeeded"),
+            },
+        }
+    }
+}
+
+/// Simulates `transaction` without verifying its signatures and against the
+/// latest blockhash, and diagnoses why it failed.
+#[cfg(feature = "fetch")]
+pub async fn simulate_transaction(
+    rpc: &RpcClient,
+    transaction: &VersionedTransaction,
+) -> Result<Preflight, ClientError> {
+    let result = rpc
+        .simulate_transaction_with_config(
+            transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(rpc.commitment()),
+                ..Default::default()
+            },
+        )
+        .await?
+        .value;
+
+    let logs = result.logs.unwrap_or_default();
+    let failure = result.err.map(|err| {
+        let message = &transaction.message;
+        let program_ids = message
+            .instructions()
+            .iter()
+            .map(|ix| ix.program_id(message.static_account_keys()))
+            .copied()
+            .collect::<Vec<_>>();
+        PreflightFailure::new(&program_ids, err.into(), &logs)
+    });
+
+    Ok(Preflight {
+        units_consumed: result.units_consumed,
+        logs,
+        failure,
+    })
+}
+
+/// Simulates a transaction of `instructions` paid by `payer_pubkey`, see
+/// [`simulate_transaction`].
+#[cfg(feature = "fetch")]
+pub async fn simulate_instructions(
+    rpc: &RpcClient,
+    payer_pubkey: &Pubkey,
+    instructions: &[Instruction],
+) -> Result<Preflight, ClientError> {
+    let transaction = Transaction::new_unsigned(Message::new(instructions, Some(payer_pubkey)));
+    simulate_transaction(rpc, &transaction.into()).await
+}
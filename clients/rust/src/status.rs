@@ -0,0 +1,126 @@
+use solana_program_error::ProgramError;
+use solana_pubkey::Pubkey;
+use spl_token_2022_interface::{
+    extension::{
+        default_account_state::DefaultAccountState, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::{AccountState, Mint},
+};
+use token_acl_interface::{
+    get_freeze_extra_account_metas_address, get_thaw_extra_account_metas_address,
+    offchain::{AccountFetchError, AccountFetcher},
+};
+
+use crate::{
+    accounts::MintConfig, generated::errors::token_acl::TokenAclError,
+    get_gating_program_from_mint_data,
+};
+
+/// What token-acl implies for the holders of a mint, see [`get_mint_acl_status`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MintAclStatus {
+    pub mint_config_address: Pubkey,
+    /// State new token accounts start in, `None` without the
+    /// `DefaultAccountState` extension.
+    pub default_account_state: Option<AccountState>,
+    pub freeze_authority_is_mint_config: bool,
+    pub mint_config: Option<MintConfig>,
+    /// Gating program of the mint config, `None` when it is not set.
+    pub config_gating_program: Option<Pubkey>,
+    /// Gating program of the `token_acl` metadata key.
+    pub metadata_gating_program: Option<Pubkey>,
+    pub permissionless_thaw_enabled: bool,
+    pub permissionless_freeze_enabled: bool,
+    /// Whether the gate has initialized its thaw extra metas for the mint.
+    pub thaw_extra_metas_exists: bool,
+    /// Whether the gate has initialized its freeze extra metas for the mint.
+    pub freeze_extra_metas_exists: bool,
+}
+
+impl MintAclStatus {
+    /// Whether token-acl holds the freeze authority of the mint.
+    pub fn uses_token_acl(&self) -> bool {
+        self.freeze_authority_is_mint_config && self.mint_config.is_some()
+    }
+
+    /// Whether the `token_acl` metadata key names another gating program than
+    /// the mint config, in which case SDKs reading either disagree.
+    pub fn gating_program_mismatch(&self) -> bool {
+        self.metadata_gating_program.is_some()
+            && self.metadata_gating_program != self.config_gating_program
+    }
+
+    /// Whether new token accounts start frozen and must be thawed before use.
+    pub fn requires_thaw(&self) -> bool {
+        self.default_account_state == Some(AccountState::Frozen)
+    }
+}
+
+/// Reads the token-acl status of `mint_pubkey`, in two batches of accounts.
+pub async fn get_mint_acl_status<A>(
+    mint_pubkey: &Pubkey,
+    fetcher: A,
+) -> Result<MintAclStatus, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    let mint_config_address = MintConfig::find_pda(mint_pubkey).0;
+
+    let mut accounts = fetcher
+        .get_multiple_account_data(&[*mint_pubkey, mint_config_address])
+        .await?
+        .into_iter();
+    let mint_data = accounts
+        .next()
+        .flatten()
+        .ok_or(ProgramError::from(TokenAclError::InvalidTokenMint))?;
+    let mint_config_data = accounts.next().flatten();
+
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)
+        .map_err(|_| ProgramError::from(TokenAclError::InvalidTokenMint))?;
+
+    let default_account_state = mint
+        .get_extension::<DefaultAccountState>()
+        .ok()
+        .and_then(|extension| AccountState::try_from(extension.state).ok());
+    let freeze_authority_is_mint_config =
+        mint.base.freeze_authority == Some(mint_config_address).into();
+    let metadata_gating_program = get_gating_program_from_mint_data(&mint_data).ok();
+
+    let mint_config = mint_config_data.and_then(|data| MintConfig::from_bytes(&data).ok());
+    let config_gating_program = mint_config
+        .as_ref()
+        .map(|config| config.gating_program)
+        .filter(|gating_program| *gating_program != Pubkey::default());
+
+    let (thaw_extra_metas_exists, freeze_extra_metas_exists) = match config_gating_program {
+        Some(gating_program) => {
+            let extra_metas = fetcher
+                .get_multiple_account_data(&[
+                    get_thaw_extra_account_metas_address(mint_pubkey, &gating_program),
+                    get_freeze_extra_account_metas_address(mint_pubkey, &gating_program),
+                ])
+                .await?;
+            let exists = |i: usize| extra_metas.get(i).is_some_and(Option::is_some);
+            (exists(0), exists(1))
+        }
+        None => (false, false),
+    };
+
+    Ok(MintAclStatus {
+        mint_config_address,
+        default_account_state,
+        freeze_authority_is_mint_config,
+        permissionless_thaw_enabled: mint_config
+            .as_ref()
+            .is_some_and(|config| config.enable_permissionless_thaw),
+        permissionless_freeze_enabled: mint_config
+            .as_ref()
+            .is_some_and(|config| config.enable_permissionless_freeze),
+        mint_config,
+        config_gating_program,
+        metadata_gating_program,
+        thaw_extra_metas_exists,
+        freeze_extra_metas_exists,
+    })
+}
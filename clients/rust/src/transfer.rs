@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+#[cfg(feature = "fetch")]
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+use solana_instruction::Instruction;
+use solana_program_error::ProgramError;
+use solana_pubkey::Pubkey;
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022_interface::{
+    error::TokenError,
+    extension::{transfer_hook, StateWithExtensions},
+    instruction::transfer_checked,
+    state::{Account, AccountState, Mint},
+    ID as SPL_TOKEN_2022_ID,
+};
+use token_acl_interface::{
+    error::ThawFreezeGateError,
+    offchain::{AccountFetchError, AccountFetcher, CachedAccountFetcher, OverlayAccountFetcher},
+};
+
+use crate::{
+    accounts::MintConfig, create_thaw_permissionless_instruction_with_extra_metas,
+    get_mint_acl_status, mock_associated_token_account_data,
+};
+#[cfg(feature = "fetch")]
+use crate::{programs::TOKEN_ACL_ID, simulate_instructions, PreflightFailure, RpcAccountFetcher};
+
+/// Why a transfer could not be prepared.
+#[cfg(feature = "fetch")]
+#[derive(Debug, thiserror::Error)]
+pub enum TransferError {
+    #[error("{0}")]
+    Fetch(AccountFetchError),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    /// The simulated thaw of the recipient failed, e.g. the gate rejected it.
+    #[error("{0}")]
+    Thaw(Box<PreflightFailure>),
+}
+
+/// Builds the instructions of [`create_transfer_instructions`] from `rpc`
+/// and, when the recipient is thawed, simulates the instructions up to the
+/// thaw so a gate rejecting the recipient fails here rather than on send.
+#[cfg(feature = "fetch")]
+pub async fn create_transfer(
+    rpc: &RpcClient,
+    payer_pubkey: &Pubkey,
+    sender_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    recipient_pubkey: &Pubkey,
+    amount: u64,
+) -> Result<Vec<Instruction>, TransferError> {
+    let instructions = create_transfer_instructions(
+        payer_pubkey,
+        sender_pubkey,
+        mint_pubkey,
+        recipient_pubkey,
+        amount,
+        RpcAccountFetcher::new(rpc),
+    )
+    .await
+    .map_err(TransferError::Fetch)?;
+
+    if let Some(thaw_index) = instructions
+        .iter()
+        .position(|ix| ix.program_id == TOKEN_ACL_ID)
+    {
+        let preflight =
+            simulate_instructions(rpc, payer_pubkey, &instructions[..=thaw_index]).await?;
+        if let Some(failure) = preflight.failure {
+            return Err(TransferError::Thaw(Box::new(failure)));
+        }
+    }
+
+    Ok(instructions)
+}
+
+/// Builds the instructions sending `amount` of `mint_pubkey` from the
+/// associated token account of `sender_pubkey` to the one of
+/// `recipient_pubkey`.
+///
+/// The recipient account is created when missing and permissionlessly thawed
+/// when it is or will be frozen, and the transfer carries the transfer-hook
+/// extra metas of the mint. Fails before building anything when the thaw
+/// cannot be built: the mint is not a token-acl mint, permissionless thaw is
+/// disabled, or the gate's extra metas cannot be resolved. The gate itself is
+/// not run, see [`create_transfer`] to also simulate the thaw.
+pub async fn create_transfer_instructions<A>(
+    payer_pubkey: &Pubkey,
+    sender_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    recipient_pubkey: &Pubkey,
+    amount: u64,
+    fetcher: A,
+) -> Result<Vec<Instruction>, AccountFetchError>
+where
+    A: AccountFetcher,
+{
+    // only lives for this call, so nothing expires
+    let fetcher = CachedAccountFetcher::new(fetcher, Duration::MAX);
+
+    let source = get_associated_token_address_with_program_id(
+        sender_pubkey,
+        mint_pubkey,
+        &SPL_TOKEN_2022_ID,
+    );
+    let destination = get_associated_token_address_with_program_id(
+        recipient_pubkey,
+        mint_pubkey,
+        &SPL_TOKEN_2022_ID,
+    );
+    fetcher
+        .prefetch(&[
+            *mint_pubkey,
+            MintConfig::find_pda(mint_pubkey).0,
+            source,
+            destination,
+        ])
+        .await?;
+
+    let mint_data = fetcher
+        .get_account_data(*mint_pubkey)
+        .await?
+        .ok_or(ThawFreezeGateError::InvalidTokenMint)?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)
+        .map_err(|_| ThawFreezeGateError::InvalidTokenMint)?;
+
+    let source_data = fetcher
+        .get_account_data(source)
+        .await?
+        .ok_or(ProgramError::UninitializedAccount)?;
+    if token_account_state(&source_data)? == AccountState::Frozen {
+        return Err(ProgramError::from(TokenError::AccountFrozen).into());
+    }
+
+    let mut instructions = Vec::new();
+    let mut overlay = OverlayAccountFetcher::new(&fetcher);
+    let destination_state = match fetcher.get_account_data(destination).await? {
+        Some(data) => token_account_state(&data)?,
+        None => {
+            instructions.push(create_associated_token_account_idempotent(
+                payer_pubkey,
+                recipient_pubkey,
+                mint_pubkey,
+                &SPL_TOKEN_2022_ID,
+            ));
+            let data =
+                mock_associated_token_account_data(mint_pubkey, &mint_data, recipient_pubkey)?;
+            let state = token_account_state(&data)?;
+            overlay = overlay.with_account(destination, data);
+            state
+        }
+    };
+
+    if destination_state == AccountState::Frozen {
+        if !get_mint_acl_status(mint_pubkey, &fetcher)
+            .await?
+            .uses_token_acl()
+        {
+            return Err(ThawFreezeGateError::InvalidTokenMint.into());
+        }
+
+        instructions.push(
+            create_thaw_permissionless_instruction_with_extra_metas(
+                payer_pubkey,
+                &destination,
+                mint_pubkey,
+                &MintConfig::find_pda(mint_pubkey).0,
+                &SPL_TOKEN_2022_ID,
+                recipient_pubkey,
+                true,
+                &overlay,
+            )
+            .await?,
+        );
+    }
+
+    let mut transfer_ix = transfer_checked(
+        &SPL_TOKEN_2022_ID,
+        &source,
+        mint_pubkey,
+        &destination,
+        sender_pubkey,
+        &[],
+        amount,
+        mint.base.decimals,
+    )?;
+    if let Some(program_id) = transfer_hook::get_program_id(&mint) {
+        spl_transfer_hook_interface::offchain::add_extra_account_metas_for_execute(
+            &mut transfer_ix,
+            &program_id,
+            &source,
+            mint_pubkey,
+            &destination,
+            sender_pubkey,
+            amount,
+            |pubkey| overlay.get_account_data(pubkey),
+        )
+        .await?;
+    }
+    instructions.push(transfer_ix);
+
+    Ok(instructions)
+}
+
+fn token_account_state(data: &[u8]) -> Result<AccountState, ProgramError> {
+    Ok(StateWithExtensions::<Account>::unpack(data)?.base.state)
+}
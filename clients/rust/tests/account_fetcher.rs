@@ -0,0 +1,388 @@
+pub mod program_test;
+use std::{sync::Mutex, time::Duration};
+
+use credential_gate_program::{create_config, state::CredentialConfig, ID as CREDENTIAL_GATE_ID};
+use litesvm::LiteSVM;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022_interface::{instruction::mint_to, state::AccountState, ID as TOKEN_PROGRAM_ID};
+use token_acl_client::{
+    accounts::MintConfig, create_thaw_permissionless_instruction_with_extra_metas,
+    AccountDataResult, AccountFetchError, AccountFetcher, CachedAccountFetcher,
+    OverlayAccountFetcher,
+};
+use token_acl_interface::get_thaw_extra_account_metas_address;
+
+use crate::program_test::{TestContext, VmAccountFetcher, AA_WD_ID};
+
+/// Serves the vm state, recording every account it is asked for.
+struct VmFetcher<'a> {
+    vm: &'a LiteSVM,
+    fetched: Mutex<Vec<Pubkey>>,
+    batches: Mutex<usize>,
+}
+
+impl<'a> VmFetcher<'a> {
+    fn new(vm: &'a LiteSVM) -> Self {
+        Self {
+            vm,
+            fetched: Mutex::new(Vec::new()),
+            batches: Mutex::new(0),
+        }
+    }
+
+    fn fetch_count(&self, pubkey: &Pubkey) -> usize {
+        self.fetched
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|key| *key == pubkey)
+            .count()
+    }
+
+    fn batches(&self) -> usize {
+        *self.batches.lock().unwrap()
+    }
+}
+
+impl AccountFetcher for VmFetcher<'_> {
+    async fn get_account_data(&self, pubkey: Pubkey) -> AccountDataResult {
+        self.fetched.lock().unwrap().push(pubkey);
+        Ok(self.vm.get_account(&pubkey).map(|a| a.data))
+    }
+
+    async fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, AccountFetchError> {
+        *self.batches.lock().unwrap() += 1;
+        self.fetched.lock().unwrap().extend_from_slice(pubkeys);
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| self.vm.get_account(pubkey).map(|a| a.data))
+            .collect())
+    }
+}
+
+fn setup() -> TestContext {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&AA_WD_ID);
+    tc.setup_aa_wd_gate_extra_metas();
+    tc.toggle_permissionless(false, true);
+    tc
+}
+
+async fn thaw_ix<A: AccountFetcher>(
+    tc: &TestContext,
+    user: &Keypair,
+    token_account: &Pubkey,
+    fetcher: A,
+) -> Result<solana_sdk::instruction::Instruction, AccountFetchError> {
+    create_thaw_permissionless_instruction_with_extra_metas(
+        &user.pubkey(),
+        token_account,
+        &tc.token.mint,
+        &MintConfig::find_pda(&tc.token.mint).0,
+        &TOKEN_PROGRAM_ID,
+        &user.pubkey(),
+        false,
+        fetcher,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_cached_fetcher_resolves_mint_accounts_once() {
+    let mut tc = setup();
+    let mint_cfg_pk = MintConfig::find_pda(&tc.token.mint).0;
+    let extra_metas_pk = get_thaw_extra_account_metas_address(&tc.token.mint, &AA_WD_ID);
+
+    let users = (0..3).map(|_| Keypair::new()).collect::<Vec<_>>();
+    let token_accounts = users
+        .iter()
+        .map(|user| tc.create_token_account(user))
+        .collect::<Vec<_>>();
+
+    let mut instructions = Vec::new();
+    {
+        let cache = CachedAccountFetcher::new(VmFetcher::new(&tc.vm), Duration::from_secs(60));
+        for (user, token_account) in users.iter().zip(&token_accounts) {
+            instructions.push(thaw_ix(&tc, user, token_account, &cache).await.unwrap());
+        }
+
+        assert_eq!(cache.inner().fetch_count(&mint_cfg_pk), 1);
+        assert_eq!(cache.inner().fetch_count(&extra_metas_pk), 1);
+    }
+
+    for ((user, token_account), ix) in users.iter().zip(&token_accounts).zip(instructions) {
+        let res = tc.send(&[ix], &[user]);
+        assert!(res.is_ok());
+        assert_eq!(
+            tc.token_account_state(token_account),
+            AccountState::Initialized
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_resolver_fetches_extra_metas_in_batches() {
+    let mut tc = setup();
+    let user = Keypair::new();
+    let token_account = tc.create_token_account(&user);
+    let extra_metas_pk = get_thaw_extra_account_metas_address(&tc.token.mint, &AA_WD_ID);
+
+    let ix = {
+        let fetcher = VmFetcher::new(&tc.vm);
+        let ix = thaw_ix(
+            &tc,
+            &user,
+            &token_account,
+            OverlayAccountFetcher::new(&fetcher),
+        )
+        .await
+        .unwrap();
+
+        // one batch for the extra metas, one for the accounts they are
+        // resolved from, such as the token account the owner is read from
+        assert_eq!(fetcher.batches(), 2);
+        assert_eq!(fetcher.fetch_count(&extra_metas_pk), 1);
+        assert_eq!(fetcher.fetch_count(&token_account), 1);
+        ix
+    };
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_cached_fetcher_prefetch_and_invalidate() {
+    let mut tc = setup();
+    let user = Keypair::new();
+    let token_account = tc.create_token_account(&user);
+    let missing = Pubkey::new_unique();
+
+    let cache = CachedAccountFetcher::new(VmFetcher::new(&tc.vm), Duration::from_secs(60));
+    cache
+        .prefetch(&[tc.token.mint, token_account, missing])
+        .await
+        .unwrap();
+    assert_eq!(cache.inner().batches(), 1);
+
+    // served from the cache, missing accounts included
+    assert!(cache
+        .get_account_data(token_account)
+        .await
+        .unwrap()
+        .is_some());
+    assert!(cache.get_account_data(missing).await.unwrap().is_none());
+    let accounts = cache
+        .get_multiple_account_data(&[missing, tc.token.mint])
+        .await
+        .unwrap();
+    assert!(accounts[0].is_none());
+    assert!(accounts[1].is_some());
+    assert_eq!(cache.inner().batches(), 1);
+    assert_eq!(cache.inner().fetch_count(&token_account), 1);
+
+    // only the accounts that are not cached are fetched
+    let mint_cfg_pk = MintConfig::find_pda(&tc.token.mint).0;
+    cache.invalidate(&token_account);
+    cache
+        .prefetch(&[tc.token.mint, token_account, mint_cfg_pk])
+        .await
+        .unwrap();
+    assert_eq!(cache.inner().batches(), 2);
+    assert_eq!(cache.inner().fetch_count(&tc.token.mint), 1);
+    assert_eq!(cache.inner().fetch_count(&token_account), 2);
+    assert_eq!(cache.inner().fetch_count(&mint_cfg_pk), 1);
+
+    cache.clear();
+    cache.get_account_data(tc.token.mint).await.unwrap();
+    assert_eq!(cache.inner().fetch_count(&tc.token.mint), 2);
+}
+
+#[tokio::test]
+async fn test_cached_fetcher_expires_entries() {
+    let tc = setup();
+
+    let cache = CachedAccountFetcher::new(VmFetcher::new(&tc.vm), Duration::ZERO);
+    cache.get_account_data(tc.token.mint).await.unwrap();
+    cache.get_account_data(tc.token.mint).await.unwrap();
+    let accounts = cache
+        .get_multiple_account_data(&[tc.token.mint])
+        .await
+        .unwrap();
+    assert!(accounts[0].is_some());
+    assert_eq!(cache.inner().fetch_count(&tc.token.mint), 3);
+}
+
+#[tokio::test]
+async fn test_overlay_fetcher_takes_precedence() {
+    let mut tc = setup();
+    let user = Keypair::new();
+    let token_account = tc.create_token_account(&user);
+    let mint_cfg_pk = MintConfig::find_pda(&tc.token.mint).0;
+    let mocked = Pubkey::new_unique();
+
+    let ix = {
+        let fetcher = VmFetcher::new(&tc.vm);
+        let overlay = OverlayAccountFetcher::new(&fetcher)
+            .with_account(mocked, vec![1, 2, 3])
+            .with_missing_account(token_account);
+
+        assert_eq!(
+            overlay.get_account_data(mocked).await.unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert!(overlay
+            .get_account_data(token_account)
+            .await
+            .unwrap()
+            .is_none());
+        let accounts = overlay
+            .get_multiple_account_data(&[token_account, tc.token.mint, mocked])
+            .await
+            .unwrap();
+        assert!(accounts[0].is_none());
+        assert!(accounts[1].is_some());
+        assert_eq!(accounts[2], Some(vec![1, 2, 3]));
+
+        // only the accounts that are not overlaid reach the inner fetcher
+        assert_eq!(fetcher.fetch_count(&mocked), 0);
+        assert_eq!(fetcher.fetch_count(&token_account), 0);
+        assert_eq!(fetcher.fetch_count(&tc.token.mint), 1);
+
+        // resolvers take overlays like any other fetcher
+        let overlay = OverlayAccountFetcher::new(&fetcher).with_missing_account(mint_cfg_pk);
+        assert!(thaw_ix(&tc, &user, &token_account, &overlay).await.is_err());
+
+        thaw_ix(
+            &tc,
+            &user,
+            &token_account,
+            OverlayAccountFetcher::new(&fetcher),
+        )
+        .await
+        .unwrap()
+    };
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_overlay_fetcher_supplies_gate_state_created_in_same_transaction() {
+    let mut tc = TestContext::new();
+    tc.add_program(&CREDENTIAL_GATE_ID, "credential_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&CREDENTIAL_GATE_ID);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    // a plain token works as credential, the gate only checks the balance
+    let credential =
+        TestContext::create_token_with_default_account_state(&mut tc.vm, AccountState::Initialized);
+    let credential_account = get_associated_token_address_with_program_id(
+        &user.pubkey(),
+        &credential.mint,
+        &TOKEN_PROGRAM_ID,
+    );
+    let ixs = [
+        create_associated_token_account_idempotent(
+            &credential.auth.pubkey(),
+            &user.pubkey(),
+            &credential.mint,
+            &TOKEN_PROGRAM_ID,
+        ),
+        mint_to(
+            &TOKEN_PROGRAM_ID,
+            &credential.mint,
+            &credential_account,
+            &credential.auth.pubkey(),
+            &[],
+            1,
+        )
+        .unwrap(),
+    ];
+    let res = tc.send(&ixs, &[&credential.auth]);
+    assert!(res.is_ok());
+
+    // the config does not exist yet, so the overlay supplies the data the
+    // extra metas read the credential mint and token program from
+    let (config_pk, bump) = CredentialConfig::find_pda(&tc.token.mint);
+    let mut config_data = vec![CredentialConfig::DISCRIMINATOR, bump];
+    config_data.extend_from_slice(tc.token.mint.as_ref());
+    config_data.extend_from_slice(credential.mint.as_ref());
+    config_data.extend_from_slice(TOKEN_PROGRAM_ID.as_ref());
+
+    let thaw_ix = {
+        let fetcher = VmFetcher::new(&tc.vm);
+        let overlay = OverlayAccountFetcher::new(&fetcher).with_account(config_pk, config_data);
+        thaw_ix(&tc, &user, &user_token_account, overlay)
+            .await
+            .unwrap()
+    };
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &credential.mint,
+    );
+    let res = tc.send(&[ix, thaw_ix], &[&auth, &user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[tokio::test]
+async fn test_fetchers_report_account_owners() {
+    let tc = TestContext::new();
+    let fetcher = VmAccountFetcher(&tc.vm);
+    let mocked = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+
+    assert_eq!(
+        fetcher.get_account_owner(tc.token.mint).await.unwrap(),
+        Some(TOKEN_PROGRAM_ID)
+    );
+    assert!(fetcher
+        .get_account_owner(Pubkey::new_unique())
+        .await
+        .unwrap()
+        .is_none());
+
+    let overlay = OverlayAccountFetcher::new(&fetcher)
+        .with_owned_account(mocked, owner, vec![1, 2, 3])
+        .with_missing_account(tc.token.mint);
+    assert_eq!(
+        overlay.get_account_owner(mocked).await.unwrap(),
+        Some(owner)
+    );
+    assert!(overlay
+        .get_account_owner(tc.token.mint)
+        .await
+        .unwrap()
+        .is_none());
+
+    let cached = CachedAccountFetcher::new(VmAccountFetcher(&tc.vm), Duration::MAX);
+    assert_eq!(
+        cached.get_account_owner(tc.token.mint).await.unwrap(),
+        Some(TOKEN_PROGRAM_ID)
+    );
+
+    // closures only return data
+    let fetch_account_data_fn = |pubkey: Pubkey| {
+        let data = tc.vm.get_account(&pubkey).map(|a| a.data);
+        async move { Ok(data) }
+    };
+    assert!(fetch_account_data_fn
+        .get_account_owner(tc.token.mint)
+        .await
+        .is_err());
+}
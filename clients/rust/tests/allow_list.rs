@@ -0,0 +1,190 @@
+pub mod program_test;
+use allow_list_gate_program::{
+    add_wallet, create_list, error::AllowListError, remove_wallet, ID as ALLOW_LIST_ID,
+};
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+use token_acl_gate_framework::{wallet_list::WalletListError, GateError};
+
+use crate::program_test::TestContext;
+
+fn setup() -> (TestContext, Keypair) {
+    let mut tc = TestContext::new();
+    tc.add_program(&ALLOW_LIST_ID, "allow_list_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&ALLOW_LIST_ID);
+
+    let list_authority = Keypair::new();
+    let res = tc.vm.airdrop(&list_authority.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_list(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &list_authority.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    (tc, list_authority)
+}
+
+#[tokio::test]
+async fn test_allow_list_thaw_permissionless() {
+    let (mut tc, list_authority) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AllowListError::WalletNotListed as u32)
+        )
+    );
+
+    let ix = add_wallet(
+        &list_authority.pubkey(),
+        &list_authority.pubkey(),
+        &tc.token.mint,
+        &user.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&list_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[tokio::test]
+async fn test_allow_list_freeze_permissionless() {
+    let (mut tc, list_authority) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let ix = add_wallet(
+        &list_authority.pubkey(),
+        &list_authority.pubkey(),
+        &tc.token.mint,
+        &user.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&list_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+
+    let keeper = Keypair::new();
+    let res = tc.vm.airdrop(&keeper.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AllowListError::WalletListed as u32)
+        )
+    );
+
+    let ix = remove_wallet(
+        &list_authority.pubkey(),
+        &tc.token.mint,
+        &user.pubkey(),
+        &list_authority.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&list_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+}
+
+#[test]
+fn test_allow_list_authorities() {
+    let mut tc = TestContext::new();
+    tc.add_program(&ALLOW_LIST_ID, "allow_list_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&ALLOW_LIST_ID);
+
+    let attacker = Keypair::new();
+    let res = tc.vm.airdrop(&attacker.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let ix = create_list(
+        &attacker.pubkey(),
+        &attacker.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &attacker.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&attacker]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(GateError::InvalidAuthority as u32)
+        )
+    );
+
+    let list_authority = Keypair::new();
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_list(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &list_authority.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    let ix = add_wallet(
+        &attacker.pubkey(),
+        &attacker.pubkey(),
+        &tc.token.mint,
+        &attacker.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&attacker]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(WalletListError::InvalidListAuthority as u32)
+        )
+    );
+}
@@ -0,0 +1,183 @@
+pub mod program_test;
+use approval_queue_gate_program::{
+    approve_request, cancel_request, create_config, error::ApprovalQueueError, reclaim_rent,
+    reject_request, request_thaw, state::ThawRequest, ID as APPROVAL_QUEUE_ID,
+};
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+
+use crate::program_test::TestContext;
+
+fn setup() -> (TestContext, Keypair) {
+    let mut tc = TestContext::new();
+    tc.add_program(&APPROVAL_QUEUE_ID, "approval_queue_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&APPROVAL_QUEUE_ID);
+
+    let operator = Keypair::new();
+    let res = tc.vm.airdrop(&operator.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &operator.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    (tc, operator)
+}
+
+#[tokio::test]
+async fn test_approval_queue_thaw_after_approval() {
+    let (mut tc, operator) = setup();
+
+    let keeper = Keypair::new();
+    let res = tc.vm.airdrop(&keeper.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    let request = ThawRequest::find_pda(&tc.token.mint, &user.pubkey()).0;
+
+    let ix = tc
+        .thaw_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ApprovalQueueError::RequestNotFound as u32)
+        )
+    );
+
+    let ix = request_thaw(&user.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    let user_lamports = tc.vm.get_balance(&user.pubkey()).unwrap();
+    let request_lamports = tc.vm.get_balance(&request).unwrap();
+
+    let ix = tc
+        .thaw_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ApprovalQueueError::RequestNotApproved as u32)
+        )
+    );
+
+    let ix = approve_request(&keeper.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ApprovalQueueError::InvalidOperator as u32)
+        )
+    );
+
+    let ix = approve_request(&operator.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&operator]);
+    assert!(res.is_ok());
+
+    let ixs = [
+        tc.thaw_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+            .await,
+        reclaim_rent(&user.pubkey(), &tc.token.mint),
+    ];
+    let res = tc.send(&ixs, &[&keeper]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+    assert_eq!(tc.vm.get_balance(&request).unwrap_or_default(), 0);
+    assert_eq!(
+        tc.vm.get_balance(&user.pubkey()).unwrap(),
+        user_lamports + request_lamports
+    );
+
+    // the approval was consumed by the thaw
+    tc.freeze(&user_token_account);
+    let ix = tc
+        .thaw_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ApprovalQueueError::RequestNotFound as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_approval_queue_rejected_request() {
+    let (mut tc, operator) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let ix = request_thaw(&user.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+
+    let ix = reject_request(&operator.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&operator]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ApprovalQueueError::RequestNotApproved as u32)
+        )
+    );
+
+    let ix = approve_request(&operator.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&operator]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ApprovalQueueError::RequestNotPending as u32)
+        )
+    );
+
+    // file a new request after the rejection
+    let ixs = [
+        cancel_request(&user.pubkey(), &tc.token.mint),
+        request_thaw(&user.pubkey(), &tc.token.mint),
+    ];
+    let res = tc.send(&ixs, &[&user]);
+    assert!(res.is_ok());
+
+    let ix = approve_request(&operator.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&operator]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
@@ -0,0 +1,161 @@
+pub mod program_test;
+use attestation_gate_program::{
+    create_config, error::AttestationError, set_provider, state::approval_message,
+    ID as ATTESTATION_GATE_ID,
+};
+use solana_ed25519_program::new_ed25519_instruction_with_signature;
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, instruction::InstructionError, pubkey::Pubkey,
+    signature::Keypair, signer::Signer, transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+
+use crate::program_test::TestContext;
+
+const NOW: i64 = 1_700_000_000;
+
+fn setup() -> (TestContext, Keypair) {
+    let mut tc = TestContext::new();
+    tc.add_program(&ATTESTATION_GATE_ID, "attestation_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&ATTESTATION_GATE_ID);
+
+    let mut clock = tc.vm.get_sysvar::<Clock>();
+    clock.unix_timestamp = NOW;
+    tc.vm.set_sysvar(&clock);
+
+    let provider = Keypair::new();
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &provider.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    (tc, provider)
+}
+
+/// Signs an approval off-chain and wraps it in an ed25519 program instruction.
+fn approval_ix(provider: &Keypair, mint: &Pubkey, owner: &Pubkey, expiry: i64) -> Instruction {
+    let message = approval_message(mint, owner, expiry);
+    let signature = provider.sign_message(&message);
+    new_ed25519_instruction_with_signature(
+        &message,
+        signature.as_array(),
+        &provider.pubkey().to_bytes(),
+    )
+}
+
+#[tokio::test]
+async fn test_attestation_gate_thaw_permissionless() {
+    let (mut tc, provider) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let thaw_ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(std::slice::from_ref(&thaw_ix), &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AttestationError::ApprovalNotFound as u32)
+        )
+    );
+
+    // approval for another wallet
+    let approval = approval_ix(&provider, &tc.token.mint, &Pubkey::new_unique(), NOW + 60);
+    let res = tc.send(&[approval, thaw_ix.clone()], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(AttestationError::ApprovalNotFound as u32)
+        )
+    );
+
+    // approval signed by someone else
+    let approval = approval_ix(&Keypair::new(), &tc.token.mint, &user.pubkey(), NOW + 60);
+    let res = tc.send(&[approval, thaw_ix.clone()], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(AttestationError::ApprovalNotFound as u32)
+        )
+    );
+
+    let approval = approval_ix(&provider, &tc.token.mint, &user.pubkey(), NOW + 60);
+    let res = tc.send(&[approval, thaw_ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[tokio::test]
+async fn test_attestation_gate_expired_approval() {
+    let (mut tc, provider) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    let approval = approval_ix(&provider, &tc.token.mint, &user.pubkey(), NOW + 60);
+    let thaw_ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+
+    let mut clock = tc.vm.get_sysvar::<Clock>();
+    clock.unix_timestamp = NOW + 60;
+    tc.vm.set_sysvar(&clock);
+
+    let res = tc.send(&[approval, thaw_ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(AttestationError::ApprovalExpired as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_attestation_gate_rotated_provider() {
+    let (mut tc, provider) = setup();
+
+    let new_provider = Keypair::new();
+    let auth = tc.token.auth.insecure_clone();
+    let ix = set_provider(
+        &auth.pubkey(),
+        &tc.token.mint,
+        &token_acl_client::accounts::MintConfig::find_pda(&tc.token.mint).0,
+        &new_provider.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    let thaw_ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+
+    let approval = approval_ix(&provider, &tc.token.mint, &user.pubkey(), NOW + 60);
+    let res = tc.send(&[approval, thaw_ix.clone()], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(AttestationError::ApprovalNotFound as u32)
+        )
+    );
+
+    let approval = approval_ix(&new_provider, &tc.token.mint, &user.pubkey(), NOW + 60);
+    let res = tc.send(&[approval, thaw_ix], &[&user]);
+    assert!(res.is_ok());
+}
@@ -0,0 +1,157 @@
+pub mod program_test;
+use block_list_gate_program::{
+    block_wallet, create_list, error::BlockListError, unblock_wallet, ID as BLOCK_LIST_ID,
+};
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+use token_acl_gate_framework::wallet_list::WalletListError;
+
+use crate::program_test::TestContext;
+
+fn setup() -> (TestContext, Keypair, Keypair) {
+    let mut tc = TestContext::new_with_default_account_state(AccountState::Initialized);
+    tc.add_program(&BLOCK_LIST_ID, "block_list_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&BLOCK_LIST_ID);
+
+    let sanctions_authority = Keypair::new();
+    let res = tc.vm.airdrop(&sanctions_authority.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_list(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &sanctions_authority.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    let keeper = Keypair::new();
+    let res = tc.vm.airdrop(&keeper.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    (tc, sanctions_authority, keeper)
+}
+
+#[tokio::test]
+async fn test_block_list_freeze_permissionless() {
+    let (mut tc, sanctions_authority, keeper) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(BlockListError::WalletNotBlocked as u32)
+        )
+    );
+
+    let ix = block_wallet(
+        &sanctions_authority.pubkey(),
+        &sanctions_authority.pubkey(),
+        &tc.token.mint,
+        &user.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&sanctions_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+}
+
+#[tokio::test]
+async fn test_block_list_thaw_permissionless() {
+    let (mut tc, sanctions_authority, keeper) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let ix = block_wallet(
+        &sanctions_authority.pubkey(),
+        &sanctions_authority.pubkey(),
+        &tc.token.mint,
+        &user.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&sanctions_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(BlockListError::WalletBlocked as u32)
+        )
+    );
+
+    let ix = unblock_wallet(
+        &sanctions_authority.pubkey(),
+        &tc.token.mint,
+        &user.pubkey(),
+        &sanctions_authority.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&sanctions_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[test]
+fn test_block_list_rejects_unauthorized_block() {
+    let (mut tc, _sanctions_authority, keeper) = setup();
+
+    let user = Keypair::new();
+    let ix = block_wallet(
+        &keeper.pubkey(),
+        &keeper.pubkey(),
+        &tc.token.mint,
+        &user.pubkey(),
+    );
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(WalletListError::InvalidListAuthority as u32)
+        )
+    );
+}
@@ -0,0 +1,254 @@
+pub mod program_test;
+use bloom_block_list_gate_program::{
+    bloom, create_list, create_shard, error::BloomListError, insert_wallets, write_shard,
+    ID as BLOOM_BLOCK_LIST_ID,
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+
+use crate::program_test::TestContext;
+
+const SHARD_LEN: u32 = 1_024;
+const NUM_HASHES: u8 = 7;
+/// Keeps `WriteShard` transactions under the packet size.
+const WRITE_CHUNK_LEN: usize = 512;
+/// Compute budget of a permissionless freeze or thaw through token-acl with
+/// the gate probing a full shard: about 28k CU for token-acl and Token-2022,
+/// plus the gate's flag and extra metas PDA checks and its single hash.
+const PERMISSIONLESS_CU_BUDGET: u64 = 40_000;
+
+fn setup() -> (TestContext, Keypair, Keypair) {
+    let mut tc = TestContext::new_with_default_account_state(AccountState::Initialized);
+    tc.add_program(&BLOOM_BLOCK_LIST_ID, "bloom_block_list_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&BLOOM_BLOCK_LIST_ID);
+
+    let sanctions_authority = Keypair::new();
+    let res = tc.vm.airdrop(&sanctions_authority.pubkey(), 10_000_000_000);
+    assert!(res.is_ok());
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_list(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &sanctions_authority.pubkey(),
+        SHARD_LEN,
+        NUM_HASHES,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    let keeper = Keypair::new();
+    let res = tc.vm.airdrop(&keeper.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    (tc, sanctions_authority, keeper)
+}
+
+fn create_shards(tc: &mut TestContext, sanctions_authority: &Keypair, wallets: &[Pubkey]) {
+    let mut indexes: Vec<u8> = wallets.iter().map(bloom::shard_index).collect();
+    indexes.sort_unstable();
+    indexes.dedup();
+    for index in indexes {
+        let ix = create_shard(
+            &sanctions_authority.pubkey(),
+            &sanctions_authority.pubkey(),
+            &tc.token.mint,
+            index,
+        );
+        let res = tc.send(&[ix], &[sanctions_authority]);
+        assert!(res.is_ok());
+    }
+}
+
+/// Other wallets in the shard of `wallet`, so a single shard is needed.
+fn same_shard_wallets(wallet: &Pubkey, count: usize) -> Vec<Pubkey> {
+    (1..=count as u64)
+        .map(|i| {
+            let mut bytes = wallet.to_bytes();
+            bytes[1..9].copy_from_slice(&i.to_le_bytes());
+            Pubkey::new_from_array(bytes)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_bloom_block_list_freeze_permissionless() {
+    let (mut tc, sanctions_authority, keeper) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    // no shard yet
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(std::slice::from_ref(&ix), &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(BloomListError::WalletNotBlocked as u32)
+        )
+    );
+
+    let mut wallets = same_shard_wallets(&user.pubkey(), 20);
+    wallets.push(user.pubkey());
+
+    let ix = insert_wallets(&sanctions_authority.pubkey(), &tc.token.mint, &wallets);
+    let res = tc.send(&[ix], &[&sanctions_authority]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(BloomListError::InvalidShard as u32)
+        )
+    );
+
+    create_shards(&mut tc, &sanctions_authority, &wallets);
+    // a trailing partial wallet is rejected
+    let mut ix = insert_wallets(&sanctions_authority.pubkey(), &tc.token.mint, &wallets);
+    ix.data.push(0);
+    let res = tc.send(&[ix], &[&sanctions_authority]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+    );
+
+    let ix = insert_wallets(&sanctions_authority.pubkey(), &tc.token.mint, &wallets);
+    let res = tc.send(&[ix], &[&sanctions_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(BloomListError::WalletBlocked as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_bloom_block_list_rebuild_shard() {
+    let (mut tc, sanctions_authority, keeper) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let mut wallets = same_shard_wallets(&user.pubkey(), 20);
+    wallets.push(user.pubkey());
+    create_shards(&mut tc, &sanctions_authority, &wallets);
+
+    let ix = insert_wallets(&sanctions_authority.pubkey(), &tc.token.mint, &wallets);
+    let res = tc.send(&[ix], &[&sanctions_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+
+    // delist the user by rebuilding its shard without it
+    wallets.pop();
+    let shards = bloom::build_shards(&tc.token.mint, &wallets, SHARD_LEN as usize, NUM_HASHES);
+    for (index, bits) in shards {
+        for (chunk, bytes) in bits.chunks(WRITE_CHUNK_LEN).enumerate() {
+            let ix = write_shard(
+                &sanctions_authority.pubkey(),
+                &tc.token.mint,
+                index,
+                (chunk * WRITE_CHUNK_LEN) as u32,
+                bytes,
+            );
+            let res = tc.send(&[ix], &[&sanctions_authority]);
+            assert!(res.is_ok());
+        }
+    }
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[test]
+fn test_bloom_block_list_rejects_unauthorized_update() {
+    let (mut tc, _sanctions_authority, keeper) = setup();
+
+    let ix = write_shard(&keeper.pubkey(), &tc.token.mint, 0, 0, &[0xff; 8]);
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(BloomListError::InvalidListAuthority as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_bloom_block_list_compute_units() {
+    let (mut tc, sanctions_authority, keeper) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let mut wallets = same_shard_wallets(&user.pubkey(), 100);
+    wallets.push(user.pubkey());
+    create_shards(&mut tc, &sanctions_authority, &wallets);
+    for batch in wallets.chunks(25) {
+        let ix = insert_wallets(&sanctions_authority.pubkey(), &tc.token.mint, batch);
+        let res = tc.send(&[ix], &[&sanctions_authority]);
+        assert!(res.is_ok());
+    }
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let freeze_units = tc.send(&[ix], &[&keeper]).unwrap().compute_units_consumed;
+
+    // clear the shard so the thaw probes it too
+    for chunk in 0..SHARD_LEN as usize / WRITE_CHUNK_LEN {
+        let ix = write_shard(
+            &sanctions_authority.pubkey(),
+            &tc.token.mint,
+            bloom::shard_index(&user.pubkey()),
+            (chunk * WRITE_CHUNK_LEN) as u32,
+            &[0; WRITE_CHUNK_LEN],
+        );
+        let res = tc.send(&[ix], &[&sanctions_authority]);
+        assert!(res.is_ok());
+    }
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let thaw_units = tc.send(&[ix], &[&user]).unwrap().compute_units_consumed;
+
+    assert!(freeze_units <= PERMISSIONLESS_CU_BUDGET);
+    assert!(thaw_units <= PERMISSIONLESS_CU_BUDGET);
+}
@@ -0,0 +1,242 @@
+pub mod program_test;
+use bonded_thaw_gate_program::{
+    claim_reward, create_config, deposit,
+    error::BondError,
+    flag_violation, request_withdraw,
+    state::{load_bond, Bond},
+    withdraw, ID as BONDED_THAW_ID,
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+
+use crate::program_test::TestContext;
+
+const MIN_BOND: u64 = 1_000_000_000;
+const SLASH_BPS: u16 = 5_000;
+const COOLDOWN_SLOTS: u64 = 50;
+
+fn setup() -> (TestContext, Keypair) {
+    let mut tc = TestContext::new();
+    tc.add_program(&BONDED_THAW_ID, "bonded_thaw_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&BONDED_THAW_ID);
+
+    let policy_authority = Keypair::new();
+    let res = tc.vm.airdrop(&policy_authority.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &policy_authority.pubkey(),
+        MIN_BOND,
+        SLASH_BPS,
+        COOLDOWN_SLOTS,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    tc.vm.warp_to_slot(1_000);
+    (tc, policy_authority)
+}
+
+fn create_user(tc: &mut TestContext) -> (Keypair, Pubkey) {
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    let res = tc.vm.airdrop(&user.pubkey(), 10_000_000_000);
+    assert!(res.is_ok());
+    (user, user_token_account)
+}
+
+fn create_keeper(tc: &mut TestContext) -> Keypair {
+    let keeper = Keypair::new();
+    let res = tc.vm.airdrop(&keeper.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+    keeper
+}
+
+fn get_bond(tc: &TestContext, owner: &Pubkey) -> Option<Bond> {
+    let bond = Bond::find_pda(&tc.token.mint, owner).0;
+    tc.vm
+        .get_account(&bond)
+        .filter(|account| account.owner == BONDED_THAW_ID)
+        .map(|account| *load_bond(&account.data).unwrap())
+}
+
+fn gate_error(error: BondError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+#[tokio::test]
+async fn test_bonded_thaw_deposit_and_withdraw() {
+    let (mut tc, _) = setup();
+    let (user, user_token_account) = create_user(&mut tc);
+    let keeper = create_keeper(&mut tc);
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(res.err().unwrap().err, gate_error(BondError::BondNotFound));
+
+    let ix = deposit(&user.pubkey(), &tc.token.mint, MIN_BOND / 2);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(BondError::InsufficientBond)
+    );
+
+    let ix = deposit(&user.pubkey(), &tc.token.mint, MIN_BOND / 2);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(BondError::BondInGoodStanding)
+    );
+
+    let ix = request_withdraw(&user.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+
+    let ix = withdraw(&user.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(BondError::CooldownNotElapsed)
+    );
+
+    // a withdrawing owner can be frozen, without being slashed
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+    assert_eq!(
+        u64::from(get_bond(&tc, &user.pubkey()).unwrap().amount),
+        MIN_BOND
+    );
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(BondError::BondWithdrawing)
+    );
+
+    tc.vm.warp_to_slot(1_000 + COOLDOWN_SLOTS);
+    let user_lamports = tc.vm.get_balance(&user.pubkey()).unwrap();
+    let ix = withdraw(&user.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert!(get_bond(&tc, &user.pubkey()).is_none());
+    assert!(tc.vm.get_balance(&user.pubkey()).unwrap() > user_lamports + MIN_BOND - 10_000);
+}
+
+#[tokio::test]
+async fn test_bonded_thaw_slash_on_violation() {
+    let (mut tc, policy_authority) = setup();
+    let (user, user_token_account) = create_user(&mut tc);
+    let reporter = create_keeper(&mut tc);
+
+    let ixs = [
+        deposit(&user.pubkey(), &tc.token.mint, MIN_BOND),
+        tc.thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+            .await,
+    ];
+    let res = tc.send(&ixs, &[&user]);
+    assert!(res.is_ok());
+
+    let ix = flag_violation(&reporter.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&reporter]);
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(BondError::InvalidPolicyAuthority)
+    );
+
+    let ix = flag_violation(&policy_authority.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&policy_authority]);
+    assert!(res.is_ok());
+
+    // the bond cannot leave before it is slashed
+    let ix = request_withdraw(&user.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    tc.vm.warp_to_slot(1_000 + COOLDOWN_SLOTS);
+    let ix = withdraw(&user.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(BondError::BondInViolation)
+    );
+
+    let ix = tc
+        .freeze_permissionless_ix(&reporter.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&reporter]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+
+    let reward = MIN_BOND * SLASH_BPS as u64 / 10_000;
+    let bond = get_bond(&tc, &user.pubkey()).unwrap();
+    assert_eq!(bond.reporter, reporter.pubkey());
+    assert_eq!(u64::from(bond.reward), reward);
+    assert_eq!(u64::from(bond.amount), MIN_BOND - reward);
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(BondError::BondInViolation)
+    );
+
+    let reporter_lamports = tc.vm.get_balance(&reporter.pubkey()).unwrap();
+    let ix = claim_reward(&reporter.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&reporter]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.vm.get_balance(&reporter.pubkey()).unwrap(),
+        reporter_lamports + reward - 5_000
+    );
+
+    let ix = withdraw(&user.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert!(get_bond(&tc, &user.pubkey()).is_none());
+}
@@ -0,0 +1,175 @@
+pub mod program_test;
+use solana_compute_budget_interface::{ComputeBudgetInstruction, ID as COMPUTE_BUDGET_ID};
+use solana_instruction::{error::InstructionError, Instruction};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, transaction::Transaction,
+    transaction::TransactionError,
+};
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account,
+};
+use spl_token_2022_interface::{state::AccountState, ID as TOKEN_PROGRAM_ID};
+use token_acl_client::{
+    compute_unit_limit_with_margin, instruction_units_consumed, programs::TOKEN_ACL_ID,
+    set_compute_budget_instructions, PriorityFee, DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT,
+    MAX_COMPUTE_UNIT_LIMIT,
+};
+
+use crate::program_test::{TestContext, AA_WD_ID};
+
+/// Creates the associated token account of a new owner and thaws it through
+/// the gate.
+async fn create_and_thaw_ixs(tc: &TestContext, payer: &Keypair) -> (Pubkey, Vec<Instruction>) {
+    let owner = Keypair::new();
+    let token_account = get_associated_token_address_with_program_id(
+        &owner.pubkey(),
+        &tc.token.mint,
+        &TOKEN_PROGRAM_ID,
+    );
+    let ixs = token_acl_client::create_ata_and_thaw_permissionless_instructions(
+        &payer.pubkey(),
+        &tc.token.mint,
+        &TOKEN_PROGRAM_ID,
+        &owner.pubkey(),
+        false,
+        &|pubkey| {
+            let data = tc.vm.get_account(&pubkey).map(|a| a.data);
+            async move { Ok(data) }
+        },
+    )
+    .await
+    .unwrap();
+    (token_account, ixs)
+}
+
+#[tokio::test]
+async fn test_compute_unit_limit_from_simulation() {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&AA_WD_ID);
+    tc.setup_aa_wd_gate_extra_metas();
+    tc.toggle_permissionless(false, true);
+    let auth = tc.token.auth.insecure_clone();
+
+    let (token_account, ixs) = create_and_thaw_ixs(&tc, &auth).await;
+    assert_eq!(ixs[1].program_id, TOKEN_ACL_ID);
+
+    let simulated_ixs = set_compute_budget_instructions(&ixs, Some(MAX_COMPUTE_UNIT_LIMIT), None);
+    let tx = Transaction::new_signed_with_payer(
+        &simulated_ixs,
+        Some(&auth.pubkey()),
+        &[&auth],
+        tc.vm.latest_blockhash(),
+    );
+    let simulation = tc.vm.simulate_transaction(tx).unwrap();
+    let units_consumed = simulation.meta.compute_units_consumed;
+
+    let program_ids = simulated_ixs
+        .iter()
+        .map(|ix| ix.program_id)
+        .collect::<Vec<_>>();
+    let units = instruction_units_consumed(&program_ids, &simulation.meta.logs);
+    assert_eq!(units.len(), 3);
+    // the compute budget program is a builtin
+    assert_eq!(units[0], None);
+    let create_units = units[1].unwrap();
+    let thaw_units = units[2].unwrap();
+    assert!(thaw_units > 0);
+    assert!(create_units + thaw_units <= units_consumed);
+
+    // one unit short fails the thaw, which runs last
+    let res = tc.send(
+        &set_compute_budget_instructions(&ixs, Some(units_consumed as u32 - 1), None),
+        &[&auth],
+    );
+    assert!(matches!(
+        res.unwrap_err().err,
+        TransactionError::InstructionError(2, InstructionError::ProgramFailedToComplete)
+    ));
+
+    let limit = compute_unit_limit_with_margin(units_consumed, DEFAULT_COMPUTE_UNIT_MARGIN_PERCENT);
+    assert!(limit as u64 > units_consumed);
+    let ixs = set_compute_budget_instructions(&ixs, Some(limit), Some(1_000));
+    assert_eq!(ixs.len(), 4);
+    let res = tc.send(&ixs, &[&auth]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&token_account),
+        AccountState::Initialized
+    );
+}
+
+#[test]
+fn test_set_compute_budget_instructions_replaces_existing() {
+    let create_ix = create_associated_token_account(
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        &TOKEN_PROGRAM_ID,
+    );
+    let ixs = vec![
+        create_ix.clone(),
+        ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+    ];
+
+    let ixs = set_compute_budget_instructions(&ixs, Some(50_000), Some(10));
+    assert_eq!(
+        ixs,
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_price(10),
+            create_ix.clone(),
+            ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+        ]
+    );
+
+    let ixs = set_compute_budget_instructions(&ixs, None, Some(20));
+    assert_eq!(
+        ixs.iter()
+            .filter(|ix| ix.program_id == COMPUTE_BUDGET_ID)
+            .count(),
+        2
+    );
+    assert_eq!(ixs[0], ComputeBudgetInstruction::set_compute_unit_price(20));
+
+    assert_eq!(
+        set_compute_budget_instructions(std::slice::from_ref(&create_ix), None, None),
+        vec![create_ix]
+    );
+}
+
+#[test]
+fn test_priority_fee_compute_unit_price() {
+    let recent_fees = [0, 500, 100, 300, 200];
+
+    assert_eq!(PriorityFee::None.compute_unit_price(&recent_fees), None);
+    assert_eq!(
+        PriorityFee::Fixed(42).compute_unit_price(&recent_fees),
+        Some(42)
+    );
+
+    let percentile = |percentile, max| PriorityFee::RecentPercentile { percentile, max };
+    assert_eq!(
+        percentile(50, u64::MAX).compute_unit_price(&recent_fees),
+        Some(200)
+    );
+    assert_eq!(
+        percentile(100, u64::MAX).compute_unit_price(&recent_fees),
+        Some(500)
+    );
+    assert_eq!(
+        percentile(100, 250).compute_unit_price(&recent_fees),
+        Some(250)
+    );
+    // nothing paid recently
+    assert_eq!(
+        percentile(0, u64::MAX).compute_unit_price(&recent_fees),
+        None
+    );
+    assert_eq!(percentile(50, u64::MAX).compute_unit_price(&[]), None);
+
+    assert_eq!(compute_unit_limit_with_margin(100_000, 20), 120_000);
+    assert_eq!(
+        compute_unit_limit_with_margin(MAX_COMPUTE_UNIT_LIMIT as u64, 20),
+        MAX_COMPUTE_UNIT_LIMIT
+    );
+}
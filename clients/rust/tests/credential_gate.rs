@@ -0,0 +1,270 @@
+pub mod program_test;
+use credential_gate_program::{
+    create_config, error::CredentialGateError, state::CredentialConfig, ID as CREDENTIAL_GATE_ID,
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use solana_system_interface::instruction::create_account;
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+use spl_token_2022_interface::{
+    extension::ExtensionType,
+    instruction::{
+        burn, freeze_account, initialize_mint2, initialize_non_transferable_mint, mint_to,
+    },
+    state::{AccountState, Mint},
+    ID as TOKEN_PROGRAM_ID,
+};
+
+use crate::program_test::TestContext;
+
+struct Credential {
+    mint: Pubkey,
+    issuer: Keypair,
+}
+
+impl Credential {
+    fn ata(&self, wallet: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(wallet, &self.mint, &TOKEN_PROGRAM_ID)
+    }
+}
+
+/// Creates a non-transferable credential mint whose issuer is also its freeze authority.
+fn create_credential_mint(tc: &mut TestContext) -> Credential {
+    let issuer = Keypair::new();
+    let res = tc.vm.airdrop(&issuer.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let mint_kp = Keypair::new();
+    let mint_size =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::NonTransferable])
+            .unwrap();
+
+    let ixs = [
+        create_account(
+            &issuer.pubkey(),
+            &mint_kp.pubkey(),
+            tc.vm.minimum_balance_for_rent_exemption(mint_size),
+            mint_size as u64,
+            &TOKEN_PROGRAM_ID,
+        ),
+        initialize_non_transferable_mint(&TOKEN_PROGRAM_ID, &mint_kp.pubkey()).unwrap(),
+        initialize_mint2(
+            &TOKEN_PROGRAM_ID,
+            &mint_kp.pubkey(),
+            &issuer.pubkey(),
+            Some(&issuer.pubkey()),
+            0,
+        )
+        .unwrap(),
+    ];
+    let res = tc.send(&ixs, &[&issuer, &mint_kp]);
+    assert!(res.is_ok());
+
+    Credential {
+        mint: mint_kp.pubkey(),
+        issuer,
+    }
+}
+
+fn issue_credential(tc: &mut TestContext, credential: &Credential, wallet: &Pubkey) {
+    let ixs = [
+        create_associated_token_account_idempotent(
+            &credential.issuer.pubkey(),
+            wallet,
+            &credential.mint,
+            &TOKEN_PROGRAM_ID,
+        ),
+        mint_to(
+            &TOKEN_PROGRAM_ID,
+            &credential.mint,
+            &credential.ata(wallet),
+            &credential.issuer.pubkey(),
+            &[],
+            1,
+        )
+        .unwrap(),
+    ];
+    let res = tc.send(&ixs, &[&credential.issuer]);
+    assert!(res.is_ok());
+}
+
+fn setup() -> (TestContext, Credential) {
+    let mut tc = TestContext::new();
+    tc.add_program(&CREDENTIAL_GATE_ID, "credential_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&CREDENTIAL_GATE_ID);
+
+    let credential = create_credential_mint(&mut tc);
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &credential.mint,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    (tc, credential)
+}
+
+#[tokio::test]
+async fn test_credential_gate_thaw_permissionless() {
+    let (mut tc, credential) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(CredentialGateError::CredentialNotHeld as u32)
+        )
+    );
+
+    issue_credential(&mut tc, &credential, &user.pubkey());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[tokio::test]
+async fn test_credential_gate_freeze_after_credential_frozen() {
+    let (mut tc, credential) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    issue_credential(&mut tc, &credential, &user.pubkey());
+    tc.thaw(&user_token_account);
+
+    let keeper = Keypair::new();
+    let res = tc.vm.airdrop(&keeper.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(CredentialGateError::CredentialHeld as u32)
+        )
+    );
+
+    let ix = freeze_account(
+        &TOKEN_PROGRAM_ID,
+        &credential.ata(&user.pubkey()),
+        &credential.mint,
+        &credential.issuer.pubkey(),
+        &[],
+    )
+    .unwrap();
+    let res = tc.send(&[ix], &[&credential.issuer]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+}
+
+#[tokio::test]
+async fn test_credential_gate_freeze_after_credential_burned() {
+    let (mut tc, credential) = setup();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    issue_credential(&mut tc, &credential, &user.pubkey());
+    tc.thaw(&user_token_account);
+
+    let ix = burn(
+        &TOKEN_PROGRAM_ID,
+        &credential.ata(&user.pubkey()),
+        &credential.mint,
+        &user.pubkey(),
+        &[],
+        1,
+    )
+    .unwrap();
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+}
+
+#[tokio::test]
+async fn test_credential_gate_config_created_in_thaw_transaction() {
+    let mut tc = TestContext::new();
+    tc.add_program(&CREDENTIAL_GATE_ID, "credential_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&CREDENTIAL_GATE_ID);
+    let credential = create_credential_mint(&mut tc);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    issue_credential(&mut tc, &credential, &user.pubkey());
+
+    // the config does not exist yet, so the client supplies the data the
+    // extra metas read the credential mint and token program from
+    let (config_pk, bump) = CredentialConfig::find_pda(&tc.token.mint);
+    let mut config_data = vec![CredentialConfig::DISCRIMINATOR, bump];
+    config_data.extend_from_slice(tc.token.mint.as_ref());
+    config_data.extend_from_slice(credential.mint.as_ref());
+    config_data.extend_from_slice(TOKEN_PROGRAM_ID.as_ref());
+
+    let thaw_ix = tc
+        .thaw_permissionless_ix_with_supplied_accounts(
+            &user.pubkey(),
+            &user_token_account,
+            &user.pubkey(),
+            &[(config_pk, config_data)],
+        )
+        .await;
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &credential.mint,
+    );
+    let res = tc.send(&[ix, thaw_ix], &[&auth, &user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
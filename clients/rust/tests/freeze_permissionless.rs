@@ -13,7 +13,7 @@ use spl_token_2022_interface::{
     ID as TOKEN_PROGRAM_ID,
 };
 
-use crate::program_test::TestContext;
+use crate::program_test::{TestContext, VmAccountFetcher};
 
 #[test]
 fn test_freeze_permissionless() {
@@ -211,6 +211,7 @@ async fn test_freeze_permissionless_always_block() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn test_freeze_permissionless_always_allow_with_deps() {
     let mut tc = TestContext::new();
     let mint_cfg_pk = tc.setup_token_acl(&program_test::AA_WD_ID);
@@ -372,3 +373,47 @@ async fn test_freeze_permissionless_always_allow_with_deps() {
     //println!("account: {:?}", account);
     assert_eq!(account.base.state, AccountState::Frozen);
 }
+
+#[tokio::test]
+async fn test_freeze_permissionless_from_token_account_or_owner() {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&program_test::AA_WD_ID);
+    tc.setup_aa_wd_gate_extra_metas();
+    tc.toggle_permissionless(true, false);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    tc.thaw(&user_token_account);
+
+    let ix = tc
+        .freeze_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let ix_from_token_account =
+        token_acl_client::create_freeze_permissionless_instruction_for_token_account(
+            &user.pubkey(),
+            &user_token_account,
+            false,
+            VmAccountFetcher(&tc.vm),
+        )
+        .await
+        .unwrap();
+    let ix_from_owner =
+        token_acl_client::create_freeze_permissionless_instruction_for_mint_and_owner(
+            &user.pubkey(),
+            &tc.token.mint,
+            &user.pubkey(),
+            false,
+            VmAccountFetcher(&tc.vm),
+        )
+        .await
+        .unwrap();
+    assert_eq!(ix_from_token_account, ix);
+    assert_eq!(ix_from_owner, ix);
+
+    let res = tc.send(&[ix_from_owner], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+}
@@ -0,0 +1,76 @@
+pub mod program_test;
+use solana_instruction::error::InstructionError;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
+use token_acl_gate_framework::GateError;
+use token_acl_interface::{
+    get_thaw_extra_account_metas_address,
+    instruction::{initialize_extra_account_metas, update_extra_account_metas},
+};
+
+use crate::program_test::{TestContext, AA_WD_ID};
+
+fn assert_invalid_authority(res: litesvm::types::TransactionResult) {
+    assert_eq!(
+        res.unwrap_err().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(GateError::InvalidAuthority as u32)
+        )
+    );
+}
+
+#[test]
+fn test_extra_metas_require_token_acl_authority() {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&AA_WD_ID);
+    let auth = tc.token.auth.insecure_clone();
+    let attacker = Keypair::new();
+    let res = tc.vm.airdrop(&attacker.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let ix = initialize_extra_account_metas(
+        &AA_WD_ID,
+        &attacker.pubkey(),
+        &attacker.pubkey(),
+        &tc.token.mint,
+        &[],
+    );
+    assert_invalid_authority(tc.send(&[ix], &[&attacker]));
+    let thaw_extra_metas = get_thaw_extra_account_metas_address(&tc.token.mint, &AA_WD_ID);
+    assert!(tc.vm.get_account(&thaw_extra_metas).is_none());
+
+    // anyone may pay, as long as the authority signs
+    let ix = initialize_extra_account_metas(
+        &AA_WD_ID,
+        &attacker.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &[],
+    );
+    let res = tc.send(&[ix], &[&attacker, &auth]);
+    assert!(res.is_ok());
+    let extra_metas = tc.vm.get_account(&thaw_extra_metas).unwrap().data;
+
+    let ix = update_extra_account_metas(
+        &AA_WD_ID,
+        &attacker.pubkey(),
+        &attacker.pubkey(),
+        &tc.token.mint,
+        &[],
+    );
+    assert_invalid_authority(tc.send(&[ix], &[&attacker]));
+    assert_eq!(
+        tc.vm.get_account(&thaw_extra_metas).unwrap().data,
+        extra_metas
+    );
+
+    let ix = update_extra_account_metas(
+        &AA_WD_ID,
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &[],
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+}
@@ -0,0 +1,273 @@
+pub mod program_test;
+use gate_router_gate_program::{
+    create_route, error::RouterError, extra_metas_accounts, set_route, ID as GATE_ROUTER_ID,
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+use token_acl_gate_framework::GateError;
+use token_acl_interface::{
+    get_freeze_extra_account_metas_address, get_thaw_extra_account_metas_address,
+    instruction::{initialize_extra_account_metas, update_extra_account_metas},
+};
+
+use crate::program_test::{TestContext, AA_WD_ID, AB_ID};
+
+/// Routes the test mint to `sub_gate` through the router, initializing the
+/// extra metas of both.
+fn setup(sub_gate: &Pubkey) -> (TestContext, Pubkey) {
+    let mut tc = TestContext::new();
+    tc.add_program(&GATE_ROUTER_ID, "gate_router_gate_program");
+    let mint_cfg_pk = tc.setup_token_acl(&GATE_ROUTER_ID);
+
+    let auth = tc.token.auth.insecure_clone();
+    // always-block predates the gate framework and keeps its own layout
+    let sub_gate_ix = if sub_gate == &AB_ID {
+        tc.get_setup_extra_metas_ix(&auth.pubkey(), sub_gate)
+    } else {
+        initialize_extra_account_metas(
+            sub_gate,
+            &auth.pubkey(),
+            &auth.pubkey(),
+            &tc.token.mint,
+            &[],
+        )
+    };
+    let ixs = [
+        sub_gate_ix,
+        create_route(
+            &auth.pubkey(),
+            &auth.pubkey(),
+            &tc.token.mint,
+            &mint_cfg_pk,
+            sub_gate,
+        ),
+        initialize_extra_account_metas(
+            &GATE_ROUTER_ID,
+            &auth.pubkey(),
+            &auth.pubkey(),
+            &tc.token.mint,
+            &extra_metas_accounts(&tc.token.mint, sub_gate),
+        ),
+    ];
+    let res = tc.send(&ixs, &[&auth]);
+    assert!(res.is_ok());
+
+    tc.toggle_permissionless(true, true);
+
+    (tc, mint_cfg_pk)
+}
+
+#[tokio::test]
+async fn test_gate_router_resolves_sub_gate_extra_metas() {
+    let (mut tc, _) = setup(&AA_WD_ID);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    // token-acl -> router -> always-allow-with-deps, whose dependencies are
+    // resolved from the router's extra metas
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let accounts = ix
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect::<Vec<_>>();
+    assert!(accounts.contains(&AA_WD_ID));
+    assert!(accounts.contains(&get_thaw_extra_account_metas_address(
+        &tc.token.mint,
+        &AA_WD_ID
+    )));
+    assert!(accounts.contains(&spl_associated_token_account_interface::program::ID));
+
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+
+    let ix = tc
+        .freeze_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let accounts = ix
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect::<Vec<_>>();
+    assert!(accounts.contains(&get_freeze_extra_account_metas_address(
+        &tc.token.mint,
+        &AA_WD_ID
+    )));
+
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+}
+
+#[tokio::test]
+async fn test_gate_router_set_route() {
+    let (mut tc, mint_cfg_pk) = setup(&AB_ID);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    // always-block rejects the forwarded call
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(0, InstructionError::Custom(999_999_999))
+    );
+
+    let auth = tc.token.auth.insecure_clone();
+    let res = tc.send(
+        &[initialize_extra_account_metas(
+            &AA_WD_ID,
+            &auth.pubkey(),
+            &auth.pubkey(),
+            &tc.token.mint,
+            &[],
+        )],
+        &[&auth],
+    );
+    assert!(res.is_ok());
+
+    let ix = set_route(&user.pubkey(), &tc.token.mint, &mint_cfg_pk, &AA_WD_ID);
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(GateError::InvalidAuthority as u32)
+        )
+    );
+
+    let ix = set_route(
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &GATE_ROUTER_ID,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RouterError::InvalidSubGate as u32)
+        )
+    );
+
+    let ix = set_route(&auth.pubkey(), &tc.token.mint, &mint_cfg_pk, &AA_WD_ID);
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    // the router's extra metas still describe always-block
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RouterError::StaleExtraMetas as u32)
+        )
+    );
+
+    let ix = update_extra_account_metas(
+        &GATE_ROUTER_ID,
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &extra_metas_accounts(&tc.token.mint, &AA_WD_ID),
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[test]
+fn test_gate_router_update_resizes_extra_metas() {
+    let (mut tc, mint_cfg_pk) = setup(&AB_ID);
+    let auth = tc.token.auth.insecure_clone();
+    let res = tc.send(
+        &[initialize_extra_account_metas(
+            &AA_WD_ID,
+            &auth.pubkey(),
+            &auth.pubkey(),
+            &tc.token.mint,
+            &[],
+        )],
+        &[&auth],
+    );
+    assert!(res.is_ok());
+
+    let extra_metas = [
+        get_thaw_extra_account_metas_address(&tc.token.mint, &GATE_ROUTER_ID),
+        get_freeze_extra_account_metas_address(&tc.token.mint, &GATE_ROUTER_ID),
+    ];
+    let accounts = |tc: &TestContext| {
+        extra_metas.map(|pubkey| {
+            let account = tc.vm.get_account(&pubkey).unwrap();
+            (account.data.len(), account.lamports)
+        })
+    };
+    let route_and_update = |tc: &mut TestContext, sub_gate: &Pubkey| {
+        let ixs = [
+            set_route(&auth.pubkey(), &tc.token.mint, &mint_cfg_pk, sub_gate),
+            update_extra_account_metas(
+                &GATE_ROUTER_ID,
+                &auth.pubkey(),
+                &auth.pubkey(),
+                &tc.token.mint,
+                &extra_metas_accounts(&tc.token.mint, sub_gate),
+            ),
+        ];
+        let res = tc.send(&ixs, &[&auth]);
+        assert!(res.is_ok());
+    };
+
+    let initial = accounts(&tc);
+    for (len, lamports) in initial {
+        assert_eq!(lamports, tc.vm.minimum_balance_for_rent_exemption(len));
+    }
+
+    // always-allow-with-deps has more extra metas to forward: the accounts
+    // grow and the payer tops up their rent
+    route_and_update(&mut tc, &AA_WD_ID);
+    let grown = accounts(&tc);
+    for ((len, lamports), (initial_len, _)) in grown.into_iter().zip(initial) {
+        assert!(len > initial_len);
+        assert_eq!(lamports, tc.vm.minimum_balance_for_rent_exemption(len));
+    }
+
+    // back to always-block: the accounts shrink to their initial size and
+    // keep their lamports
+    route_and_update(&mut tc, &AB_ID);
+    for ((len, lamports), ((initial_len, _), (_, grown_lamports))) in accounts(&tc)
+        .into_iter()
+        .zip(initial.into_iter().zip(grown))
+    {
+        assert_eq!(len, initial_len);
+        assert_eq!(lamports, grown_lamports);
+    }
+}
@@ -0,0 +1,120 @@
+pub mod program_test;
+use solana_sdk::{
+    message::{Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::VersionedTransaction,
+};
+use spl_token_2022_interface::{state::AccountState, ID as TOKEN_PROGRAM_ID};
+use token_acl_client::{
+    accounts::MintConfig, create_lookup_table_instructions, create_v0_message,
+    extend_lookup_table_instructions, get_lookup_table_account, get_mint_lookup_table_addresses,
+    programs::TOKEN_ACL_ID,
+};
+
+use crate::program_test::{TestContext, AA_WD_ID};
+
+/// Largest transaction a validator accepts, in bytes.
+const PACKET_DATA_SIZE: usize = 1232;
+
+fn fetch_account_data_fn(
+    tc: &TestContext,
+) -> impl Fn(Pubkey) -> std::future::Ready<token_acl_client::AccountDataResult> + '_ {
+    |pubkey| std::future::ready(Ok(tc.vm.get_account(&pubkey).map(|a| a.data)))
+}
+
+/// Size of `message` once signed, as sent over the wire.
+fn transaction_size(message: &VersionedMessage) -> usize {
+    let signatures = message.header().num_required_signatures as usize;
+    1 + signatures * 64 + message.serialize().len()
+}
+
+#[tokio::test]
+async fn test_lookup_table_batches_thaws_in_v0_transaction() {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&AA_WD_ID);
+    tc.setup_aa_wd_gate_extra_metas();
+    tc.toggle_permissionless(false, true);
+    let auth = tc.token.auth.insecure_clone();
+
+    let addresses = get_mint_lookup_table_addresses(&tc.token.mint, fetch_account_data_fn(&tc))
+        .await
+        .unwrap();
+    for address in [
+        TOKEN_ACL_ID,
+        tc.token.mint,
+        MintConfig::find_pda(&tc.token.mint).0,
+        AA_WD_ID,
+        token_acl_interface::get_thaw_extra_account_metas_address(&tc.token.mint, &AA_WD_ID),
+        // fixed extra metas of the gate
+        spl_associated_token_account_interface::program::ID,
+        TOKEN_PROGRAM_ID,
+    ] {
+        assert!(addresses.contains(&address));
+    }
+
+    let (lookup_table_pubkey, ixs) =
+        create_lookup_table_instructions(&auth.pubkey(), &auth.pubkey(), 0, &addresses);
+    for ix in ixs {
+        let res = tc.send(&[ix], &[&auth]);
+        assert!(res.is_ok());
+    }
+    // extended addresses are usable from the next slot
+    tc.vm.warp_to_slot(1);
+
+    let lookup_table = get_lookup_table_account(&lookup_table_pubkey, fetch_account_data_fn(&tc))
+        .await
+        .unwrap();
+    assert_eq!(lookup_table.addresses, addresses);
+    assert!(extend_lookup_table_instructions(
+        &lookup_table,
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &addresses
+    )
+    .is_empty());
+
+    let mut token_accounts = Vec::new();
+    let mut thaw_ixs = Vec::new();
+    for _ in 0..8 {
+        let owner = Keypair::new();
+        let token_account = tc.create_token_account(&owner);
+        thaw_ixs.push(
+            token_acl_client::create_thaw_permissionless_instruction_with_extra_metas(
+                &auth.pubkey(),
+                &token_account,
+                &tc.token.mint,
+                &MintConfig::find_pda(&tc.token.mint).0,
+                &TOKEN_PROGRAM_ID,
+                &owner.pubkey(),
+                false,
+                fetch_account_data_fn(&tc),
+            )
+            .await
+            .unwrap(),
+        );
+        token_accounts.push(token_account);
+    }
+
+    let blockhash = tc.vm.latest_blockhash();
+    let legacy = VersionedMessage::Legacy(Message::new_with_blockhash(
+        &thaw_ixs,
+        Some(&auth.pubkey()),
+        &blockhash,
+    ));
+    assert!(transaction_size(&legacy) > PACKET_DATA_SIZE);
+
+    let message = create_v0_message(&auth.pubkey(), &thaw_ixs, &[lookup_table], blockhash).unwrap();
+    assert!(transaction_size(&message) <= PACKET_DATA_SIZE);
+
+    let tx = VersionedTransaction::try_new(message, &[&auth]).unwrap();
+    let res = tc.vm.send_transaction(tx);
+    assert!(res.is_ok());
+    for token_account in token_accounts {
+        assert_eq!(
+            tc.token_account_state(&token_account),
+            AccountState::Initialized
+        );
+    }
+}
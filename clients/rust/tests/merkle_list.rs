@@ -0,0 +1,151 @@
+pub mod program_test;
+use merkle_list_gate_program::{
+    close_proof, create_list, error::MerkleListError, merkle, set_root, write_proof,
+    ID as MERKLE_LIST_ID,
+};
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+
+use crate::program_test::TestContext;
+
+const LIST_SIZE: usize = 1_000;
+
+/// Sets up a list of `LIST_SIZE` wallets with `user` at `user_index`.
+fn setup(user: &Keypair, user_index: usize) -> (TestContext, Keypair, Vec<Pubkey>) {
+    let mut tc = TestContext::new();
+    tc.add_program(&MERKLE_LIST_ID, "merkle_list_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&MERKLE_LIST_ID);
+
+    let mut wallets: Vec<Pubkey> = (0..LIST_SIZE).map(|_| Pubkey::new_unique()).collect();
+    wallets[user_index] = user.pubkey();
+
+    let list_authority = Keypair::new();
+    let res = tc.vm.airdrop(&list_authority.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_list(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &list_authority.pubkey(),
+        &merkle::root(&wallets),
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    (tc, list_authority, wallets)
+}
+
+#[tokio::test]
+async fn test_merkle_list_thaw_permissionless() {
+    let user = Keypair::new();
+    let (mut tc, _list_authority, wallets) = setup(&user, 417);
+    let user_token_account = tc.create_token_account(&user);
+
+    let thaw_ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(std::slice::from_ref(&thaw_ix), &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MerkleListError::WalletNotListed as u32)
+        )
+    );
+
+    // proof of another wallet
+    let proof = merkle::proof(&wallets, 3).unwrap();
+    let ix = write_proof(&user.pubkey(), &tc.token.mint, &user.pubkey(), &proof);
+    let res = tc.send(&[ix, thaw_ix.clone()], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(MerkleListError::WalletNotListed as u32)
+        )
+    );
+
+    let proof = merkle::proof(&wallets, 417).unwrap();
+    assert_eq!(proof.len(), 10);
+    let ix = write_proof(&user.pubkey(), &tc.token.mint, &user.pubkey(), &proof);
+    let res = tc.send(&[ix, thaw_ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+
+    let ix = close_proof(&user.pubkey(), &tc.token.mint, &user.pubkey());
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_merkle_list_thaw_by_relayer() {
+    let user = Keypair::new();
+    let (mut tc, _list_authority, wallets) = setup(&user, LIST_SIZE - 1);
+    let user_token_account = tc.create_token_account(&user);
+
+    let relayer = Keypair::new();
+    let res = tc.vm.airdrop(&relayer.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let proof = merkle::proof(&wallets, LIST_SIZE - 1).unwrap();
+    let ix = write_proof(&relayer.pubkey(), &tc.token.mint, &user.pubkey(), &proof);
+    let thaw_ix = tc
+        .thaw_permissionless_ix(&relayer.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix, thaw_ix], &[&relayer]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[tokio::test]
+async fn test_merkle_list_set_root() {
+    let user = Keypair::new();
+    let (mut tc, list_authority, mut wallets) = setup(&user, 0);
+    let user_token_account = tc.create_token_account(&user);
+
+    let proof = merkle::proof(&wallets, 0).unwrap();
+    let ix = write_proof(&user.pubkey(), &tc.token.mint, &user.pubkey(), &proof);
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+
+    wallets.remove(0);
+    let new_root = merkle::root(&wallets);
+
+    let ix = set_root(&user.pubkey(), &tc.token.mint, &new_root);
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MerkleListError::InvalidListAuthority as u32)
+        )
+    );
+
+    let ix = set_root(&list_authority.pubkey(), &tc.token.mint, &new_root);
+    let res = tc.send(&[ix], &[&list_authority]);
+    assert!(res.is_ok());
+
+    let thaw_ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[thaw_ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MerkleListError::WalletNotListed as u32)
+        )
+    );
+}
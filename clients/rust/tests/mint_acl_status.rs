@@ -0,0 +1,84 @@
+pub mod program_test;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use spl_token_2022_interface::state::AccountState;
+use token_acl_client::{accounts::MintConfig, get_mint_acl_status, set_mint_tacl_metadata_ix};
+
+use crate::program_test::{TestContext, AA_WD_ID, AB_ID};
+
+fn fetch_account_data_fn(
+    tc: &TestContext,
+) -> impl Fn(Pubkey) -> std::future::Ready<token_acl_client::AccountDataResult> + '_ {
+    |pubkey| std::future::ready(Ok(tc.vm.get_account(&pubkey).map(|a| a.data)))
+}
+
+#[tokio::test]
+async fn test_mint_acl_status() {
+    let mut tc = TestContext::new();
+    let mint_cfg_pk = MintConfig::find_pda(&tc.token.mint).0;
+
+    let status = get_mint_acl_status(&tc.token.mint, fetch_account_data_fn(&tc))
+        .await
+        .unwrap();
+    assert_eq!(status.mint_config_address, mint_cfg_pk);
+    assert_eq!(status.default_account_state, Some(AccountState::Frozen));
+    assert!(status.requires_thaw());
+    assert!(!status.uses_token_acl());
+    assert!(!status.freeze_authority_is_mint_config);
+    assert_eq!(status.mint_config, None);
+    assert_eq!(status.config_gating_program, None);
+    assert_eq!(status.metadata_gating_program, None);
+    assert!(!status.gating_program_mismatch());
+
+    tc.setup_token_acl(&AA_WD_ID);
+
+    let status = get_mint_acl_status(&tc.token.mint, fetch_account_data_fn(&tc))
+        .await
+        .unwrap();
+    assert!(status.uses_token_acl());
+    assert!(status.freeze_authority_is_mint_config);
+    assert_eq!(status.config_gating_program, Some(AA_WD_ID));
+    assert_eq!(status.metadata_gating_program, Some(AA_WD_ID));
+    assert!(!status.gating_program_mismatch());
+    assert!(!status.permissionless_thaw_enabled);
+    assert!(!status.permissionless_freeze_enabled);
+    assert!(!status.thaw_extra_metas_exists);
+    assert!(!status.freeze_extra_metas_exists);
+
+    tc.setup_aa_wd_gate_extra_metas();
+    tc.toggle_permissionless(false, true);
+
+    let status = get_mint_acl_status(&tc.token.mint, fetch_account_data_fn(&tc))
+        .await
+        .unwrap();
+    assert_eq!(
+        status
+            .mint_config
+            .as_ref()
+            .map(|config| config.gating_program),
+        Some(AA_WD_ID)
+    );
+    assert!(status.permissionless_thaw_enabled);
+    assert!(!status.permissionless_freeze_enabled);
+    assert!(status.thaw_extra_metas_exists);
+    assert!(status.freeze_extra_metas_exists);
+
+    // metadata pointing wallets to another gate than the one token-acl calls
+    let auth = tc.token.auth.insecure_clone();
+    let ix = set_mint_tacl_metadata_ix(&tc.token.mint, &auth.pubkey(), &AB_ID);
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    let status = get_mint_acl_status(&tc.token.mint, fetch_account_data_fn(&tc))
+        .await
+        .unwrap();
+    assert_eq!(status.metadata_gating_program, Some(AB_ID));
+    assert!(status.gating_program_mismatch());
+}
+
+#[tokio::test]
+async fn test_mint_acl_status_missing_mint() {
+    let tc = TestContext::new();
+
+    let res = get_mint_acl_status(&Pubkey::new_unique(), fetch_account_data_fn(&tc)).await;
+    assert!(res.is_err());
+}
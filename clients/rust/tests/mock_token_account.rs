@@ -0,0 +1,187 @@
+pub mod program_test;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use solana_system_interface::instruction::create_account;
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account,
+};
+use spl_token_2022_interface::{
+    extension::{
+        default_account_state::instruction::initialize_default_account_state,
+        transfer_fee::instruction::initialize_transfer_fee_config, transfer_hook, ExtensionType,
+    },
+    instruction::{initialize_account3, initialize_immutable_owner, initialize_mint2},
+    state::{Account, AccountState, Mint},
+    ID as TOKEN_PROGRAM_ID,
+};
+use token_acl_client::{mock_associated_token_account_data, mock_token_account_data};
+
+use crate::program_test::TestContext;
+
+/// Creates a frozen-by-default mint whose accounts require the transfer fee
+/// and transfer hook account extensions.
+fn create_mint_with_account_extensions(tc: &mut TestContext) -> Pubkey {
+    let auth = tc.token.auth.insecure_clone();
+    let mint = Keypair::new();
+    let mint_size = ExtensionType::try_calculate_account_len::<Mint>(&[
+        ExtensionType::DefaultAccountState,
+        ExtensionType::TransferFeeConfig,
+        ExtensionType::TransferHook,
+    ])
+    .unwrap();
+
+    let ixs = [
+        create_account(
+            &auth.pubkey(),
+            &mint.pubkey(),
+            tc.vm.minimum_balance_for_rent_exemption(mint_size),
+            mint_size as u64,
+            &TOKEN_PROGRAM_ID,
+        ),
+        initialize_default_account_state(&TOKEN_PROGRAM_ID, &mint.pubkey(), &AccountState::Frozen)
+            .unwrap(),
+        initialize_transfer_fee_config(
+            &TOKEN_PROGRAM_ID,
+            &mint.pubkey(),
+            Some(&auth.pubkey()),
+            Some(&auth.pubkey()),
+            10,
+            1_000,
+        )
+        .unwrap(),
+        transfer_hook::instruction::initialize(
+            &TOKEN_PROGRAM_ID,
+            &mint.pubkey(),
+            Some(auth.pubkey()),
+            Some(Pubkey::new_unique()),
+        )
+        .unwrap(),
+        initialize_mint2(
+            &TOKEN_PROGRAM_ID,
+            &mint.pubkey(),
+            &auth.pubkey(),
+            Some(&auth.pubkey()),
+            6,
+        )
+        .unwrap(),
+    ];
+    let res = tc.send(&ixs, &[&auth, &mint]);
+    assert!(res.is_ok());
+
+    mint.pubkey()
+}
+
+/// Creates a keypair token account, initializing `extension_types` before
+/// `InitializeAccount3` like a wallet would.
+fn create_keypair_token_account(
+    tc: &mut TestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    extension_types: &[ExtensionType],
+) -> Pubkey {
+    let auth = tc.token.auth.insecure_clone();
+    let token_account = Keypair::new();
+    let size = ExtensionType::try_calculate_account_len::<Account>(
+        &[
+            extension_types,
+            &[
+                ExtensionType::TransferFeeAmount,
+                ExtensionType::TransferHookAccount,
+            ],
+        ]
+        .concat(),
+    )
+    .unwrap();
+
+    let mut ixs = vec![create_account(
+        &auth.pubkey(),
+        &token_account.pubkey(),
+        tc.vm.minimum_balance_for_rent_exemption(size),
+        size as u64,
+        &TOKEN_PROGRAM_ID,
+    )];
+    if extension_types.contains(&ExtensionType::ImmutableOwner) {
+        ixs.push(initialize_immutable_owner(&TOKEN_PROGRAM_ID, &token_account.pubkey()).unwrap());
+    }
+    ixs.push(initialize_account3(&TOKEN_PROGRAM_ID, &token_account.pubkey(), mint, owner).unwrap());
+    let res = tc.send(&ixs, &[&auth, &token_account]);
+    assert!(res.is_ok());
+
+    token_account.pubkey()
+}
+
+fn assert_mock_matches(
+    tc: &TestContext,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    extension_types: &[ExtensionType],
+) {
+    let mint_data = tc.vm.get_account(mint).unwrap().data;
+    let data = tc.vm.get_account(token_account).unwrap().data;
+    assert_eq!(
+        mock_token_account_data(mint, &mint_data, owner, extension_types).unwrap(),
+        data
+    );
+}
+
+#[test]
+fn test_mock_associated_token_account_matches_created_account() {
+    let mut tc = TestContext::new();
+    let owner = Keypair::new();
+    let token_account = tc.create_token_account(&owner);
+
+    let mint_data = tc.vm.get_account(&tc.token.mint).unwrap().data;
+    let data = tc.vm.get_account(&token_account).unwrap().data;
+    assert_eq!(
+        mock_associated_token_account_data(&tc.token.mint, &mint_data, &owner.pubkey()).unwrap(),
+        data
+    );
+}
+
+#[test]
+fn test_mock_token_account_with_required_extensions() {
+    let mut tc = TestContext::new();
+    let mint = create_mint_with_account_extensions(&mut tc);
+
+    let owner = Keypair::new();
+    let ix = create_associated_token_account(
+        &tc.token.auth.pubkey(),
+        &owner.pubkey(),
+        &mint,
+        &TOKEN_PROGRAM_ID,
+    );
+    let auth = tc.token.auth.insecure_clone();
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+    let token_account =
+        get_associated_token_address_with_program_id(&owner.pubkey(), &mint, &TOKEN_PROGRAM_ID);
+    assert_mock_matches(
+        &tc,
+        &token_account,
+        &mint,
+        &owner.pubkey(),
+        &[ExtensionType::ImmutableOwner],
+    );
+    assert_eq!(tc.token_account_state(&token_account), AccountState::Frozen);
+}
+
+#[test]
+fn test_mock_keypair_token_account() {
+    let mut tc = TestContext::new();
+    let mint = create_mint_with_account_extensions(&mut tc);
+    let owner = Pubkey::new_unique();
+
+    let token_account = create_keypair_token_account(&mut tc, &mint, &owner, &[]);
+    assert_mock_matches(&tc, &token_account, &mint, &owner, &[]);
+
+    let token_account =
+        create_keypair_token_account(&mut tc, &mint, &owner, &[ExtensionType::ImmutableOwner]);
+    assert_mock_matches(
+        &tc,
+        &token_account,
+        &mint,
+        &owner,
+        &[ExtensionType::ImmutableOwner],
+    );
+}
@@ -0,0 +1,258 @@
+pub mod program_test;
+use owner_policy_gate_program::{
+    add_program, create_config, create_registry,
+    error::OwnerPolicyError,
+    prove_pda, remove_program,
+    state::{OwnerPolicy, ProgramRegistry},
+    ID as OWNER_POLICY_ID,
+};
+use solana_sdk::{
+    account::Account, instruction::InstructionError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::TransactionError,
+};
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account,
+};
+use spl_token_2022_interface::{state::AccountState, ID as TOKEN_PROGRAM_ID};
+
+use crate::program_test::TestContext;
+
+fn setup(policy: OwnerPolicy) -> (TestContext, Keypair) {
+    let mut tc = TestContext::new();
+    tc.add_program(&OWNER_POLICY_ID, "owner_policy_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&OWNER_POLICY_ID);
+
+    let registry_authority = Keypair::new();
+    let res = tc.vm.airdrop(&registry_authority.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let ix = create_registry(&registry_authority.pubkey(), &registry_authority.pubkey());
+    let res = tc.send(&[ix], &[&registry_authority]);
+    assert!(res.is_ok());
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        policy,
+        &ProgramRegistry::find_pda(&registry_authority.pubkey()).0,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    (tc, registry_authority)
+}
+
+/// Creates a vault PDA of `program_id` owned by `account_owner`, and its token
+/// account.
+fn create_vault(
+    tc: &mut TestContext,
+    program_id: &Pubkey,
+    account_owner: &Pubkey,
+) -> (Pubkey, Pubkey) {
+    let (vault, _) = Pubkey::find_program_address(&[b"vault"], program_id);
+    tc.vm
+        .set_account(
+            vault,
+            Account {
+                lamports: 1_000_000_000,
+                data: vec![0; 8],
+                owner: *account_owner,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix =
+        create_associated_token_account(&auth.pubkey(), &vault, &tc.token.mint, &TOKEN_PROGRAM_ID);
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    let token_account =
+        get_associated_token_address_with_program_id(&vault, &tc.token.mint, &TOKEN_PROGRAM_ID);
+    (vault, token_account)
+}
+
+fn prove_vault(tc: &mut TestContext, payer: &Keypair, program_id: &Pubkey) {
+    let (_, bump) = Pubkey::find_program_address(&[b"vault"], program_id);
+    let ix = prove_pda(&payer.pubkey(), program_id, &[b"vault", &[bump]]);
+    let res = tc.send(&[ix], &[payer]);
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_owner_policy_wallets_only() {
+    let (mut tc, _) = setup(OwnerPolicy::WalletsOnly);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+
+    let ix = tc
+        .freeze_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OwnerPolicyError::OwnerAllowed as u32)
+        )
+    );
+
+    let (vault, vault_token_account) = {
+        let program_id = Pubkey::new_unique();
+        create_vault(&mut tc, &program_id, &program_id)
+    };
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &vault_token_account, &vault)
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OwnerPolicyError::OwnerNotAllowed as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_owner_policy_approved_programs_only() {
+    let (mut tc, registry_authority) = setup(OwnerPolicy::ApprovedProgramsOnly);
+
+    let keeper = Keypair::new();
+    let res = tc.vm.airdrop(&keeper.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let approved_program = Pubkey::new_unique();
+    let ix = add_program(&registry_authority.pubkey(), &approved_program);
+    let res = tc.send(&[ix], &[&registry_authority]);
+    assert!(res.is_ok());
+
+    let ix = add_program(&registry_authority.pubkey(), &approved_program);
+    let res = tc.send(&[ix], &[&registry_authority]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OwnerPolicyError::ProgramAlreadyApproved as u32)
+        )
+    );
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+    let ix = tc
+        .thaw_permissionless_ix(&keeper.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OwnerPolicyError::OwnerNotAllowed as u32)
+        )
+    );
+
+    let (other_vault, other_vault_token_account) = {
+        let program_id = Pubkey::new_unique();
+        create_vault(&mut tc, &program_id, &program_id)
+    };
+    let ix = tc
+        .thaw_permissionless_ix(&keeper.pubkey(), &other_vault_token_account, &other_vault)
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OwnerPolicyError::OwnerNotAllowed as u32)
+        )
+    );
+
+    // vaults are only attributed to their program once proven
+    let (vault, vault_token_account) = create_vault(&mut tc, &approved_program, &approved_program);
+    let ix = tc
+        .thaw_permissionless_ix(&keeper.pubkey(), &vault_token_account, &vault)
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OwnerPolicyError::OwnerNotAllowed as u32)
+        )
+    );
+
+    prove_vault(&mut tc, &keeper, &approved_program);
+    let ix = tc
+        .thaw_permissionless_ix(&keeper.pubkey(), &vault_token_account, &vault)
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&vault_token_account),
+        AccountState::Initialized
+    );
+
+    // revoking the program lets anyone freeze its vaults
+    let ix = remove_program(&registry_authority.pubkey(), &approved_program);
+    let res = tc.send(&[ix], &[&registry_authority]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .freeze_permissionless_ix(&keeper.pubkey(), &vault_token_account, &vault)
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&vault_token_account),
+        AccountState::Frozen
+    );
+}
+
+#[tokio::test]
+async fn test_owner_policy_rejects_pdas_of_other_programs() {
+    let (mut tc, registry_authority) = setup(OwnerPolicy::ApprovedProgramsOnly);
+
+    let keeper = Keypair::new();
+    let res = tc.vm.airdrop(&keeper.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let approved_program = Pubkey::new_unique();
+    let ix = add_program(&registry_authority.pubkey(), &approved_program);
+    let res = tc.send(&[ix], &[&registry_authority]);
+    assert!(res.is_ok());
+
+    // a program can assign its own PDA to the approved program and still sign
+    // for it, so owning the account does not make it a PDA of that program
+    let other_program = Pubkey::new_unique();
+    let (vault, vault_token_account) = create_vault(&mut tc, &other_program, &approved_program);
+    prove_vault(&mut tc, &keeper, &other_program);
+
+    let ix = tc
+        .thaw_permissionless_ix(&keeper.pubkey(), &vault_token_account, &vault)
+        .await;
+    let res = tc.send(&[ix], &[&keeper]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OwnerPolicyError::OwnerNotAllowed as u32)
+        )
+    );
+}
@@ -0,0 +1,145 @@
+pub mod program_test;
+use solana_sdk::{program_pack::Pack, signature::Keypair, signer::Signer};
+use solana_system_interface::instruction::{create_account, transfer};
+use spl_associated_token_account_interface::{
+    address::get_associated_token_address_with_program_id,
+    instruction::{create_associated_token_account, create_associated_token_account_idempotent},
+};
+use spl_token_2022_interface::{
+    instruction::initialize_account3,
+    state::{Account, AccountState},
+    ID as TOKEN_PROGRAM_ID,
+};
+use token_acl_client::{insert_permissionless_thaw_instructions, programs::TOKEN_ACL_ID};
+
+use crate::program_test::{TestContext, AA_ID, AA_WD_ID};
+
+#[tokio::test]
+async fn test_insert_permissionless_thaw_instructions() {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&AA_ID);
+    tc.setup_aa_gate_extra_metas();
+    tc.toggle_permissionless(false, true);
+
+    // frozen by default, but not a token-acl mint
+    let other_token = TestContext::create_token(&mut tc.vm);
+
+    let user = Keypair::new();
+    let existing_token_account = tc.create_token_account(&user);
+
+    let recipient = Keypair::new().pubkey();
+    let recipient_token_account =
+        get_associated_token_address_with_program_id(&recipient, &tc.token.mint, &TOKEN_PROGRAM_ID);
+    let other_token_account = get_associated_token_address_with_program_id(
+        &recipient,
+        &other_token.mint,
+        &TOKEN_PROGRAM_ID,
+    );
+    let account = Keypair::new();
+
+    let instructions = vec![
+        transfer(&user.pubkey(), &recipient, 1_000_000),
+        create_associated_token_account(
+            &user.pubkey(),
+            &recipient,
+            &tc.token.mint,
+            &TOKEN_PROGRAM_ID,
+        ),
+        create_associated_token_account_idempotent(
+            &user.pubkey(),
+            &recipient,
+            &other_token.mint,
+            &TOKEN_PROGRAM_ID,
+        ),
+        create_associated_token_account_idempotent(
+            &user.pubkey(),
+            &user.pubkey(),
+            &tc.token.mint,
+            &TOKEN_PROGRAM_ID,
+        ),
+        create_account(
+            &user.pubkey(),
+            &account.pubkey(),
+            tc.vm.minimum_balance_for_rent_exemption(Account::LEN),
+            Account::LEN as u64,
+            &TOKEN_PROGRAM_ID,
+        ),
+        initialize_account3(
+            &TOKEN_PROGRAM_ID,
+            &account.pubkey(),
+            &tc.token.mint,
+            &user.pubkey(),
+        )
+        .unwrap(),
+    ];
+
+    let augmented =
+        insert_permissionless_thaw_instructions(&user.pubkey(), instructions.clone(), |pubkey| {
+            let data = tc.vm.get_account(&pubkey).map(|a| a.data);
+            async move { Ok(data) }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(augmented.len(), instructions.len() + 3);
+    let thaws = augmented
+        .iter()
+        .enumerate()
+        .filter(|(_, ix)| ix.program_id == TOKEN_ACL_ID)
+        .map(|(i, ix)| (i, ix.accounts[2].pubkey))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        thaws,
+        vec![
+            (2, recipient_token_account),
+            (5, existing_token_account),
+            (8, account.pubkey()),
+        ]
+    );
+    assert!(augmented
+        .iter()
+        .filter(|ix| ix.program_id != TOKEN_ACL_ID)
+        .eq(instructions.iter()));
+
+    let res = tc.send(&augmented, &[&user, &account]);
+    assert!(res.is_ok());
+    for token_account in [
+        recipient_token_account,
+        existing_token_account,
+        account.pubkey(),
+    ] {
+        assert_eq!(
+            tc.token_account_state(&token_account),
+            AccountState::Initialized
+        );
+    }
+    assert_eq!(
+        tc.token_account_state(&other_token_account),
+        AccountState::Frozen
+    );
+}
+
+#[tokio::test]
+async fn test_insert_permissionless_thaw_instructions_disabled() {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&AA_WD_ID);
+    tc.setup_aa_wd_gate_extra_metas();
+
+    let user = Keypair::new();
+    let instructions = vec![create_associated_token_account(
+        &user.pubkey(),
+        &user.pubkey(),
+        &tc.token.mint,
+        &TOKEN_PROGRAM_ID,
+    )];
+
+    // permissionless thaw is not enabled, so there is nothing to insert
+    let augmented =
+        insert_permissionless_thaw_instructions(&user.pubkey(), instructions.clone(), |pubkey| {
+            let data = tc.vm.get_account(&pubkey).map(|a| a.data);
+            async move { Ok(data) }
+        })
+        .await
+        .unwrap();
+    assert_eq!(augmented, instructions);
+}
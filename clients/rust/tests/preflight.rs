@@ -0,0 +1,129 @@
+pub mod program_test;
+use solana_instruction::Instruction;
+use solana_sdk::{signature::Keypair, signer::Signer};
+use spl_associated_token_account_interface::instruction::create_associated_token_account_idempotent;
+use spl_token_2022_interface::{
+    error::TokenError, instruction::transfer_checked, ID as TOKEN_PROGRAM_ID,
+};
+use token_acl_client::{
+    errors::TokenAclError, programs::TOKEN_ACL_ID, FailedProgram, PreflightFailure,
+};
+use token_acl_interface::error::GateRejection;
+
+use crate::program_test::{TestContext, AB_ID};
+
+fn preflight_failure(
+    tc: &mut TestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> PreflightFailure {
+    let program_ids = instructions
+        .iter()
+        .map(|ix| ix.program_id)
+        .collect::<Vec<_>>();
+    let res = tc.send(instructions, signers).unwrap_err();
+    PreflightFailure::new(&program_ids, res.err, &res.meta.logs)
+}
+
+/// Puts `thaw_ix` after an instruction that succeeds, so the thaw is the
+/// second instruction.
+fn thaw_ixs(tc: &TestContext, user: &Keypair, thaw_ix: Instruction) -> Vec<Instruction> {
+    vec![
+        create_associated_token_account_idempotent(
+            &user.pubkey(),
+            &user.pubkey(),
+            &tc.token.mint,
+            &TOKEN_PROGRAM_ID,
+        ),
+        thaw_ix,
+    ]
+}
+
+#[tokio::test]
+async fn test_preflight_token_acl_and_gate_failures() {
+    let mut tc = TestContext::new();
+    let mint_cfg_pk = tc.setup_token_acl(&AB_ID);
+    tc.setup_ab_gate_extra_metas();
+
+    let user = Keypair::new();
+    let token_account = tc.create_token_account(&user);
+
+    let thaw_ix = token_acl_client::instructions::ThawPermissionlessBuilder::new()
+        .authority(user.pubkey())
+        .mint(tc.token.mint)
+        .mint_config(mint_cfg_pk)
+        .token_account(token_account)
+        .token_account_owner(user.pubkey())
+        .gating_program(AB_ID)
+        .flag_account(token_acl_client::accounts::FlagAccount::find_pda(&token_account).0)
+        .instruction();
+    let ixs = thaw_ixs(&tc, &user, thaw_ix);
+    let failure = preflight_failure(&mut tc, &ixs, &[&user]);
+    assert_eq!(failure.instruction_index, Some(1));
+    assert_eq!(failure.program, Some(FailedProgram::TokenAcl));
+    assert_eq!(
+        failure.token_acl_error,
+        Some(TokenAclError::PermissionlessThawNotEnabled)
+    );
+    assert_eq!(failure.gate_rejection, None);
+    assert_eq!(
+        failure.logs.first().unwrap(),
+        &format!("Program {TOKEN_ACL_ID} invoke [1]")
+    );
+    assert!(failure.to_string().contains("PermissionlessThawNotEnabled"));
+
+    tc.toggle_permissionless(false, true);
+    let thaw_ix = token_acl_client::create_thaw_permissionless_instruction_with_extra_metas(
+        &user.pubkey(),
+        &token_account,
+        &tc.token.mint,
+        &mint_cfg_pk,
+        &TOKEN_PROGRAM_ID,
+        &user.pubkey(),
+        false,
+        |pubkey| {
+            let data = tc.vm.get_account(&pubkey).map(|a| a.data);
+            async move { Ok(data) }
+        },
+    )
+    .await
+    .unwrap();
+    let ixs = thaw_ixs(&tc, &user, thaw_ix);
+    let failure = preflight_failure(&mut tc, &ixs, &[&user]);
+    assert_eq!(failure.instruction_index, Some(1));
+    assert_eq!(failure.program, Some(FailedProgram::Gate(AB_ID)));
+    assert_eq!(failure.token_acl_error, None);
+    assert_eq!(
+        failure.gate_rejection,
+        Some(GateRejection::UnsupportedInstruction)
+    );
+    assert!(failure
+        .logs
+        .iter()
+        .any(|log| log.starts_with(&format!("Program {AB_ID} failed"))));
+}
+
+#[test]
+fn test_preflight_token_2022_failure() {
+    let mut tc = TestContext::new();
+    let user = Keypair::new();
+    let token_account = tc.create_token_account(&user);
+
+    // accounts start frozen
+    let ix = transfer_checked(
+        &TOKEN_PROGRAM_ID,
+        &token_account,
+        &tc.token.mint,
+        &token_account,
+        &user.pubkey(),
+        &[],
+        0,
+        6,
+    )
+    .unwrap();
+    let failure = preflight_failure(&mut tc, &[ix], &[&user]);
+    assert_eq!(failure.instruction_index, Some(0));
+    assert_eq!(failure.program, Some(FailedProgram::Token2022));
+    assert_eq!(failure.token_error, Some(TokenError::AccountFrozen));
+    assert_eq!(failure.token_acl_error, None);
+}
@@ -1,4 +1,4 @@
-use litesvm::LiteSVM;
+use litesvm::{types::TransactionResult, LiteSVM};
 use solana_instruction::{AccountMeta, Instruction};
 use solana_pubkey::Pubkey;
 use solana_sdk::transaction::Transaction;
@@ -10,19 +10,34 @@ use spl_associated_token_account_interface::instruction::create_associated_token
 use spl_token_2022_interface::{
     extension::{
         default_account_state::instruction::initialize_default_account_state,
-        metadata_pointer::instruction::initialize, ExtensionType,
+        metadata_pointer::instruction::initialize, ExtensionType, StateWithExtensions,
     },
     instruction::{initialize_mint2, initialize_mint_close_authority},
     state::{AccountState, Mint},
     ID as TOKEN_PROGRAM_ID,
 };
 
-use token_acl_client::set_mint_tacl_metadata_ix;
+use token_acl_client::{
+    set_mint_tacl_metadata_ix, AccountDataResult, AccountFetchError, AccountFetcher,
+};
 
 pub const AA_ID: Pubkey = Pubkey::from_str_const("Eba1ts11111111111111111111111111111111111112");
 pub const AB_ID: Pubkey = Pubkey::from_str_const("Eba1ts11111111111111111111111111111111111113");
 pub const AA_WD_ID: Pubkey = Pubkey::from_str_const("Eba1ts11111111111111111111111111111111111114");
 
+/// Serves the vm state, owners included.
+pub struct VmAccountFetcher<'a>(pub &'a LiteSVM);
+
+impl AccountFetcher for VmAccountFetcher<'_> {
+    async fn get_account_data(&self, pubkey: Pubkey) -> AccountDataResult {
+        Ok(self.0.get_account(&pubkey).map(|a| a.data))
+    }
+
+    async fn get_account_owner(&self, pubkey: Pubkey) -> Result<Option<Pubkey>, AccountFetchError> {
+        Ok(self.0.get_account(&pubkey).map(|a| a.owner))
+    }
+}
+
 pub struct TestContext {
     pub vm: LiteSVM,
     pub token: TokenContext,
@@ -41,6 +56,10 @@ impl Default for TestContext {
 
 impl TestContext {
     pub fn new() -> Self {
+        Self::new_with_default_account_state(AccountState::Frozen)
+    }
+
+    pub fn new_with_default_account_state(default_account_state: AccountState) -> Self {
         let mut vm = LiteSVM::new();
 
         // current path
@@ -74,12 +93,46 @@ impl TestContext {
         //let tokenKp = Keypair::new();
         //let auth_pubkey = auth.pubkey();
 
-        let token = Self::create_token(&mut vm);
+        let token = Self::create_token_with_default_account_state(&mut vm, default_account_state);
 
         Self { vm, token }
     }
 
+    /// Loads a gate program built into `tests/fixtures/<name>.so`.
+    pub fn add_program(&mut self, program_id: &Pubkey, name: &str) {
+        let current_dir = std::env::current_dir().unwrap();
+        let res = self.vm.add_program_from_file(
+            *program_id,
+            current_dir.join(format!("tests/fixtures/{name}.so")),
+        );
+        assert!(res.is_ok());
+    }
+
+    /// Signs and sends `instructions` with a fresh blockhash, the first signer paying fees.
+    #[allow(clippy::result_large_err)]
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        self.vm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.vm.latest_blockhash(),
+        );
+        self.vm.send_transaction(tx)
+    }
+
     pub fn create_token(vm: &mut LiteSVM) -> TokenContext {
+        Self::create_token_with_default_account_state(vm, AccountState::Frozen)
+    }
+
+    pub fn create_token_with_default_account_state(
+        vm: &mut LiteSVM,
+        default_account_state: AccountState,
+    ) -> TokenContext {
         let auth = Keypair::new();
         let auth_pubkey = auth.pubkey();
 
@@ -106,7 +159,7 @@ impl TestContext {
         );
 
         let ix2 =
-            initialize_default_account_state(token_program_id, &mint_pk, &AccountState::Frozen)
+            initialize_default_account_state(token_program_id, &mint_pk, &default_account_state)
                 .unwrap();
 
         let ix3 = initialize(token_program_id, &mint_pk, Some(auth_pubkey), Some(mint_pk)).unwrap();
@@ -289,8 +342,13 @@ impl TestContext {
     }
 
     pub fn setup_aa_wd_gate_extra_metas(&mut self) {
-        let setup_extra_metas_ix =
-            self.get_setup_extra_metas_ix(&self.token.auth.pubkey(), &AA_WD_ID);
+        let setup_extra_metas_ix = token_acl_interface::instruction::initialize_extra_account_metas(
+            &AA_WD_ID,
+            &self.token.auth.pubkey(),
+            &self.token.auth.pubkey(),
+            &self.token.mint,
+            &[],
+        );
         let set_metadata_ix =
             set_mint_tacl_metadata_ix(&self.token.mint, &self.token.auth.pubkey(), &AA_WD_ID);
         let tx = Transaction::new_signed_with_payer(
@@ -304,6 +362,148 @@ impl TestContext {
         assert!(res.is_ok());
     }
 
+    /// Creates the token-acl config for `gating_program`, initializes the gate's
+    /// extra metas through the standard instruction and enables both
+    /// permissionless operations.
+    pub fn setup_gate(&mut self, gating_program: &Pubkey) -> Pubkey {
+        let mint_cfg_pk = self.setup_token_acl(gating_program);
+        let auth = self.token.auth.insecure_clone();
+
+        let ix = token_acl_interface::instruction::initialize_extra_account_metas(
+            gating_program,
+            &auth.pubkey(),
+            &auth.pubkey(),
+            &self.token.mint,
+            &[],
+        );
+        let res = self.send(&[ix], &[&auth]);
+        assert!(res.is_ok());
+
+        self.toggle_permissionless(true, true);
+
+        mint_cfg_pk
+    }
+
+    pub fn toggle_permissionless(&mut self, freeze_enabled: bool, thaw_enabled: bool) {
+        let auth = self.token.auth.insecure_clone();
+        let ix = token_acl_client::instructions::TogglePermissionlessInstructionsBuilder::new()
+            .authority(auth.pubkey())
+            .freeze_enabled(freeze_enabled)
+            .thaw_enabled(thaw_enabled)
+            .mint_config(token_acl_client::accounts::MintConfig::find_pda(&self.token.mint).0)
+            .instruction();
+
+        let res = self.send(&[ix], &[&auth]);
+        assert!(res.is_ok());
+    }
+
+    pub async fn thaw_permissionless_ix(
+        &self,
+        signer: &Pubkey,
+        token_account: &Pubkey,
+        token_account_owner: &Pubkey,
+    ) -> Instruction {
+        token_acl_client::create_thaw_permissionless_instruction_with_extra_metas(
+            signer,
+            token_account,
+            &self.token.mint,
+            &token_acl_client::accounts::MintConfig::find_pda(&self.token.mint).0,
+            &TOKEN_PROGRAM_ID,
+            token_account_owner,
+            false,
+            |pubkey| {
+                let data = self.vm.get_account(&pubkey).map(|a| a.data);
+                async move { Ok(data) }
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Like [`Self::thaw_permissionless_ix`], resolving extra metas with
+    /// `supplied_accounts` overlaid on the vm state.
+    pub async fn thaw_permissionless_ix_with_supplied_accounts(
+        &self,
+        signer: &Pubkey,
+        token_account: &Pubkey,
+        token_account_owner: &Pubkey,
+        supplied_accounts: &[(Pubkey, Vec<u8>)],
+    ) -> Instruction {
+        let mint_config_pk = token_acl_client::accounts::MintConfig::find_pda(&self.token.mint).0;
+        let mint_config = token_acl_client::accounts::MintConfig::from_bytes(
+            &self.vm.get_account(&mint_config_pk).unwrap().data,
+        )
+        .unwrap();
+        let flag_account = token_acl_client::accounts::FlagAccount::find_pda(token_account).0;
+
+        let mut ix = token_acl_client::instructions::ThawPermissionlessBuilder::new()
+            .gating_program(mint_config.gating_program)
+            .authority(*signer)
+            .mint(self.token.mint)
+            .token_account(*token_account)
+            .token_account_owner(*token_account_owner)
+            .mint_config(mint_config_pk)
+            .token_program(TOKEN_PROGRAM_ID)
+            .flag_account(flag_account)
+            .system_program(ID)
+            .instruction();
+
+        token_acl_interface::offchain::add_extra_account_metas_for_thaw_with_hook(
+            &mut ix,
+            &mint_config.gating_program,
+            signer,
+            token_account,
+            &self.token.mint,
+            token_account_owner,
+            &flag_account,
+            |pubkey| {
+                let data = self.vm.get_account(&pubkey).map(|a| a.data);
+                async move { Ok(data) }
+            },
+            |pubkey| {
+                supplied_accounts
+                    .iter()
+                    .find(|(key, _)| key == pubkey)
+                    .map(|(_, data)| data.clone())
+            },
+        )
+        .await
+        .unwrap();
+
+        ix
+    }
+
+    pub async fn freeze_permissionless_ix(
+        &self,
+        signer: &Pubkey,
+        token_account: &Pubkey,
+        token_account_owner: &Pubkey,
+    ) -> Instruction {
+        token_acl_client::create_freeze_permissionless_instruction_with_extra_metas(
+            signer,
+            token_account,
+            &self.token.mint,
+            &token_acl_client::accounts::MintConfig::find_pda(&self.token.mint).0,
+            &TOKEN_PROGRAM_ID,
+            token_account_owner,
+            false,
+            |pubkey| {
+                let data = self.vm.get_account(&pubkey).map(|a| a.data);
+                async move { Ok(data) }
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    pub fn token_account_state(&self, token_account: &Pubkey) -> AccountState {
+        let data = self.vm.get_account(token_account).unwrap().data;
+        StateWithExtensions::<spl_token_2022_interface::state::Account>::unpack(&data)
+            .unwrap()
+            .base
+            .state
+    }
+
     pub fn thaw(&mut self, token_account: &Pubkey) {
         let ix = token_acl_client::instructions::ThawBuilder::new()
             .authority(self.token.auth.pubkey())
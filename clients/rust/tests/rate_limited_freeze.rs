@@ -0,0 +1,181 @@
+pub mod program_test;
+use litesvm::types::TransactionResult;
+use rate_limited_freeze_gate_program::{
+    create_caller_counter, create_config,
+    error::RateLimitError,
+    state::{CallerCounter, RateLimitConfig},
+    ID as RATE_LIMITED_FREEZE_ID,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, InstructionError},
+    signature::Keypair,
+    signer::Signer,
+    transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+
+use crate::program_test::TestContext;
+
+const WINDOW_SLOTS: u64 = 100;
+const MINT_QUOTA: u8 = 3;
+const CALLER_QUOTA: u8 = 2;
+
+fn setup() -> TestContext {
+    let mut tc = TestContext::new_with_default_account_state(AccountState::Initialized);
+    tc.add_program(&RATE_LIMITED_FREEZE_ID, "rate_limited_freeze_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&RATE_LIMITED_FREEZE_ID);
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        WINDOW_SLOTS,
+        MINT_QUOTA,
+        CALLER_QUOTA,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    tc
+}
+
+fn register_caller(tc: &mut TestContext) -> Keypair {
+    let caller = Keypair::new();
+    let res = tc.vm.airdrop(&caller.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+
+    let ix = create_caller_counter(&caller.pubkey(), &tc.token.mint);
+    let res = tc.send(&[ix], &[&caller]);
+    assert!(res.is_ok());
+
+    caller
+}
+
+/// Creates a token account and freezes it permissionlessly through `caller`.
+async fn freeze_new_account(tc: &mut TestContext, caller: &Keypair) -> TransactionResult {
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let ix = tc
+        .freeze_permissionless_ix(&caller.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    tc.send(&[ix], &[caller])
+}
+
+fn gate_error(error: RateLimitError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+#[tokio::test]
+async fn test_rate_limited_freeze_quota_exhaustion() {
+    let mut tc = setup();
+    tc.vm.warp_to_slot(1_000);
+
+    let first_caller = register_caller(&mut tc);
+    for _ in 0..CALLER_QUOTA {
+        let res = freeze_new_account(&mut tc, &first_caller).await;
+        assert!(res.is_ok());
+    }
+    let res = freeze_new_account(&mut tc, &first_caller).await;
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(RateLimitError::CallerQuotaExceeded)
+    );
+
+    let second_caller = register_caller(&mut tc);
+    let res = freeze_new_account(&mut tc, &second_caller).await;
+    assert!(res.is_ok());
+    let res = freeze_new_account(&mut tc, &second_caller).await;
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(RateLimitError::MintQuotaExceeded)
+    );
+
+    let unregistered_caller = Keypair::new();
+    let res = tc.vm.airdrop(&unregistered_caller.pubkey(), 1_000_000_000);
+    assert!(res.is_ok());
+    let res = freeze_new_account(&mut tc, &unregistered_caller).await;
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(RateLimitError::InvalidCallerCounter)
+    );
+}
+
+#[tokio::test]
+async fn test_rate_limited_freeze_window_rollover() {
+    let mut tc = setup();
+    let caller = register_caller(&mut tc);
+
+    tc.vm.warp_to_slot(1_000);
+    for _ in 0..CALLER_QUOTA {
+        let res = freeze_new_account(&mut tc, &caller).await;
+        assert!(res.is_ok());
+    }
+
+    tc.vm.warp_to_slot(1_000 + WINDOW_SLOTS - 1);
+    let res = freeze_new_account(&mut tc, &caller).await;
+    assert_eq!(
+        res.err().unwrap().err,
+        gate_error(RateLimitError::CallerQuotaExceeded)
+    );
+
+    tc.vm.warp_to_slot(1_000 + WINDOW_SLOTS);
+    for _ in 0..CALLER_QUOTA {
+        let res = freeze_new_account(&mut tc, &caller).await;
+        assert!(res.is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limited_freeze_direct_call_does_not_use_quota() {
+    let mut tc = setup();
+    tc.vm.warp_to_slot(1_000);
+    let caller = register_caller(&mut tc);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let config = RateLimitConfig::find_pda(&tc.token.mint).0;
+    let counter = CallerCounter::find_pda(&tc.token.mint, &caller.pubkey()).0;
+    let config_before = tc.vm.get_account(&config).unwrap();
+    let counter_before = tc.vm.get_account(&counter).unwrap();
+
+    // calling the gate directly, without token-acl setting the flag account
+    let mut ix = token_acl_interface::instruction::can_freeze_permissionless(
+        &RATE_LIMITED_FREEZE_ID,
+        &caller.pubkey(),
+        &user_token_account,
+        &tc.token.mint,
+        &user.pubkey(),
+        &token_acl_interface::get_flag_account_address(
+            &user_token_account,
+            &token_acl_interface::TOKEN_ACL_ID,
+        ),
+    );
+    ix.accounts.extend([
+        AccountMeta::new_readonly(
+            token_acl_interface::get_freeze_extra_account_metas_address(
+                &tc.token.mint,
+                &RATE_LIMITED_FREEZE_ID,
+            ),
+            false,
+        ),
+        AccountMeta::new(config, false),
+        AccountMeta::new(counter, false),
+    ]);
+    let res = tc.send(&[ix], &[&caller]);
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountOwner)
+    );
+
+    assert_eq!(tc.vm.get_account(&config).unwrap(), config_before);
+    assert_eq!(tc.vm.get_account(&counter).unwrap(), counter_before);
+
+    for _ in 0..CALLER_QUOTA {
+        let res = freeze_new_account(&mut tc, &caller).await;
+        assert!(res.is_ok());
+    }
+}
@@ -4,6 +4,7 @@ use solana_sdk::{
     instruction::InstructionError,
     program_option::COption,
     program_pack::Pack,
+    pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     transaction::{Transaction, TransactionError},
@@ -11,7 +12,7 @@ use solana_sdk::{
 use solana_system_interface::program::ID as SYSTEM_PROGRAM_ID;
 use spl_associated_token_account_interface::{
     address::get_associated_token_address_with_program_id,
-    instruction::create_associated_token_account_idempotent,
+    instruction::{create_associated_token_account, create_associated_token_account_idempotent},
 };
 use spl_token_2022_interface::{
     extension::StateWithExtensions,
@@ -19,7 +20,9 @@ use spl_token_2022_interface::{
     ID as TOKEN_PROGRAM_ID,
 };
 
-use crate::program_test::TestContext;
+use token_acl_client::{mock_associated_token_account_data, OverlayAccountFetcher};
+
+use crate::program_test::{TestContext, VmAccountFetcher};
 
 #[test]
 fn test_thaw_permissionless() {
@@ -408,6 +411,7 @@ async fn test_thaw_permissionless_always_block() {
 }
 
 #[tokio::test]
+#[allow(deprecated)]
 async fn test_thaw_permissionless_always_allow_with_deps() {
     let mut tc = TestContext::new();
     let mint_cfg_pk = tc.setup_token_acl(&program_test::AA_WD_ID);
@@ -561,3 +565,129 @@ async fn test_thaw_permissionless_always_allow_with_deps() {
     //println!("account: {:?}", account);
     assert_eq!(account.base.state, AccountState::Initialized);
 }
+
+#[tokio::test]
+async fn test_thaw_permissionless_from_token_account_or_owner() {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&program_test::AA_WD_ID);
+    tc.setup_aa_wd_gate_extra_metas();
+    tc.toggle_permissionless(false, true);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let ix_from_token_account =
+        token_acl_client::create_thaw_permissionless_instruction_for_token_account(
+            &user.pubkey(),
+            &user_token_account,
+            false,
+            VmAccountFetcher(&tc.vm),
+        )
+        .await
+        .unwrap();
+    let ix_from_owner =
+        token_acl_client::create_thaw_permissionless_instruction_for_mint_and_owner(
+            &user.pubkey(),
+            &tc.token.mint,
+            &user.pubkey(),
+            false,
+            VmAccountFetcher(&tc.vm),
+        )
+        .await
+        .unwrap();
+    assert_eq!(ix_from_token_account, ix);
+    assert_eq!(ix_from_owner, ix);
+
+    let res = token_acl_client::create_thaw_permissionless_instruction_for_token_account(
+        &user.pubkey(),
+        &Keypair::new().pubkey(),
+        false,
+        VmAccountFetcher(&tc.vm),
+    )
+    .await;
+    assert!(res.is_err());
+
+    let res = tc.send(&[ix_from_token_account], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
+
+#[tokio::test]
+async fn test_thaw_permissionless_token_program_from_account_owner() {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&program_test::AA_WD_ID);
+    tc.setup_aa_wd_gate_extra_metas();
+    tc.toggle_permissionless(false, true);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    // same accounts, owned by a token program at another address
+    let token_program = Pubkey::new_unique();
+    for pubkey in [tc.token.mint, user_token_account] {
+        let mut account = tc.vm.get_account(&pubkey).unwrap();
+        account.owner = token_program;
+        tc.vm.set_account(pubkey, account).unwrap();
+    }
+    let ata = get_associated_token_address_with_program_id(
+        &user.pubkey(),
+        &tc.token.mint,
+        &token_program,
+    );
+    assert_ne!(ata, user_token_account);
+
+    let ix = token_acl_client::create_thaw_permissionless_instruction_for_token_account(
+        &user.pubkey(),
+        &user_token_account,
+        false,
+        VmAccountFetcher(&tc.vm),
+    )
+    .await
+    .unwrap();
+    assert_eq!(ix.accounts[2].pubkey, user_token_account);
+    assert_eq!(ix.accounts[6].pubkey, token_program);
+
+    let fetcher = VmAccountFetcher(&tc.vm);
+    let mint_data = tc.vm.get_account(&tc.token.mint).unwrap().data;
+    let ata_data =
+        mock_associated_token_account_data(&tc.token.mint, &mint_data, &user.pubkey()).unwrap();
+    let ix = token_acl_client::create_thaw_permissionless_instruction_for_mint_and_owner(
+        &user.pubkey(),
+        &tc.token.mint,
+        &user.pubkey(),
+        false,
+        OverlayAccountFetcher::new(&fetcher).with_account(ata, ata_data),
+    )
+    .await
+    .unwrap();
+    assert_eq!(ix.accounts[2].pubkey, ata);
+    assert_eq!(ix.accounts[6].pubkey, token_program);
+
+    let ixs = token_acl_client::create_ata_and_thaw_permissionless_instructions(
+        &user.pubkey(),
+        &tc.token.mint,
+        &token_program,
+        &user.pubkey(),
+        false,
+        &fetcher,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        ixs[0],
+        create_associated_token_account(
+            &user.pubkey(),
+            &user.pubkey(),
+            &tc.token.mint,
+            &token_program
+        )
+    );
+    assert_eq!(ixs[1].accounts[2].pubkey, ata);
+    assert_eq!(ixs[1].accounts[6].pubkey, token_program);
+}
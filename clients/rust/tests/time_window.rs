@@ -0,0 +1,179 @@
+pub mod program_test;
+use litesvm::types::TransactionResult;
+use solana_sdk::{
+    clock::Clock, instruction::InstructionError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::TransactionError,
+};
+use spl_token_2022_interface::state::AccountState;
+use time_window_gate_program::{
+    create_config, error::TimeWindowError, set_window, state::WindowUnit, ID as TIME_WINDOW_ID,
+};
+
+use crate::program_test::TestContext;
+
+const START: i64 = 1_750_000_000;
+const END: i64 = START + 7 * 24 * 60 * 60;
+
+fn setup(unit: WindowUnit, start: i64, end: i64) -> (TestContext, Pubkey) {
+    let mut tc = TestContext::new();
+    tc.add_program(&TIME_WINDOW_ID, "time_window_gate_program");
+    let mint_cfg_pk = tc.setup_gate(&TIME_WINDOW_ID);
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = create_config(
+        &auth.pubkey(),
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        unit,
+        start,
+        end,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    (tc, mint_cfg_pk)
+}
+
+fn set_unix_timestamp(tc: &mut TestContext, unix_timestamp: i64) {
+    let mut clock = tc.vm.get_sysvar::<Clock>();
+    clock.unix_timestamp = unix_timestamp;
+    tc.vm.set_sysvar(&clock);
+}
+
+fn assert_gate_error(res: TransactionResult, error: TimeWindowError) {
+    assert_eq!(
+        res.err().unwrap().err,
+        TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_time_window_subscription_window() {
+    let (mut tc, _) = setup(WindowUnit::UnixTimestamp, START, END);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    set_unix_timestamp(&mut tc, START - 1);
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_gate_error(res, TimeWindowError::WindowNotOpen);
+
+    set_unix_timestamp(&mut tc, START);
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+
+    set_unix_timestamp(&mut tc, END - 1);
+    let ix = tc
+        .freeze_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_gate_error(res, TimeWindowError::WindowNotClosed);
+
+    set_unix_timestamp(&mut tc, END);
+    let ix = tc
+        .freeze_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Frozen
+    );
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_gate_error(res, TimeWindowError::WindowClosed);
+}
+
+#[tokio::test]
+async fn test_time_window_slot_lockup() {
+    const LOCKUP_SLOT: i64 = 1_000;
+    let (mut tc, _) = setup(WindowUnit::Slot, LOCKUP_SLOT, i64::MAX);
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    tc.vm.warp_to_slot(LOCKUP_SLOT as u64 - 1);
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_gate_error(res, TimeWindowError::WindowNotOpen);
+
+    tc.vm.warp_to_slot(LOCKUP_SLOT as u64);
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+
+    // the lockup never closes, so the gate never freezes
+    tc.vm.warp_to_slot(u32::MAX as u64);
+    let ix = tc
+        .freeze_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_gate_error(res, TimeWindowError::WindowNotClosed);
+}
+
+#[tokio::test]
+async fn test_time_window_set_window() {
+    let (mut tc, mint_cfg_pk) = setup(WindowUnit::UnixTimestamp, START, END);
+    let auth = tc.token.auth.insecure_clone();
+
+    let user = Keypair::new();
+    let user_token_account = tc.create_token_account(&user);
+
+    let ix = set_window(
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        WindowUnit::UnixTimestamp,
+        END,
+        END,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert_gate_error(res, TimeWindowError::InvalidWindow);
+
+    set_unix_timestamp(&mut tc, END);
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert_gate_error(res, TimeWindowError::WindowClosed);
+
+    // extend the sale by a day
+    let ix = set_window(
+        &auth.pubkey(),
+        &tc.token.mint,
+        &mint_cfg_pk,
+        WindowUnit::UnixTimestamp,
+        START,
+        END + 24 * 60 * 60,
+    );
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    let ix = tc
+        .thaw_permissionless_ix(&user.pubkey(), &user_token_account, &user.pubkey())
+        .await;
+    let res = tc.send(&[ix], &[&user]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&user_token_account),
+        AccountState::Initialized
+    );
+}
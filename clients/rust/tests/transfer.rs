@@ -0,0 +1,146 @@
+pub mod program_test;
+use solana_program_error::ProgramError;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+use spl_token_2022_interface::{
+    error::TokenError,
+    extension::StateWithExtensions,
+    instruction::mint_to,
+    state::{Account, AccountState},
+    ID as TOKEN_PROGRAM_ID,
+};
+use token_acl_client::{
+    create_transfer_instructions, errors::TokenAclError, programs::TOKEN_ACL_ID, AccountFetchError,
+};
+
+use crate::program_test::{TestContext, AA_WD_ID};
+
+/// Sets up a thawable mint and a sender holding 1_000 tokens.
+fn setup() -> (TestContext, Keypair) {
+    let mut tc = TestContext::new();
+    tc.setup_token_acl(&AA_WD_ID);
+    tc.setup_aa_wd_gate_extra_metas();
+    tc.toggle_permissionless(false, true);
+
+    let sender = Keypair::new();
+    let sender_token_account = tc.create_token_account(&sender);
+    tc.thaw(&sender_token_account);
+
+    let auth = tc.token.auth.insecure_clone();
+    let ix = mint_to(
+        &TOKEN_PROGRAM_ID,
+        &tc.token.mint,
+        &sender_token_account,
+        &auth.pubkey(),
+        &[],
+        1_000,
+    )
+    .unwrap();
+    let res = tc.send(&[ix], &[&auth]);
+    assert!(res.is_ok());
+
+    (tc, sender)
+}
+
+async fn transfer_ixs(
+    tc: &TestContext,
+    sender: &Keypair,
+    recipient: &Pubkey,
+    amount: u64,
+) -> Result<Vec<solana_sdk::instruction::Instruction>, AccountFetchError> {
+    create_transfer_instructions(
+        &sender.pubkey(),
+        &sender.pubkey(),
+        &tc.token.mint,
+        recipient,
+        amount,
+        |pubkey| {
+            let data = tc.vm.get_account(&pubkey).map(|a| a.data);
+            async move { Ok(data) }
+        },
+    )
+    .await
+}
+
+fn balance(tc: &TestContext, owner: &Pubkey) -> u64 {
+    let token_account =
+        get_associated_token_address_with_program_id(owner, &tc.token.mint, &TOKEN_PROGRAM_ID);
+    let data = tc.vm.get_account(&token_account).unwrap().data;
+    StateWithExtensions::<Account>::unpack(&data)
+        .unwrap()
+        .base
+        .amount
+}
+
+#[tokio::test]
+async fn test_transfer_creates_and_thaws_recipient() {
+    let (mut tc, sender) = setup();
+    let recipient = Pubkey::new_unique();
+    let recipient_token_account =
+        get_associated_token_address_with_program_id(&recipient, &tc.token.mint, &TOKEN_PROGRAM_ID);
+
+    let ixs = transfer_ixs(&tc, &sender, &recipient, 100).await.unwrap();
+    assert_eq!(
+        ixs.iter().map(|ix| ix.program_id).collect::<Vec<_>>(),
+        vec![
+            spl_associated_token_account_interface::program::ID,
+            TOKEN_ACL_ID,
+            TOKEN_PROGRAM_ID,
+        ]
+    );
+    let res = tc.send(&ixs, &[&sender]);
+    assert!(res.is_ok());
+    assert_eq!(
+        tc.token_account_state(&recipient_token_account),
+        AccountState::Initialized
+    );
+    assert_eq!(balance(&tc, &recipient), 100);
+    assert_eq!(balance(&tc, &sender.pubkey()), 900);
+
+    // the recipient is usable now, so only the transfer is left
+    let ixs = transfer_ixs(&tc, &sender, &recipient, 50).await.unwrap();
+    assert_eq!(ixs.len(), 1);
+    let res = tc.send(&ixs, &[&sender]);
+    assert!(res.is_ok());
+    assert_eq!(balance(&tc, &recipient), 150);
+
+    // a frozen recipient is thawed again
+    tc.freeze(&recipient_token_account);
+    let ixs = transfer_ixs(&tc, &sender, &recipient, 50).await.unwrap();
+    assert_eq!(
+        ixs.iter().map(|ix| ix.program_id).collect::<Vec<_>>(),
+        vec![TOKEN_ACL_ID, TOKEN_PROGRAM_ID]
+    );
+    let res = tc.send(&ixs, &[&sender]);
+    assert!(res.is_ok());
+    assert_eq!(balance(&tc, &recipient), 200);
+}
+
+#[tokio::test]
+async fn test_transfer_checks_recipient_is_thawable() {
+    let (mut tc, sender) = setup();
+    tc.toggle_permissionless(false, false);
+
+    let err = transfer_ixs(&tc, &sender, &Pubkey::new_unique(), 100)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        *err.downcast::<TokenAclError>().unwrap(),
+        TokenAclError::PermissionlessThawNotEnabled
+    );
+
+    // the sender cannot send from a frozen account either
+    let sender_token_account = get_associated_token_address_with_program_id(
+        &sender.pubkey(),
+        &tc.token.mint,
+        &TOKEN_PROGRAM_ID,
+    );
+    tc.freeze(&sender_token_account);
+    let err = transfer_ixs(&tc, &sender, &Pubkey::new_unique(), 100)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        *err.downcast::<ProgramError>().unwrap(),
+        ProgramError::from(TokenError::AccountFrozen)
+    );
+}
@@ -0,0 +1,15 @@
+use solana_program_error::ProgramError;
+
+/// Errors of the allow decision. Codes below 3 are list management
+/// [`WalletListError`](token_acl_gate_framework::wallet_list::WalletListError)s.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AllowListError {
+    WalletNotListed = 3,
+    WalletListed,
+}
+
+impl From<AllowListError> for ProgramError {
+    fn from(e: AllowListError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
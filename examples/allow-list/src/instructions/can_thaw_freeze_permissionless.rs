@@ -0,0 +1,45 @@
+use solana_program::account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_gate_framework::wallet_list;
+use token_acl_interface::gate::GateAccounts;
+
+use crate::error::AllowListError;
+
+pub struct CanThawFreezePermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    /// Entry of the token account owner, resolved by token-acl from the extra metas.
+    pub list_entry: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawFreezePermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [list_entry, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self { gate, list_entry })
+    }
+
+    /// Whether the token account owner is on the mint's list.
+    fn is_listed(&self) -> Result<bool, ProgramError> {
+        wallet_list::is_listed::<crate::AllowList>(
+            self.list_entry,
+            self.gate.mint.key,
+            self.gate.token_account_owner.key,
+        )
+    }
+
+    pub fn process_thaw(&self) -> ProgramResult {
+        if !self.is_listed()? {
+            return Err(AllowListError::WalletNotListed.into());
+        }
+        Ok(())
+    }
+
+    pub fn process_freeze(&self) -> ProgramResult {
+        if self.is_listed()? {
+            return Err(AllowListError::WalletListed.into());
+        }
+        Ok(())
+    }
+}
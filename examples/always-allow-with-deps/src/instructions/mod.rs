@@ -1,5 +1,3 @@
 mod can_thaw_freeze_permissionless;
-mod initialize_extra_metas;
 
 pub use can_thaw_freeze_permissionless::*;
-pub use initialize_extra_metas::*;
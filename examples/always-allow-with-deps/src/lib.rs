@@ -1,10 +1,10 @@
-use solana_program::{
-    account_info::AccountInfo, declare_id, entrypoint, entrypoint::ProgramResult, pubkey::Pubkey,
-};
+use solana_program::{declare_id, entrypoint::ProgramResult, pubkey::Pubkey};
 use solana_program_error::ProgramError;
-use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
-use token_acl_interface::instruction::{
-    CanFreezePermissionlessInstruction, CanThawPermissionlessInstruction,
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, pubkey_data::PubkeyData, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{
+    gate::GateAccounts, instruction::EfficientBlockAllowInstruction,
+    FREEZE_EXTRA_ACCOUNT_METAS_SEED, THAW_EXTRA_ACCOUNT_METAS_SEED,
 };
 
 pub mod instructions;
@@ -12,36 +12,72 @@ pub use instructions::*;
 
 declare_id!("Eba1ts11111111111111111111111111111111111114");
 
-entrypoint!(process_instruction);
-fn process_instruction<'a>(
-    _program_id: &Pubkey,
-    accounts: &'a [AccountInfo<'a>],
-    instruction_data: &'a [u8],
-) -> ProgramResult {
-    let (discriminator, _remaining) = instruction_data.split_at(ArrayDiscriminator::LENGTH);
-
-    match discriminator {
-        InitializeExtraMetas::DISCRIMINATOR_SLICE => {
-            InitializeExtraMetas::try_from(accounts)?.process()
-        }
-        CanThawPermissionlessInstruction::SPL_DISCRIMINATOR_SLICE => {
-            CanThawFreezePermissionless::try_from(accounts)?.process()
-        }
-        CanFreezePermissionlessInstruction::SPL_DISCRIMINATOR_SLICE => {
-            CanThawFreezePermissionless::try_from(accounts)?.process()
-        }
-        _ => Err(CustomErrors::InvalidInstruction.into()),
+pub struct AlwaysAllowWithDeps;
+
+gate_program!(AlwaysAllowWithDeps);
+
+impl GateProgram for AlwaysAllowWithDeps {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process()
     }
-}
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum CustomErrors {
-    InvalidInstruction,
-    UnsupportedInstruction,
-}
+    fn can_freeze(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        instruction: EfficientBlockAllowInstruction,
+        _accounts: &[solana_program::account_info::AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        let extra_metas_seed = match instruction {
+            EfficientBlockAllowInstruction::CanThawPermissionless => THAW_EXTRA_ACCOUNT_METAS_SEED,
+            EfficientBlockAllowInstruction::CanFreezePermissionless => {
+                FREEZE_EXTRA_ACCOUNT_METAS_SEED
+            }
+        };
 
-impl From<CustomErrors> for ProgramError {
-    fn from(e: CustomErrors) -> Self {
-        ProgramError::Custom(e as u32)
+        Ok(vec![
+            // [6] associated token program
+            ExtraAccountMeta::new_with_pubkey(
+                &spl_associated_token_account_interface::program::ID,
+                false,
+                false,
+            )?,
+            // [7] token program
+            ExtraAccountMeta::new_with_pubkey(&spl_token_2022_interface::ID, false, false)?,
+            // [8] token account owner
+            ExtraAccountMeta::new_with_pubkey_data(
+                &PubkeyData::AccountData {
+                    account_index: 1,
+                    data_index: 32,
+                },
+                false,
+                false,
+            )?,
+            // [9] ata
+            ExtraAccountMeta::new_external_pda_with_seeds(
+                6,
+                &[
+                    Seed::AccountKey { index: 3 }, // owner
+                    Seed::AccountKey { index: 7 }, // token program
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+            // [10] extra metas account
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: extra_metas_seed.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+        ])
     }
 }
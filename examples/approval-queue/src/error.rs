@@ -0,0 +1,17 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ApprovalQueueError {
+    InvalidQueueConfig,
+    InvalidOperator,
+    InvalidThawRequest,
+    RequestNotFound,
+    RequestNotPending,
+    RequestNotApproved,
+}
+
+impl From<ApprovalQueueError> for ProgramError {
+    fn from(e: ApprovalQueueError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,54 @@
+use solana_program::account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::gate::GateAccounts;
+
+use crate::{
+    error::ApprovalQueueError,
+    state::{load_thaw_request, RequestStatus},
+};
+
+pub struct CanThawPermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    /// Thaw request of the token account owner, resolved by token-acl from the
+    /// extra metas as writable.
+    pub thaw_request: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawPermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [thaw_request, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self { gate, thaw_request })
+    }
+
+    /// Succeeds for an approved request of the owner, and consumes it.
+    ///
+    /// The request is only closed here because the flag account, checked when
+    /// loading the gate accounts, proves token-acl is thawing the account
+    /// right after this call. Its lamports stay in the request address until
+    /// `ReclaimRent` moves them back to the owner.
+    pub fn process(&self) -> ProgramResult {
+        if self.thaw_request.owner != &crate::ID {
+            return Err(ApprovalQueueError::RequestNotFound.into());
+        }
+        {
+            let data = self.thaw_request.try_borrow_data()?;
+            let request = load_thaw_request(&data)?;
+            if &request.mint != self.gate.mint.key
+                || &request.owner != self.gate.token_account_owner.key
+            {
+                return Err(ApprovalQueueError::InvalidThawRequest.into());
+            }
+            if request.status()? != RequestStatus::Approved {
+                return Err(ApprovalQueueError::RequestNotApproved.into());
+            }
+        }
+
+        self.thaw_request.resize(0)?;
+        self.thaw_request
+            .assign(&solana_system_interface::program::ID);
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::close_account;
+
+use crate::{
+    error::ApprovalQueueError,
+    state::{load_thaw_request, ThawRequest},
+};
+
+/// Closes a thaw request of the owner whatever its status, e.g. to file a new
+/// one after a rejection.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("approval-queue-gate:cancel-request")]
+pub struct CancelRequest<'a> {
+    pub owner: &'a AccountInfo<'a>,
+    pub thaw_request: &'a AccountInfo<'a>,
+}
+
+impl CancelRequest<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        if self.thaw_request.owner != &crate::ID {
+            return Err(ApprovalQueueError::RequestNotFound.into());
+        }
+        {
+            let data = self.thaw_request.try_borrow_data()?;
+            let request = load_thaw_request(&data)?;
+            if &request.owner != self.owner.key {
+                return Err(ApprovalQueueError::InvalidThawRequest.into());
+            }
+        }
+        if !self.owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        close_account(self.thaw_request, self.owner)
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CancelRequest<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [owner, thaw_request] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            owner,
+            thaw_request,
+        })
+    }
+}
+
+pub fn cancel_request(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(ThawRequest::find_pda(mint, owner).0, false),
+        ],
+        data: CancelRequest::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
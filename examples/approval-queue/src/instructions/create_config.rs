@@ -0,0 +1,100 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::{check_token_acl_authority, create_pda_account};
+
+use crate::state::QueueConfig;
+
+/// Sets the operator reviewing the thaw requests of a mint. Only the
+/// token-acl authority of the mint can create the config.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("approval-queue-gate:create-config")]
+pub struct CreateConfig<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub queue_config: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub queue_config_bump: u8,
+}
+
+impl CreateConfig<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let operator =
+            Pubkey::try_from(remaining_data).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        let bump_seed = [self.queue_config_bump];
+        create_pda_account(
+            self.payer,
+            self.queue_config,
+            QueueConfig::LEN,
+            &crate::ID,
+            &[QueueConfig::SEED_PREFIX, self.mint.key.as_ref(), &bump_seed],
+        )?;
+
+        let mut data = self.queue_config.try_borrow_mut_data()?;
+        let config = bytemuck::from_bytes_mut::<QueueConfig>(&mut data);
+        config.discriminator = QueueConfig::DISCRIMINATOR;
+        config.bump = self.queue_config_bump;
+        config.mint = *self.mint.key;
+        config.operator = operator;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, queue_config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (queue_config_address, queue_config_bump) = QueueConfig::find_pda(mint.key);
+        if queue_config.key != &queue_config_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            queue_config,
+            system_program,
+            queue_config_bump,
+        })
+    }
+}
+
+pub fn create_config(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    operator: &Pubkey,
+) -> Instruction {
+    let mut data = CreateConfig::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(operator.as_ref());
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(QueueConfig::find_pda(mint).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
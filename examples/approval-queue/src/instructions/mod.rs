@@ -0,0 +1,13 @@
+mod can_thaw_permissionless;
+mod cancel_request;
+mod create_config;
+mod reclaim_rent;
+mod request_thaw;
+mod review_request;
+
+pub use can_thaw_permissionless::*;
+pub use cancel_request::*;
+pub use create_config::*;
+pub use reclaim_rent::*;
+pub use request_thaw::*;
+pub use review_request::*;
@@ -0,0 +1,88 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{error::ApprovalQueueError, state::ThawRequest};
+
+/// Returns the rent of a thaw request closed by a thaw to its owner.
+///
+/// token-acl only passes the owner to the gate as read-only, so the gate
+/// closes the request in place and the lamports are moved here, usually in
+/// the same transaction as `ThawPermissionless`. Anyone can call it.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("approval-queue-gate:reclaim-rent")]
+pub struct ReclaimRent<'a> {
+    pub owner: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub thaw_request: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub thaw_request_bump: u8,
+}
+
+impl ReclaimRent<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        if self.thaw_request.owner != &solana_system_interface::program::ID
+            || !self.thaw_request.data_is_empty()
+        {
+            return Err(ApprovalQueueError::InvalidThawRequest.into());
+        }
+
+        let bump_seed = [self.thaw_request_bump];
+        let ix = solana_system_interface::instruction::transfer(
+            self.thaw_request.key,
+            self.owner.key,
+            self.thaw_request.lamports(),
+        );
+        invoke_signed(
+            &ix,
+            &[self.thaw_request.clone(), self.owner.clone()],
+            &[&[
+                ThawRequest::SEED_PREFIX,
+                self.mint.key.as_ref(),
+                self.owner.key.as_ref(),
+                &bump_seed,
+            ]],
+        )
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for ReclaimRent<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [owner, mint, thaw_request, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (thaw_request_address, thaw_request_bump) = ThawRequest::find_pda(mint.key, owner.key);
+        if thaw_request.key != &thaw_request_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            owner,
+            mint,
+            thaw_request,
+            system_program,
+            thaw_request_bump,
+        })
+    }
+}
+
+pub fn reclaim_rent(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(ThawRequest::find_pda(mint, owner).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data: ReclaimRent::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
@@ -0,0 +1,90 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::create_pda_account;
+
+use crate::state::{RequestStatus, ThawRequest};
+
+/// Opens a pending thaw request for the owner, who pays for it and gets the
+/// rent back once the request is closed.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("approval-queue-gate:request-thaw")]
+pub struct RequestThaw<'a> {
+    pub owner: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub thaw_request: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub thaw_request_bump: u8,
+}
+
+impl RequestThaw<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        if !self.owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bump_seed = [self.thaw_request_bump];
+        create_pda_account(
+            self.owner,
+            self.thaw_request,
+            ThawRequest::LEN,
+            &crate::ID,
+            &[
+                ThawRequest::SEED_PREFIX,
+                self.mint.key.as_ref(),
+                self.owner.key.as_ref(),
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.thaw_request.try_borrow_mut_data()?;
+        let request = bytemuck::from_bytes_mut::<ThawRequest>(&mut data);
+        request.discriminator = ThawRequest::DISCRIMINATOR;
+        request.bump = self.thaw_request_bump;
+        request.status = RequestStatus::Pending as u8;
+        request.mint = *self.mint.key;
+        request.owner = *self.owner.key;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for RequestThaw<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [owner, mint, thaw_request, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (thaw_request_address, thaw_request_bump) = ThawRequest::find_pda(mint.key, owner.key);
+        if thaw_request.key != &thaw_request_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            owner,
+            mint,
+            thaw_request,
+            system_program,
+            thaw_request_bump,
+        })
+    }
+}
+
+pub fn request_thaw(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(ThawRequest::find_pda(mint, owner).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data: RequestThaw::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
@@ -0,0 +1,101 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{
+    error::ApprovalQueueError,
+    state::{load_queue_config, load_thaw_request, QueueConfig, RequestStatus, ThawRequest},
+};
+
+/// Approves or rejects a pending thaw request. Only the operator of the mint
+/// can review requests.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("approval-queue-gate:review-request")]
+pub struct ReviewRequest<'a> {
+    pub operator: &'a AccountInfo<'a>,
+    pub queue_config: &'a AccountInfo<'a>,
+    pub thaw_request: &'a AccountInfo<'a>,
+}
+
+impl ReviewRequest<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let status = match remaining_data {
+            [1] => RequestStatus::Approved,
+            [2] => RequestStatus::Rejected,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        if self.queue_config.owner != &crate::ID {
+            return Err(ApprovalQueueError::InvalidQueueConfig.into());
+        }
+        let data = self.queue_config.try_borrow_data()?;
+        let config = load_queue_config(&data)?;
+        if !self.operator.is_signer || &config.operator != self.operator.key {
+            return Err(ApprovalQueueError::InvalidOperator.into());
+        }
+
+        if self.thaw_request.owner != &crate::ID {
+            return Err(ApprovalQueueError::RequestNotFound.into());
+        }
+        let mut data = self.thaw_request.try_borrow_mut_data()?;
+        let request = load_thaw_request(&data)?;
+        if request.mint != config.mint {
+            return Err(ApprovalQueueError::InvalidThawRequest.into());
+        }
+        if request.status()? != RequestStatus::Pending {
+            return Err(ApprovalQueueError::RequestNotPending.into());
+        }
+
+        bytemuck::from_bytes_mut::<ThawRequest>(&mut data).status = status as u8;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for ReviewRequest<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [operator, queue_config, thaw_request] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            operator,
+            queue_config,
+            thaw_request,
+        })
+    }
+}
+
+fn review_request(
+    operator: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    status: RequestStatus,
+) -> Instruction {
+    let mut data = ReviewRequest::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.push(status as u8);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*operator, true),
+            AccountMeta::new_readonly(QueueConfig::find_pda(mint).0, false),
+            AccountMeta::new(ThawRequest::find_pda(mint, owner).0, false),
+        ],
+        data,
+    }
+}
+
+pub fn approve_request(operator: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    review_request(operator, mint, owner, RequestStatus::Approved)
+}
+
+pub fn reject_request(operator: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    review_request(operator, mint, owner, RequestStatus::Rejected)
+}
@@ -0,0 +1,90 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts1111111111111111111111111111111111111D");
+
+/// Issuer-approval queue gate: owners file a thaw request, the mint's
+/// operator approves or rejects it, and anyone can then thaw the token account
+/// permissionlessly. An approved request is consumed by the thaw, relying on
+/// the flag account to know the gate is called by token-acl.
+///
+/// Approvals are one-off, so permissionless freeze is not supported and
+/// freezing stays with the token-acl authority.
+pub struct ApprovalQueue;
+
+gate_program!(ApprovalQueue);
+
+impl GateProgram for ApprovalQueue {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawPermissionless::from_gate_accounts(accounts)?.process()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        if instruction == EfficientBlockAllowInstruction::CanFreezePermissionless {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![
+            // [6] thaw request of the token account owner, closed by the gate
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::ThawRequest::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                    Seed::AccountKey { index: 3 }, // owner
+                ],
+                false,
+                true,
+            )?,
+        ])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateConfig::SPL_DISCRIMINATOR_SLICE => {
+                CreateConfig::try_from(accounts)?.process(remaining_data)
+            }
+            RequestThaw::SPL_DISCRIMINATOR_SLICE => {
+                RequestThaw::try_from(accounts)?.process(remaining_data)
+            }
+            ReviewRequest::SPL_DISCRIMINATOR_SLICE => {
+                ReviewRequest::try_from(accounts)?.process(remaining_data)
+            }
+            CancelRequest::SPL_DISCRIMINATOR_SLICE => {
+                CancelRequest::try_from(accounts)?.process(remaining_data)
+            }
+            ReclaimRent::SPL_DISCRIMINATOR_SLICE => {
+                ReclaimRent::try_from(accounts)?.process(remaining_data)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+
+use crate::error::ApprovalQueueError;
+
+/// Per-mint gate configuration, holding the operator reviewing thaw requests.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct QueueConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub operator: Pubkey,
+}
+
+impl QueueConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"approval_queue";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 32 + 32;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RequestStatus {
+    Pending = 0,
+    Approved = 1,
+    Rejected = 2,
+}
+
+/// Request of `owner` to get its token account of `mint` thawed, paid for by
+/// the owner and closed when the thaw goes through.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ThawRequest {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub status: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+}
+
+impl ThawRequest {
+    pub const SEED_PREFIX: &'static [u8] = b"thaw_request";
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const LEN: usize = 1 + 1 + 1 + 32 + 32;
+
+    pub fn find_pda(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED_PREFIX, mint.as_ref(), owner.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    pub fn status(&self) -> Result<RequestStatus, ProgramError> {
+        match self.status {
+            0 => Ok(RequestStatus::Pending),
+            1 => Ok(RequestStatus::Approved),
+            2 => Ok(RequestStatus::Rejected),
+            _ => Err(ApprovalQueueError::InvalidThawRequest.into()),
+        }
+    }
+}
+
+#[inline(always)]
+pub fn load_queue_config(data: &[u8]) -> Result<&QueueConfig, ProgramError> {
+    bytemuck::try_from_bytes::<QueueConfig>(data)
+        .map_err(|_| ApprovalQueueError::InvalidQueueConfig.into())
+        .and_then(|cfg: &QueueConfig| {
+            if cfg.discriminator == QueueConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(ApprovalQueueError::InvalidQueueConfig.into())
+            }
+        })
+}
+
+#[inline(always)]
+pub fn load_thaw_request(data: &[u8]) -> Result<&ThawRequest, ProgramError> {
+    bytemuck::try_from_bytes::<ThawRequest>(data)
+        .map_err(|_| ApprovalQueueError::InvalidThawRequest.into())
+        .and_then(|request: &ThawRequest| {
+            if request.discriminator == ThawRequest::DISCRIMINATOR {
+                Ok(request)
+            } else {
+                Err(ApprovalQueueError::InvalidThawRequest.into())
+            }
+        })
+}
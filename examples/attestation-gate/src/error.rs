@@ -0,0 +1,14 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AttestationError {
+    InvalidAttestationConfig,
+    ApprovalNotFound,
+    ApprovalExpired,
+}
+
+impl From<AttestationError> for ProgramError {
+    fn from(e: AttestationError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,122 @@
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    ed25519_program,
+    sysvar::{self, instructions::load_instruction_at_checked, Sysvar},
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::gate::GateAccounts;
+
+use crate::{
+    error::AttestationError,
+    state::{load_attestation_config, APPROVAL_MESSAGE_LEN},
+};
+
+/// Size of the ed25519 program instruction header: signature count and padding.
+const ED25519_HEADER_LEN: usize = 2;
+/// Size of one ed25519 signature offsets entry: seven `u16` fields.
+const ED25519_OFFSETS_LEN: usize = 14;
+
+pub struct CanThawPermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub attestation_config: &'a AccountInfo<'a>,
+    /// Instructions sysvar, resolved by token-acl from the extra metas.
+    pub instructions_sysvar: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawPermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [attestation_config, instructions_sysvar, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            attestation_config,
+            instructions_sysvar,
+        })
+    }
+
+    /// Succeeds when an ed25519 program instruction of the current transaction
+    /// verified a provider signature over `(mint, owner, expiry)` with an expiry
+    /// still in the future.
+    ///
+    /// Only signatures whose key, message and signature all live in the ed25519
+    /// instruction itself are considered, so the verified bytes are exactly the
+    /// ones read here.
+    pub fn process(&self) -> ProgramResult {
+        if self.attestation_config.owner != &crate::ID {
+            return Err(AttestationError::InvalidAttestationConfig.into());
+        }
+        let data = self.attestation_config.try_borrow_data()?;
+        let config = load_attestation_config(&data)?;
+        if &config.mint != self.gate.mint.key {
+            return Err(AttestationError::InvalidAttestationConfig.into());
+        }
+
+        let mint = self.gate.mint.key.as_ref();
+        let owner = self.gate.token_account_owner.key.as_ref();
+        let now = Clock::get()?.unix_timestamp;
+        let mut expired = false;
+
+        if !sysvar::instructions::check_id(self.instructions_sysvar.key) {
+            return Err(ProgramError::UnsupportedSysvar);
+        }
+        let instruction_count = {
+            let data = self.instructions_sysvar.try_borrow_data()?;
+            let count = data.get(..2).ok_or(ProgramError::InvalidAccountData)?;
+            u16::from_le_bytes([count[0], count[1]])
+        };
+        for index in 0..instruction_count as usize {
+            let ix = load_instruction_at_checked(index, self.instructions_sysvar)?;
+            if ix.program_id != ed25519_program::ID {
+                continue;
+            }
+
+            for (provider, message) in self_contained_signatures(&ix.data) {
+                if provider != config.provider.as_ref()
+                    || message.len() != APPROVAL_MESSAGE_LEN
+                    || &message[..32] != mint
+                    || &message[32..64] != owner
+                {
+                    continue;
+                }
+
+                let expiry = i64::from_le_bytes(message[64..].try_into().unwrap());
+                if now < expiry {
+                    return Ok(());
+                }
+                expired = true;
+            }
+        }
+
+        if expired {
+            Err(AttestationError::ApprovalExpired.into())
+        } else {
+            Err(AttestationError::ApprovalNotFound.into())
+        }
+    }
+}
+
+/// Yields the `(public key, message)` of every signature in an ed25519 program
+/// instruction whose offsets all point into that same instruction.
+fn self_contained_signatures(data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let count = data.first().copied().unwrap_or(0) as usize;
+    (0..count).filter_map(move |i| {
+        let start = ED25519_HEADER_LEN + i * ED25519_OFFSETS_LEN;
+        let offsets = data.get(start..start + ED25519_OFFSETS_LEN)?;
+        let field = |n: usize| u16::from_le_bytes([offsets[2 * n], offsets[2 * n + 1]]);
+
+        // signature, public key and message instruction indexes
+        if field(1) != u16::MAX || field(3) != u16::MAX || field(6) != u16::MAX {
+            return None;
+        }
+
+        let public_key_offset = field(2) as usize;
+        let message_offset = field(4) as usize;
+        let message_size = field(5) as usize;
+        let public_key = data.get(public_key_offset..public_key_offset + 32)?;
+        let message = data.get(message_offset..message_offset + message_size)?;
+        Some((public_key, message))
+    })
+}
@@ -0,0 +1,7 @@
+mod can_thaw_permissionless;
+mod create_config;
+mod set_provider;
+
+pub use can_thaw_permissionless::*;
+pub use create_config::*;
+pub use set_provider::*;
@@ -0,0 +1,84 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::check_token_acl_authority;
+
+use crate::{
+    error::AttestationError,
+    state::{load_attestation_config, AttestationConfig},
+};
+
+/// Rotates the provider key of a mint. Approvals signed by the previous key
+/// stop being accepted immediately.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("attestation-gate:set-provider")]
+pub struct SetProvider<'a> {
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub attestation_config: &'a AccountInfo<'a>,
+}
+
+impl SetProvider<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let provider =
+            Pubkey::try_from(remaining_data).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        if self.attestation_config.owner != &crate::ID {
+            return Err(AttestationError::InvalidAttestationConfig.into());
+        }
+        let mut data = self.attestation_config.try_borrow_mut_data()?;
+        if &load_attestation_config(&data)?.mint != self.mint.key {
+            return Err(AttestationError::InvalidAttestationConfig.into());
+        }
+
+        let config = bytemuck::from_bytes_mut::<AttestationConfig>(&mut data);
+        config.provider = provider;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for SetProvider<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [authority, mint, mint_config, attestation_config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            mint,
+            mint_config,
+            attestation_config,
+        })
+    }
+}
+
+pub fn set_provider(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    provider: &Pubkey,
+) -> Instruction {
+    let mut data = SetProvider::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(provider.as_ref());
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(AttestationConfig::find_pda(mint).0, false),
+        ],
+        data,
+    }
+}
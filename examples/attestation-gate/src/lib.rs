@@ -0,0 +1,81 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, pubkey::Pubkey, sysvar,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts11111111111111111111111111111111111118");
+
+/// Ed25519-attestation gate: token accounts can be thawed permissionlessly in
+/// a transaction that also verifies, through the ed25519 program, an approval
+/// signed off-chain by the mint's attestation provider.
+///
+/// Approvals leave no on-chain state, so permissionless freeze is not
+/// supported and freezing stays with the token-acl authority.
+pub struct AttestationGate;
+
+gate_program!(AttestationGate);
+
+impl GateProgram for AttestationGate {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawPermissionless::from_gate_accounts(accounts)?.process()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        if instruction == EfficientBlockAllowInstruction::CanFreezePermissionless {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![
+            // [6] attestation config
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::AttestationConfig::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+            // [7] instructions sysvar
+            ExtraAccountMeta::new_with_pubkey(&sysvar::instructions::ID, false, false)?,
+        ])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateConfig::SPL_DISCRIMINATOR_SLICE => {
+                CreateConfig::try_from(accounts)?.process(remaining_data)
+            }
+            SetProvider::SPL_DISCRIMINATOR_SLICE => {
+                SetProvider::try_from(accounts)?.process(remaining_data)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
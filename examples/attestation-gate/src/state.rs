@@ -0,0 +1,52 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+
+use crate::error::AttestationError;
+
+/// Per-mint gate configuration, holding the key the attestation provider signs
+/// approvals with.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct AttestationConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+}
+
+impl AttestationConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"attestation_config";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 32 + 32;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+/// Length of an approval message: `mint || owner || expiry`.
+pub const APPROVAL_MESSAGE_LEN: usize = 32 + 32 + 8;
+
+/// Message the provider signs to approve `owner` for `mint` until the unix
+/// timestamp `expiry` (exclusive), the expiry encoded little-endian.
+pub fn approval_message(mint: &Pubkey, owner: &Pubkey, expiry: i64) -> [u8; APPROVAL_MESSAGE_LEN] {
+    let mut message = [0u8; APPROVAL_MESSAGE_LEN];
+    message[..32].copy_from_slice(mint.as_ref());
+    message[32..64].copy_from_slice(owner.as_ref());
+    message[64..].copy_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+#[inline(always)]
+pub fn load_attestation_config(data: &[u8]) -> Result<&AttestationConfig, ProgramError> {
+    bytemuck::try_from_bytes::<AttestationConfig>(data)
+        .map_err(|_| AttestationError::InvalidAttestationConfig.into())
+        .and_then(|cfg: &AttestationConfig| {
+            if cfg.discriminator == AttestationConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(AttestationError::InvalidAttestationConfig.into())
+            }
+        })
+}
@@ -0,0 +1,15 @@
+use solana_program_error::ProgramError;
+
+/// Errors of the block decision. Codes below 3 are list management
+/// [`WalletListError`](token_acl_gate_framework::wallet_list::WalletListError)s.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BlockListError {
+    WalletBlocked = 3,
+    WalletNotBlocked,
+}
+
+impl From<BlockListError> for ProgramError {
+    fn from(e: BlockListError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,3 @@
+mod can_thaw_freeze_permissionless;
+
+pub use can_thaw_freeze_permissionless::*;
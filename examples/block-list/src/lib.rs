@@ -0,0 +1,93 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, instruction::Instruction,
+    pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_tlv_account_resolution::account::ExtraAccountMeta;
+use token_acl_gate_framework::{
+    gate_program,
+    wallet_list::{self, list_entry_extra_meta, process_wallet_list_instruction, WalletList},
+    GateProgram,
+};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod error;
+pub mod instructions;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts11111111111111111111111111111111111116");
+
+/// Block-list gate: token accounts can be frozen permissionlessly once their
+/// owner is blocked, and thawed permissionlessly only while it is not.
+///
+/// Meant for mints whose default account state is `Initialized`, so accounts
+/// are usable by default and any keeper can freeze newly sanctioned wallets.
+pub struct BlockList;
+
+gate_program!(BlockList);
+
+impl WalletList for BlockList {
+    const PROGRAM_ID: Pubkey = ID;
+    const LIST_SEED_PREFIX: &'static [u8] = b"block_list";
+    const ENTRY_SEED_PREFIX: &'static [u8] = b"block_list_entry";
+}
+
+impl GateProgram for BlockList {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_thaw()
+    }
+
+    fn can_freeze(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_freeze()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        _instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        // [6] list entry of the token account owner
+        Ok(vec![list_entry_extra_meta::<Self>()?])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        process_wallet_list_instruction::<Self>(accounts, instruction_data)
+    }
+}
+
+/// Creates the block list of `mint`, managed by `list_authority`.
+pub fn create_list(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    list_authority: &Pubkey,
+) -> Instruction {
+    wallet_list::create_list::<BlockList>(payer, authority, mint, mint_config, list_authority)
+}
+
+/// Blocks `wallet` on the block list of `mint`.
+pub fn block_wallet(
+    payer: &Pubkey,
+    list_authority: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+) -> Instruction {
+    wallet_list::add_wallet::<BlockList>(payer, list_authority, mint, wallet)
+}
+
+/// Unblocks `wallet` on the block list of `mint`.
+pub fn unblock_wallet(
+    list_authority: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    wallet_list::remove_wallet::<BlockList>(list_authority, mint, wallet, destination)
+}
@@ -0,0 +1,63 @@
+//! Bloom filter used by the list. Wallets are spread over up to 256 shards by
+//! the first byte of their address, and each shard is a fixed-size bloom
+//! filter over `sha256(mint || wallet)`, probed with double hashing.
+//!
+//! Bloom filters have false positives but no false negatives: a blocked
+//! wallet is always reported, and an unlisted wallet is reported with a
+//! probability set by the shard size and hash count. Removing wallets means
+//! rebuilding the affected shards with [`build_shards`].
+
+use std::collections::BTreeMap;
+
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+/// Shard holding `wallet`. Mirrors the extra meta seed reading the first
+/// byte of the token account owner.
+pub fn shard_index(wallet: &Pubkey) -> u8 {
+    wallet.as_ref()[0]
+}
+
+/// Bits of a shard of `shard_len` bytes probed for `wallet`.
+pub fn bit_indices(
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    num_hashes: u8,
+    shard_len: usize,
+) -> impl Iterator<Item = usize> {
+    let hash = hashv(&[mint.as_ref(), wallet.as_ref()]).to_bytes();
+    let h1 = u64::from_le_bytes(hash[..8].try_into().unwrap());
+    // odd, so consecutive probes never collapse on the same bit
+    let h2 = u64::from_le_bytes(hash[8..16].try_into().unwrap()) | 1;
+    let shard_bits = shard_len as u64 * 8;
+
+    (0..num_hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % shard_bits) as usize)
+}
+
+pub fn insert(bits: &mut [u8], mint: &Pubkey, wallet: &Pubkey, num_hashes: u8) {
+    for bit in bit_indices(mint, wallet, num_hashes, bits.len()) {
+        bits[bit / 8] |= 1 << (bit % 8);
+    }
+}
+
+pub fn contains(bits: &[u8], mint: &Pubkey, wallet: &Pubkey, num_hashes: u8) -> bool {
+    bit_indices(mint, wallet, num_hashes, bits.len())
+        .all(|bit| bits[bit / 8] & (1 << (bit % 8)) != 0)
+}
+
+/// Builds the bits of every shard holding at least one of `wallets`, to be
+/// written with `WriteShard` when rebuilding a list.
+pub fn build_shards(
+    mint: &Pubkey,
+    wallets: &[Pubkey],
+    shard_len: usize,
+    num_hashes: u8,
+) -> BTreeMap<u8, Vec<u8>> {
+    let mut shards = BTreeMap::new();
+    for wallet in wallets {
+        let bits = shards
+            .entry(shard_index(wallet))
+            .or_insert_with(|| vec![0u8; shard_len]);
+        insert(bits, mint, wallet, num_hashes);
+    }
+    shards
+}
@@ -0,0 +1,18 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BloomListError {
+    InvalidListConfig,
+    InvalidShard,
+    InvalidListAuthority,
+    InvalidShardParameters,
+    MissingShard,
+    WalletBlocked,
+    WalletNotBlocked,
+}
+
+impl From<BloomListError> for ProgramError {
+    fn from(e: BloomListError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,76 @@
+use solana_program::account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::gate::GateAccounts;
+
+use crate::{
+    bloom,
+    error::BloomListError,
+    state::{load_list_config, load_shard},
+};
+
+pub struct CanThawFreezePermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    /// Shard of the token account owner, resolved by token-acl from the extra metas.
+    pub shard: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawFreezePermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [list_config, shard, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            list_config,
+            shard,
+        })
+    }
+
+    /// Whether the token account owner hits the bloom filter of its shard.
+    ///
+    /// token-acl derives the shard address from the owner before calling the
+    /// gate, so a missing shard means the owner is not blocked.
+    fn is_blocked(&self) -> Result<bool, ProgramError> {
+        if self.list_config.owner != &crate::ID {
+            return Err(BloomListError::InvalidListConfig.into());
+        }
+        let data = self.list_config.try_borrow_data()?;
+        let config = load_list_config(&data)?;
+        if &config.mint != self.gate.mint.key {
+            return Err(BloomListError::InvalidListConfig.into());
+        }
+
+        if self.shard.owner != &crate::ID || self.shard.data_is_empty() {
+            return Ok(false);
+        }
+        let data = self.shard.try_borrow_data()?;
+        let (header, bits) = load_shard(&data)?;
+        let owner = self.gate.token_account_owner.key;
+        if &header.mint != self.gate.mint.key || header.index != bloom::shard_index(owner) {
+            return Err(BloomListError::InvalidShard.into());
+        }
+
+        Ok(bloom::contains(
+            bits,
+            &config.mint,
+            owner,
+            config.num_hashes,
+        ))
+    }
+
+    pub fn process_thaw(&self) -> ProgramResult {
+        if self.is_blocked()? {
+            return Err(BloomListError::WalletBlocked.into());
+        }
+        Ok(())
+    }
+
+    pub fn process_freeze(&self) -> ProgramResult {
+        if !self.is_blocked()? {
+            return Err(BloomListError::WalletNotBlocked.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::{check_token_acl_authority, create_pda_account};
+
+use crate::{
+    error::BloomListError,
+    state::{ListConfig, ShardHeader},
+};
+
+/// Creates the bloom block list of a mint with its shard geometry. Only the
+/// token-acl authority of the mint can create it, and it hands shard updates
+/// over to `list_authority`.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bloom-block-list-gate:create-list")]
+pub struct CreateList<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub list_config_bump: u8,
+}
+
+impl CreateList<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let [list_authority @ .., l0, l1, l2, l3, num_hashes] = remaining_data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let list_authority =
+            Pubkey::try_from(list_authority).map_err(|_| ProgramError::InvalidInstructionData)?;
+        let shard_len = u32::from_le_bytes([*l0, *l1, *l2, *l3]);
+        if shard_len == 0
+            || shard_len as usize > ShardHeader::MAX_SHARD_LEN
+            || *num_hashes == 0
+            || *num_hashes > ListConfig::MAX_NUM_HASHES
+        {
+            return Err(BloomListError::InvalidShardParameters.into());
+        }
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        let bump_seed = [self.list_config_bump];
+        create_pda_account(
+            self.payer,
+            self.list_config,
+            ListConfig::LEN,
+            &crate::ID,
+            &[ListConfig::SEED_PREFIX, self.mint.key.as_ref(), &bump_seed],
+        )?;
+
+        let mut data = self.list_config.try_borrow_mut_data()?;
+        let list_config = bytemuck::from_bytes_mut::<ListConfig>(&mut data);
+        list_config.discriminator = ListConfig::DISCRIMINATOR;
+        list_config.bump = self.list_config_bump;
+        list_config.num_hashes = *num_hashes;
+        list_config.shard_len = shard_len.into();
+        list_config.mint = *self.mint.key;
+        list_config.authority = list_authority;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateList<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, list_config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (list_config_address, list_config_bump) = ListConfig::find_pda(mint.key);
+        if list_config.key != &list_config_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            list_config,
+            system_program,
+            list_config_bump,
+        })
+    }
+}
+
+pub fn create_list(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    list_authority: &Pubkey,
+    shard_len: u32,
+    num_hashes: u8,
+) -> Instruction {
+    let mut data = CreateList::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(list_authority.as_ref());
+    data.extend_from_slice(&shard_len.to_le_bytes());
+    data.push(num_hashes);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(ListConfig::find_pda(mint).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
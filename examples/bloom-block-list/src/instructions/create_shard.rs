@@ -0,0 +1,118 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::create_pda_account;
+
+use crate::state::{ListConfig, ShardHeader};
+
+/// Creates an empty shard of the list. Wallets whose shard does not exist are
+/// never blocked.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bloom-block-list-gate:create-shard")]
+pub struct CreateShard<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub list_authority: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    pub shard: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+}
+
+impl CreateShard<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let [index] = remaining_data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        let config = check_list_authority(self.list_config, self.list_authority)?;
+
+        let (shard_address, bump) = ShardHeader::find_pda(&config.mint, *index);
+        if self.shard.key != &shard_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let bump_seed = [bump];
+        create_pda_account(
+            self.payer,
+            self.shard,
+            ShardHeader::LEN + u32::from(config.shard_len) as usize,
+            &crate::ID,
+            &[
+                ShardHeader::SEED_PREFIX,
+                config.mint.as_ref(),
+                &[*index],
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.shard.try_borrow_mut_data()?;
+        let header = bytemuck::from_bytes_mut::<ShardHeader>(&mut data[..ShardHeader::LEN]);
+        header.discriminator = ShardHeader::DISCRIMINATOR;
+        header.bump = bump;
+        header.index = *index;
+        header.mint = config.mint;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateShard<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, list_authority, list_config, shard, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            payer,
+            list_authority,
+            list_config,
+            shard,
+            system_program,
+        })
+    }
+}
+
+pub(crate) fn check_list_authority(
+    list_config: &AccountInfo,
+    list_authority: &AccountInfo,
+) -> Result<ListConfig, ProgramError> {
+    if list_config.owner != &crate::ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = list_config.try_borrow_data()?;
+    let config = crate::state::load_list_config(&data)?;
+
+    if !list_authority.is_signer || &config.authority != list_authority.key {
+        return Err(crate::error::BloomListError::InvalidListAuthority.into());
+    }
+
+    Ok(*config)
+}
+
+pub fn create_shard(
+    payer: &Pubkey,
+    list_authority: &Pubkey,
+    mint: &Pubkey,
+    index: u8,
+) -> Instruction {
+    let mut data = CreateShard::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.push(index);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*list_authority, true),
+            AccountMeta::new_readonly(ListConfig::find_pda(mint).0, false),
+            AccountMeta::new(ShardHeader::find_pda(mint, index).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
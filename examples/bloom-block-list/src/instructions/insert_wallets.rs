@@ -0,0 +1,103 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{
+    bloom,
+    error::BloomListError,
+    instructions::check_list_authority,
+    state::{load_shard_mut, ListConfig, ShardHeader},
+};
+
+/// Blocks a batch of wallets by setting their bits in the shards passed after
+/// the list config. Every wallet's shard must be among them.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bloom-block-list-gate:insert-wallets")]
+pub struct InsertWallets<'a> {
+    pub list_authority: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    pub shards: &'a [AccountInfo<'a>],
+}
+
+impl InsertWallets<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        // fails unless the data is a whole number of wallets
+        let wallets = bytemuck::try_cast_slice::<u8, [u8; 32]>(remaining_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        let config = check_list_authority(self.list_config, self.list_authority)?;
+
+        // position in `shards` of each shard index
+        let mut positions = [None; 256];
+        for (position, shard) in self.shards.iter().enumerate() {
+            if shard.owner != &crate::ID {
+                return Err(BloomListError::InvalidShard.into());
+            }
+            let data = shard.try_borrow_data()?;
+            let (header, _) = crate::state::load_shard(&data)?;
+            if header.mint != config.mint {
+                return Err(BloomListError::InvalidShard.into());
+            }
+            positions[header.index as usize] = Some(position);
+        }
+
+        for wallet in wallets {
+            let wallet = Pubkey::new_from_array(*wallet);
+            let position = positions[bloom::shard_index(&wallet) as usize]
+                .ok_or(BloomListError::MissingShard)?;
+
+            let mut data = self.shards[position].try_borrow_mut_data()?;
+            let (_, bits) = load_shard_mut(&mut data)?;
+            bloom::insert(bits, &config.mint, &wallet, config.num_hashes);
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for InsertWallets<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [list_authority, list_config, shards @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            list_authority,
+            list_config,
+            shards,
+        })
+    }
+}
+
+/// Builds an [`InsertWallets`] instruction passing the shard of every wallet.
+pub fn insert_wallets(list_authority: &Pubkey, mint: &Pubkey, wallets: &[Pubkey]) -> Instruction {
+    let mut data = InsertWallets::SPL_DISCRIMINATOR_SLICE.to_vec();
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*list_authority, true),
+        AccountMeta::new_readonly(ListConfig::find_pda(mint).0, false),
+    ];
+
+    let mut indexes: Vec<u8> = wallets.iter().map(bloom::shard_index).collect();
+    indexes.sort_unstable();
+    indexes.dedup();
+    accounts.extend(
+        indexes
+            .into_iter()
+            .map(|index| AccountMeta::new(ShardHeader::find_pda(mint, index).0, false)),
+    );
+    for wallet in wallets {
+        data.extend_from_slice(wallet.as_ref());
+    }
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,11 @@
+mod can_thaw_freeze_permissionless;
+mod create_list;
+mod create_shard;
+mod insert_wallets;
+mod write_shard;
+
+pub use can_thaw_freeze_permissionless::*;
+pub use create_list::*;
+pub use create_shard::*;
+pub use insert_wallets::*;
+pub use write_shard::*;
@@ -0,0 +1,87 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{
+    error::BloomListError,
+    instructions::check_list_authority,
+    state::{load_shard_mut, ListConfig, ShardHeader},
+};
+
+/// Overwrites a range of a shard's bits, used to rebuild shards off-chain,
+/// e.g. when wallets are removed from the list.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bloom-block-list-gate:write-shard")]
+pub struct WriteShard<'a> {
+    pub list_authority: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    pub shard: &'a AccountInfo<'a>,
+}
+
+impl WriteShard<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let [o0, o1, o2, o3, bytes @ ..] = remaining_data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let offset = u32::from_le_bytes([*o0, *o1, *o2, *o3]) as usize;
+
+        let config = check_list_authority(self.list_config, self.list_authority)?;
+
+        if self.shard.owner != &crate::ID {
+            return Err(BloomListError::InvalidShard.into());
+        }
+        let mut data = self.shard.try_borrow_mut_data()?;
+        let (header, bits) = load_shard_mut(&mut data)?;
+        if header.mint != config.mint {
+            return Err(BloomListError::InvalidShard.into());
+        }
+
+        bits.get_mut(offset..offset + bytes.len())
+            .ok_or(ProgramError::InvalidInstructionData)?
+            .copy_from_slice(bytes);
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for WriteShard<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [list_authority, list_config, shard] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            list_authority,
+            list_config,
+            shard,
+        })
+    }
+}
+
+pub fn write_shard(
+    list_authority: &Pubkey,
+    mint: &Pubkey,
+    index: u8,
+    offset: u32,
+    bytes: &[u8],
+) -> Instruction {
+    let mut data = WriteShard::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(&offset.to_le_bytes());
+    data.extend_from_slice(bytes);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*list_authority, true),
+            AccountMeta::new_readonly(ListConfig::find_pda(mint).0, false),
+            AccountMeta::new(ShardHeader::find_pda(mint, index).0, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,105 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod bloom;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts1111111111111111111111111111111111111A");
+
+/// Bloom block-list gate: blocked owners are hashed into fixed-size bloom
+/// filter shards instead of one account each, so large sanctions lists fit in
+/// at most 256 accounts and are updated in batches.
+///
+/// Like `block-list`, it is meant for mints whose default account state is
+/// `Initialized`: blocked owners can be frozen permissionlessly and cannot
+/// thaw. A false positive blocks an unlisted owner, so shards should be sized
+/// for the expected list.
+pub struct BloomBlockList;
+
+gate_program!(BloomBlockList);
+
+impl GateProgram for BloomBlockList {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_thaw()
+    }
+
+    fn can_freeze(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_freeze()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        _instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        Ok(vec![
+            // [6] list config
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::ListConfig::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+            // [7] shard of the token account owner
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::ShardHeader::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                    // first byte of the owner, read from the token account
+                    Seed::AccountData {
+                        account_index: 1,
+                        data_index: 32,
+                        length: 1,
+                    },
+                ],
+                false,
+                false,
+            )?,
+        ])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateList::SPL_DISCRIMINATOR_SLICE => {
+                CreateList::try_from(accounts)?.process(remaining_data)
+            }
+            CreateShard::SPL_DISCRIMINATOR_SLICE => {
+                CreateShard::try_from(accounts)?.process(remaining_data)
+            }
+            InsertWallets::SPL_DISCRIMINATOR_SLICE => {
+                InsertWallets::try_from(accounts)?.process(remaining_data)
+            }
+            WriteShard::SPL_DISCRIMINATOR_SLICE => {
+                WriteShard::try_from(accounts)?.process(remaining_data)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
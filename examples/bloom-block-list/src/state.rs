@@ -0,0 +1,92 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+use spl_pod::primitives::PodU32;
+
+use crate::error::BloomListError;
+
+/// Per-mint bloom block list, holding the shard geometry and the authority
+/// that updates the shards.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ListConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub num_hashes: u8,
+    pub shard_len: PodU32,
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+}
+
+impl ListConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"bloom_list";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 1 + 4 + 32 + 32;
+    pub const MAX_NUM_HASHES: u8 = 32;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+/// Header of a shard, followed by `shard_len` bytes of bloom filter bits.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ShardHeader {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub index: u8,
+    pub mint: Pubkey,
+}
+
+impl ShardHeader {
+    pub const SEED_PREFIX: &'static [u8] = b"bloom_shard";
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const LEN: usize = 1 + 1 + 1 + 32;
+    /// Shards are created through a system program CPI, capped at 10KiB.
+    pub const MAX_SHARD_LEN: usize = 10_240 - Self::LEN;
+
+    pub fn find_pda(mint: &Pubkey, index: u8) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref(), &[index]], &crate::ID)
+    }
+}
+
+#[inline(always)]
+pub fn load_list_config(data: &[u8]) -> Result<&ListConfig, ProgramError> {
+    bytemuck::try_from_bytes::<ListConfig>(data)
+        .map_err(|_| BloomListError::InvalidListConfig.into())
+        .and_then(|cfg: &ListConfig| {
+            if cfg.discriminator == ListConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(BloomListError::InvalidListConfig.into())
+            }
+        })
+}
+
+/// Splits a shard into its header and bloom filter bits.
+#[inline(always)]
+pub fn load_shard(data: &[u8]) -> Result<(&ShardHeader, &[u8]), ProgramError> {
+    let (header, bits) = data
+        .split_at_checked(ShardHeader::LEN)
+        .ok_or(BloomListError::InvalidShard)?;
+    let header = bytemuck::try_from_bytes::<ShardHeader>(header)
+        .map_err(|_| BloomListError::InvalidShard)?;
+    if header.discriminator != ShardHeader::DISCRIMINATOR {
+        return Err(BloomListError::InvalidShard.into());
+    }
+    Ok((header, bits))
+}
+
+#[inline(always)]
+pub fn load_shard_mut(data: &mut [u8]) -> Result<(&ShardHeader, &mut [u8]), ProgramError> {
+    let (header, bits) = data
+        .split_at_mut_checked(ShardHeader::LEN)
+        .ok_or(BloomListError::InvalidShard)?;
+    let header = bytemuck::try_from_bytes::<ShardHeader>(header)
+        .map_err(|_| BloomListError::InvalidShard)?;
+    if header.discriminator != ShardHeader::DISCRIMINATOR {
+        return Err(BloomListError::InvalidShard.into());
+    }
+    Ok((header, bits))
+}
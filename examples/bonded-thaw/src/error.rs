@@ -0,0 +1,23 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BondError {
+    InvalidBondConfig,
+    InvalidBondParameters,
+    InvalidBond,
+    InvalidPolicyAuthority,
+    BondNotFound,
+    InsufficientBond,
+    BondWithdrawing,
+    BondInViolation,
+    BondInGoodStanding,
+    WithdrawalNotRequested,
+    CooldownNotElapsed,
+    NoReward,
+}
+
+impl From<BondError> for ProgramError {
+    fn from(e: BondError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
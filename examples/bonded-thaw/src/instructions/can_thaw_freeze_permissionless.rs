@@ -0,0 +1,104 @@
+use solana_program::account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::gate::GateAccounts;
+
+use crate::{
+    error::BondError,
+    state::{load_bond, load_bond_config, load_bond_mut, BondConfig, MAX_SLASH_BPS},
+};
+
+pub struct CanThawFreezePermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub bond_config: &'a AccountInfo<'a>,
+    /// Bond of the token account owner, resolved by token-acl from the extra
+    /// metas as writable so a freeze can slash it.
+    pub bond: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawFreezePermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [bond_config, bond, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            bond_config,
+            bond,
+        })
+    }
+
+    fn config(&self) -> Result<BondConfig, ProgramError> {
+        if self.bond_config.owner != &crate::ID {
+            return Err(BondError::InvalidBondConfig.into());
+        }
+        let data = self.bond_config.try_borrow_data()?;
+        let config = load_bond_config(&data)?;
+        if &config.mint != self.gate.mint.key {
+            return Err(BondError::InvalidBondConfig.into());
+        }
+        Ok(*config)
+    }
+
+    /// Succeeds when the owner has at least the minimum bonded, is not
+    /// withdrawing and has not been flagged.
+    pub fn process_thaw(&self) -> ProgramResult {
+        let config = self.config()?;
+
+        if self.bond.owner != &crate::ID {
+            return Err(BondError::BondNotFound.into());
+        }
+        let data = self.bond.try_borrow_data()?;
+        let bond = load_bond(&data)?;
+        if bond.mint != config.mint || &bond.owner != self.gate.token_account_owner.key {
+            return Err(BondError::InvalidBond.into());
+        }
+        if bond.violation != 0 {
+            return Err(BondError::BondInViolation.into());
+        }
+        if bond.is_withdrawing() {
+            return Err(BondError::BondWithdrawing.into());
+        }
+        if u64::from(bond.amount) < u64::from(config.min_bond) {
+            return Err(BondError::InsufficientBond.into());
+        }
+
+        Ok(())
+    }
+
+    /// Succeeds when the owner could not thaw. The first freeze after a
+    /// violation is flagged slashes the bond in favor of the caller.
+    ///
+    /// Gate accounts are only loaded once token-acl's flag account is checked,
+    /// so the bond can only be slashed by an actual `FreezePermissionless`.
+    pub fn process_freeze(&self) -> ProgramResult {
+        let config = self.config()?;
+
+        if self.bond.owner != &crate::ID {
+            return Ok(());
+        }
+        let mut data = self.bond.try_borrow_mut_data()?;
+        let bond = load_bond_mut(&mut data)?;
+        if bond.mint != config.mint || &bond.owner != self.gate.token_account_owner.key {
+            return Err(BondError::InvalidBond.into());
+        }
+
+        if bond.violation != 0 {
+            if bond.slashed == 0 {
+                let amount = u64::from(bond.amount);
+                let reward = (amount as u128 * u16::from(config.slash_bps) as u128
+                    / MAX_SLASH_BPS as u128) as u64;
+                bond.amount = (amount - reward).into();
+                bond.reward = reward.into();
+                bond.reporter = *self.gate.caller.key;
+                bond.slashed = 1;
+            }
+            return Ok(());
+        }
+        if bond.is_withdrawing() || u64::from(bond.amount) < u64::from(config.min_bond) {
+            return Ok(());
+        }
+
+        Err(BondError::BondInGoodStanding.into())
+    }
+}
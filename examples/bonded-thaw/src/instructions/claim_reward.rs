@@ -0,0 +1,86 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::close_account;
+
+use crate::{
+    error::BondError,
+    state::{load_bond_mut, Bond},
+};
+
+/// Pays the reporter the share of a bond slashed by its freeze.
+///
+/// token-acl only passes the caller to the gate as read-only, so the slash
+/// earmarks the reward in the bond and the reporter claims it here. The escrow
+/// is closed to the owner once both sides have been paid out.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bonded-thaw-gate:claim-reward")]
+pub struct ClaimReward<'a> {
+    pub reporter: &'a AccountInfo<'a>,
+    pub owner: &'a AccountInfo<'a>,
+    pub bond: &'a AccountInfo<'a>,
+}
+
+impl ClaimReward<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        if self.bond.owner != &crate::ID {
+            return Err(BondError::BondNotFound.into());
+        }
+        let (reward, amount) = {
+            let mut data = self.bond.try_borrow_mut_data()?;
+            let bond = load_bond_mut(&mut data)?;
+            if &bond.owner != self.owner.key {
+                return Err(BondError::InvalidBond.into());
+            }
+            if !self.reporter.is_signer || &bond.reporter != self.reporter.key {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            let reward = u64::from(bond.reward);
+            if reward == 0 {
+                return Err(BondError::NoReward.into());
+            }
+            bond.reward = 0.into();
+            (reward, u64::from(bond.amount))
+        };
+
+        **self.bond.try_borrow_mut_lamports()? -= reward;
+        **self.reporter.try_borrow_mut_lamports()? += reward;
+
+        if amount == 0 {
+            return close_account(self.bond, self.owner);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for ClaimReward<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [reporter, owner, bond] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            reporter,
+            owner,
+            bond,
+        })
+    }
+}
+
+pub fn claim_reward(reporter: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*reporter, true),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new(Bond::find_pda(mint, owner).0, false),
+        ],
+        data: ClaimReward::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
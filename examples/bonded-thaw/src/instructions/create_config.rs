@@ -0,0 +1,121 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::{check_token_acl_authority, create_pda_account};
+
+use crate::{
+    error::BondError,
+    state::{BondConfig, MAX_SLASH_BPS},
+};
+
+/// Sets the bonding terms of a mint. Only the token-acl authority of the mint
+/// can create the config.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bonded-thaw-gate:create-config")]
+pub struct CreateConfig<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub bond_config: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub bond_config_bump: u8,
+}
+
+impl CreateConfig<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        if remaining_data.len() != 32 + 8 + 2 + 8 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let policy_authority = Pubkey::try_from(&remaining_data[..32]).unwrap();
+        let min_bond = u64::from_le_bytes(remaining_data[32..40].try_into().unwrap());
+        let slash_bps = u16::from_le_bytes(remaining_data[40..42].try_into().unwrap());
+        let cooldown_slots = u64::from_le_bytes(remaining_data[42..50].try_into().unwrap());
+        if min_bond == 0 || slash_bps > MAX_SLASH_BPS {
+            return Err(BondError::InvalidBondParameters.into());
+        }
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        let bump_seed = [self.bond_config_bump];
+        create_pda_account(
+            self.payer,
+            self.bond_config,
+            BondConfig::LEN,
+            &crate::ID,
+            &[BondConfig::SEED_PREFIX, self.mint.key.as_ref(), &bump_seed],
+        )?;
+
+        let mut data = self.bond_config.try_borrow_mut_data()?;
+        let config = bytemuck::from_bytes_mut::<BondConfig>(&mut data);
+        config.discriminator = BondConfig::DISCRIMINATOR;
+        config.bump = self.bond_config_bump;
+        config.slash_bps = slash_bps.into();
+        config.mint = *self.mint.key;
+        config.policy_authority = policy_authority;
+        config.min_bond = min_bond.into();
+        config.cooldown_slots = cooldown_slots.into();
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, bond_config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (bond_config_address, bond_config_bump) = BondConfig::find_pda(mint.key);
+        if bond_config.key != &bond_config_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            bond_config,
+            system_program,
+            bond_config_bump,
+        })
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_config(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    policy_authority: &Pubkey,
+    min_bond: u64,
+    slash_bps: u16,
+    cooldown_slots: u64,
+) -> Instruction {
+    let mut data = CreateConfig::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(policy_authority.as_ref());
+    data.extend_from_slice(&min_bond.to_le_bytes());
+    data.extend_from_slice(&slash_bps.to_le_bytes());
+    data.extend_from_slice(&cooldown_slots.to_le_bytes());
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(BondConfig::find_pda(mint).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,130 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::create_pda_account;
+
+use crate::{
+    error::BondError,
+    state::{load_bond, load_bond_mut, Bond},
+};
+
+/// Adds lamports to the owner's bond, creating the escrow on the first
+/// deposit. Depositing cancels a pending withdrawal.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bonded-thaw-gate:deposit")]
+pub struct Deposit<'a> {
+    pub owner: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub bond: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub bond_bump: u8,
+}
+
+impl Deposit<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let amount = remaining_data
+            .try_into()
+            .map(u64::from_le_bytes)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        if !self.owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if self.bond.owner != &crate::ID {
+            let bump_seed = [self.bond_bump];
+            create_pda_account(
+                self.owner,
+                self.bond,
+                Bond::LEN,
+                &crate::ID,
+                &[
+                    Bond::SEED_PREFIX,
+                    self.mint.key.as_ref(),
+                    self.owner.key.as_ref(),
+                    &bump_seed,
+                ],
+            )?;
+
+            let mut data = self.bond.try_borrow_mut_data()?;
+            let bond = bytemuck::from_bytes_mut::<Bond>(&mut data);
+            bond.discriminator = Bond::DISCRIMINATOR;
+            bond.bump = self.bond_bump;
+            bond.mint = *self.mint.key;
+            bond.owner = *self.owner.key;
+        }
+
+        let ix =
+            solana_system_interface::instruction::transfer(self.owner.key, self.bond.key, amount);
+        invoke(&ix, &[self.owner.clone(), self.bond.clone()])?;
+
+        let mut data = self.bond.try_borrow_mut_data()?;
+        let bond = load_bond_mut(&mut data)?;
+        bond.amount = u64::from(bond.amount)
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .into();
+        bond.unlock_slot = 0.into();
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for Deposit<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [owner, mint, bond, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (bond_address, bond_bump) = Bond::find_pda(mint.key, owner.key);
+        if bond.key != &bond_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            owner,
+            mint,
+            bond,
+            system_program,
+            bond_bump,
+        })
+    }
+}
+
+/// Checks that `bond` is a bond of this program held by `owner`, who signed.
+pub(crate) fn check_bond_owner(bond: &AccountInfo, owner: &AccountInfo) -> ProgramResult {
+    if bond.owner != &crate::ID {
+        return Err(BondError::BondNotFound.into());
+    }
+    if &load_bond(&bond.try_borrow_data()?)?.owner != owner.key {
+        return Err(BondError::InvalidBond.into());
+    }
+    if !owner.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    Ok(())
+}
+
+pub fn deposit(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    let mut data = Deposit::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(Bond::find_pda(mint, owner).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
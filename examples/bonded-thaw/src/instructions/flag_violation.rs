@@ -0,0 +1,77 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{
+    error::BondError,
+    state::{load_bond_config, load_bond_mut, Bond, BondConfig},
+};
+
+/// Marks an owner as violating the mint's policy. Its token account can then
+/// be frozen permissionlessly, and the first reporter to do so is paid from
+/// the bond.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bonded-thaw-gate:flag-violation")]
+pub struct FlagViolation<'a> {
+    pub policy_authority: &'a AccountInfo<'a>,
+    pub bond_config: &'a AccountInfo<'a>,
+    pub bond: &'a AccountInfo<'a>,
+}
+
+impl FlagViolation<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        if self.bond_config.owner != &crate::ID {
+            return Err(BondError::InvalidBondConfig.into());
+        }
+        let config_data = self.bond_config.try_borrow_data()?;
+        let config = load_bond_config(&config_data)?;
+        if !self.policy_authority.is_signer || &config.policy_authority != self.policy_authority.key
+        {
+            return Err(BondError::InvalidPolicyAuthority.into());
+        }
+
+        if self.bond.owner != &crate::ID {
+            return Err(BondError::BondNotFound.into());
+        }
+        let mut data = self.bond.try_borrow_mut_data()?;
+        let bond = load_bond_mut(&mut data)?;
+        if bond.mint != config.mint {
+            return Err(BondError::InvalidBond.into());
+        }
+        bond.violation = 1;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for FlagViolation<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [policy_authority, bond_config, bond] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            policy_authority,
+            bond_config,
+            bond,
+        })
+    }
+}
+
+pub fn flag_violation(policy_authority: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*policy_authority, true),
+            AccountMeta::new_readonly(BondConfig::find_pda(mint).0, false),
+            AccountMeta::new(Bond::find_pda(mint, owner).0, false),
+        ],
+        data: FlagViolation::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
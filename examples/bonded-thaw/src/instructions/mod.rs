@@ -0,0 +1,15 @@
+mod can_thaw_freeze_permissionless;
+mod claim_reward;
+mod create_config;
+mod deposit;
+mod flag_violation;
+mod request_withdraw;
+mod withdraw;
+
+pub use can_thaw_freeze_permissionless::*;
+pub use claim_reward::*;
+pub use create_config::*;
+pub use deposit::*;
+pub use flag_violation::*;
+pub use request_withdraw::*;
+pub use withdraw::*;
@@ -0,0 +1,82 @@
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{
+    error::BondError,
+    instructions::check_bond_owner,
+    state::{load_bond_config, load_bond_mut, Bond, BondConfig},
+};
+
+/// Starts the cooldown after which the owner can withdraw its bond. The token
+/// account can be frozen permissionlessly from now on.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bonded-thaw-gate:request-withdraw")]
+pub struct RequestWithdraw<'a> {
+    pub owner: &'a AccountInfo<'a>,
+    pub bond_config: &'a AccountInfo<'a>,
+    pub bond: &'a AccountInfo<'a>,
+}
+
+impl RequestWithdraw<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        check_bond_owner(self.bond, self.owner)?;
+
+        if self.bond_config.owner != &crate::ID {
+            return Err(BondError::InvalidBondConfig.into());
+        }
+        let config_data = self.bond_config.try_borrow_data()?;
+        let config = load_bond_config(&config_data)?;
+
+        let mut data = self.bond.try_borrow_mut_data()?;
+        let bond = load_bond_mut(&mut data)?;
+        if bond.mint != config.mint {
+            return Err(BondError::InvalidBondConfig.into());
+        }
+        if bond.is_withdrawing() {
+            return Err(BondError::BondWithdrawing.into());
+        }
+
+        let unlock_slot = Clock::get()?
+            .slot
+            .saturating_add(config.cooldown_slots.into())
+            .max(1);
+        bond.unlock_slot = unlock_slot.into();
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for RequestWithdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [owner, bond_config, bond] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            owner,
+            bond_config,
+            bond,
+        })
+    }
+}
+
+pub fn request_withdraw(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(BondConfig::find_pda(mint).0, false),
+            AccountMeta::new(Bond::find_pda(mint, owner).0, false),
+        ],
+        data: RequestWithdraw::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
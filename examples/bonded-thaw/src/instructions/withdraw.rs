@@ -0,0 +1,81 @@
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::close_account;
+
+use crate::{
+    error::BondError,
+    instructions::check_bond_owner,
+    state::{load_bond_mut, Bond},
+};
+
+/// Returns the bonded lamports to the owner once the cooldown has elapsed,
+/// closing the escrow unless a reporter still has a reward to claim.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("bonded-thaw-gate:withdraw")]
+pub struct Withdraw<'a> {
+    pub owner: &'a AccountInfo<'a>,
+    pub bond: &'a AccountInfo<'a>,
+}
+
+impl Withdraw<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        check_bond_owner(self.bond, self.owner)?;
+
+        let (amount, reward) = {
+            let mut data = self.bond.try_borrow_mut_data()?;
+            let bond = load_bond_mut(&mut data)?;
+            if !bond.is_withdrawing() {
+                return Err(BondError::WithdrawalNotRequested.into());
+            }
+            if Clock::get()?.slot < u64::from(bond.unlock_slot) {
+                return Err(BondError::CooldownNotElapsed.into());
+            }
+            // reporters must get the chance to slash a flagged bond first
+            if bond.violation != 0 && bond.slashed == 0 {
+                return Err(BondError::BondInViolation.into());
+            }
+
+            let amount = u64::from(bond.amount);
+            bond.amount = 0.into();
+            (amount, u64::from(bond.reward))
+        };
+
+        if reward == 0 {
+            return close_account(self.bond, self.owner);
+        }
+
+        **self.bond.try_borrow_mut_lamports()? -= amount;
+        **self.owner.try_borrow_mut_lamports()? += amount;
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for Withdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [owner, bond] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self { owner, bond })
+    }
+}
+
+pub fn withdraw(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(Bond::find_pda(mint, owner).0, false),
+        ],
+        data: Withdraw::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
@@ -0,0 +1,105 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts1111111111111111111111111111111111111F");
+
+/// Bonded thaw gate: owners post a bond in a gate-owned escrow to become
+/// thawable, and keep it until a withdrawal cooldown has elapsed.
+///
+/// Token accounts whose owner is under-bonded or withdrawing can be frozen
+/// permissionlessly. Once the mint's policy authority flags a violation, the
+/// first permissionless freeze also slashes part of the bond to its caller.
+pub struct BondedThaw;
+
+gate_program!(BondedThaw);
+
+impl GateProgram for BondedThaw {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_thaw()
+    }
+
+    fn can_freeze(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_freeze()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        let is_freeze = instruction == EfficientBlockAllowInstruction::CanFreezePermissionless;
+
+        Ok(vec![
+            // [6] bond config
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::BondConfig::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+            // [7] bond of the token account owner, slashed on freeze
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::Bond::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                    Seed::AccountKey { index: 3 }, // owner
+                ],
+                false,
+                is_freeze,
+            )?,
+        ])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateConfig::SPL_DISCRIMINATOR_SLICE => {
+                CreateConfig::try_from(accounts)?.process(remaining_data)
+            }
+            Deposit::SPL_DISCRIMINATOR_SLICE => {
+                Deposit::try_from(accounts)?.process(remaining_data)
+            }
+            RequestWithdraw::SPL_DISCRIMINATOR_SLICE => {
+                RequestWithdraw::try_from(accounts)?.process(remaining_data)
+            }
+            Withdraw::SPL_DISCRIMINATOR_SLICE => {
+                Withdraw::try_from(accounts)?.process(remaining_data)
+            }
+            FlagViolation::SPL_DISCRIMINATOR_SLICE => {
+                FlagViolation::try_from(accounts)?.process(remaining_data)
+            }
+            ClaimReward::SPL_DISCRIMINATOR_SLICE => {
+                ClaimReward::try_from(accounts)?.process(remaining_data)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
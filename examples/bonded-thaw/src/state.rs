@@ -0,0 +1,111 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+use spl_pod::primitives::{PodU16, PodU64};
+
+use crate::error::BondError;
+
+/// Basis points in a whole bond.
+pub const MAX_SLASH_BPS: u16 = 10_000;
+
+/// Per-mint gate configuration.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct BondConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    /// Share of a bond paid to the reporter of a violation, in basis points.
+    pub slash_bps: PodU16,
+    pub mint: Pubkey,
+    /// Key allowed to flag owners as violating the mint's policy.
+    pub policy_authority: Pubkey,
+    /// Lamports an owner must have bonded to thaw.
+    pub min_bond: PodU64,
+    /// Slots between a withdrawal request and the withdrawal.
+    pub cooldown_slots: PodU64,
+}
+
+impl BondConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"bond_config";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 2 + 32 + 32 + 8 + 8;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+/// Escrow of an owner for a mint. Its lamports are the rent, the bonded
+/// `amount` and the `reward` owed to the reporter of a violation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct Bond {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub violation: u8,
+    pub slashed: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub reporter: Pubkey,
+    pub amount: PodU64,
+    pub reward: PodU64,
+    /// Slot from which the bond can be withdrawn, `0` when no withdrawal was
+    /// requested.
+    pub unlock_slot: PodU64,
+}
+
+impl Bond {
+    pub const SEED_PREFIX: &'static [u8] = b"bond";
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const LEN: usize = 1 + 1 + 1 + 1 + 32 + 32 + 32 + 8 + 8 + 8;
+
+    pub fn find_pda(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED_PREFIX, mint.as_ref(), owner.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    pub fn is_withdrawing(&self) -> bool {
+        u64::from(self.unlock_slot) != 0
+    }
+}
+
+#[inline(always)]
+pub fn load_bond_config(data: &[u8]) -> Result<&BondConfig, ProgramError> {
+    bytemuck::try_from_bytes::<BondConfig>(data)
+        .map_err(|_| BondError::InvalidBondConfig.into())
+        .and_then(|cfg: &BondConfig| {
+            if cfg.discriminator == BondConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(BondError::InvalidBondConfig.into())
+            }
+        })
+}
+
+#[inline(always)]
+pub fn load_bond(data: &[u8]) -> Result<&Bond, ProgramError> {
+    bytemuck::try_from_bytes::<Bond>(data)
+        .map_err(|_| BondError::InvalidBond.into())
+        .and_then(|bond: &Bond| {
+            if bond.discriminator == Bond::DISCRIMINATOR {
+                Ok(bond)
+            } else {
+                Err(BondError::InvalidBond.into())
+            }
+        })
+}
+
+#[inline(always)]
+pub fn load_bond_mut(data: &mut [u8]) -> Result<&mut Bond, ProgramError> {
+    bytemuck::try_from_bytes_mut::<Bond>(data)
+        .map_err(|_| BondError::InvalidBond.into())
+        .and_then(|bond: &mut Bond| {
+            if bond.discriminator == Bond::DISCRIMINATOR {
+                Ok(bond)
+            } else {
+                Err(BondError::InvalidBond.into())
+            }
+        })
+}
@@ -0,0 +1,16 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CredentialGateError {
+    InvalidCredentialConfig,
+    InvalidCredentialMint,
+    InvalidCredentialAccount,
+    CredentialNotHeld,
+    CredentialHeld,
+}
+
+impl From<CredentialGateError> for ProgramError {
+    fn from(e: CredentialGateError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,87 @@
+use solana_program::{account_info::AccountInfo, program_pack::Pack};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_token_2022_interface::{
+    extension::StateWithExtensions,
+    state::{Account, AccountState},
+};
+use token_acl_interface::gate::GateAccounts;
+
+use crate::{error::CredentialGateError, state::load_credential_config};
+
+pub struct CanThawFreezePermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub credential_config: &'a AccountInfo<'a>,
+    pub credential_mint: &'a AccountInfo<'a>,
+    pub credential_token_program: &'a AccountInfo<'a>,
+    /// Credential ATA of the token account owner, resolved by token-acl from the extra metas.
+    pub credential_account: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawFreezePermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [credential_config, credential_mint, credential_token_program, _associated_token_program, credential_account, ..] =
+            gate.extra_accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            credential_config,
+            credential_mint,
+            credential_token_program,
+            credential_account,
+        })
+    }
+
+    /// Whether the token account owner holds a usable credential: a non-zero,
+    /// non-frozen balance of the configured credential mint.
+    ///
+    /// A missing or closed ATA means the credential was never issued or was
+    /// burned and closed.
+    fn holds_credential(&self) -> Result<bool, ProgramError> {
+        if self.credential_config.owner != &crate::ID {
+            return Err(CredentialGateError::InvalidCredentialConfig.into());
+        }
+        let data = self.credential_config.try_borrow_data()?;
+        let config = load_credential_config(&data)?;
+        if &config.mint != self.gate.mint.key
+            || &config.credential_mint != self.credential_mint.key
+            || &config.credential_token_program != self.credential_token_program.key
+        {
+            return Err(CredentialGateError::InvalidCredentialConfig.into());
+        }
+
+        if self.credential_account.owner != self.credential_token_program.key
+            || self.credential_account.data_len() < Account::LEN
+        {
+            return Ok(false);
+        }
+
+        let data = self.credential_account.try_borrow_data()?;
+        let credential = StateWithExtensions::<Account>::unpack(&data)
+            .map_err(|_| CredentialGateError::InvalidCredentialAccount)?
+            .base;
+        if &credential.mint != self.credential_mint.key
+            || &credential.owner != self.gate.token_account_owner.key
+        {
+            return Err(CredentialGateError::InvalidCredentialAccount.into());
+        }
+
+        Ok(credential.amount > 0 && credential.state == AccountState::Initialized)
+    }
+
+    pub fn process_thaw(&self) -> ProgramResult {
+        if !self.holds_credential()? {
+            return Err(CredentialGateError::CredentialNotHeld.into());
+        }
+        Ok(())
+    }
+
+    pub fn process_freeze(&self) -> ProgramResult {
+        if self.holds_credential()? {
+            return Err(CredentialGateError::CredentialHeld.into());
+        }
+        Ok(())
+    }
+}
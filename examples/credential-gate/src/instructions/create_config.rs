@@ -0,0 +1,116 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::{check_token_acl_authority, create_pda_account};
+
+use crate::{error::CredentialGateError, state::CredentialConfig};
+
+/// Sets the credential mint of a mint. Only the token-acl authority of the
+/// mint can create the config; the credential token program is taken from the
+/// owner of the credential mint.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("credential-gate:create-config")]
+pub struct CreateConfig<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub credential_config: &'a AccountInfo<'a>,
+    pub credential_mint: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub credential_config_bump: u8,
+}
+
+impl CreateConfig<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        let credential_token_program = *self.credential_mint.owner;
+        if credential_token_program != spl_token_interface::ID
+            && credential_token_program != spl_token_2022_interface::ID
+        {
+            return Err(CredentialGateError::InvalidCredentialMint.into());
+        }
+        if self.credential_mint.key == self.mint.key {
+            return Err(CredentialGateError::InvalidCredentialMint.into());
+        }
+
+        let bump_seed = [self.credential_config_bump];
+        create_pda_account(
+            self.payer,
+            self.credential_config,
+            CredentialConfig::LEN,
+            &crate::ID,
+            &[
+                CredentialConfig::SEED_PREFIX,
+                self.mint.key.as_ref(),
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.credential_config.try_borrow_mut_data()?;
+        let config = bytemuck::from_bytes_mut::<CredentialConfig>(&mut data);
+        config.discriminator = CredentialConfig::DISCRIMINATOR;
+        config.bump = self.credential_config_bump;
+        config.mint = *self.mint.key;
+        config.credential_mint = *self.credential_mint.key;
+        config.credential_token_program = credential_token_program;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, credential_config, credential_mint, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (credential_config_address, credential_config_bump) =
+            CredentialConfig::find_pda(mint.key);
+        if credential_config.key != &credential_config_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            credential_config,
+            credential_mint,
+            system_program,
+            credential_config_bump,
+        })
+    }
+}
+
+pub fn create_config(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    credential_mint: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(CredentialConfig::find_pda(mint).0, false),
+            AccountMeta::new_readonly(*credential_mint, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data: CreateConfig::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
@@ -0,0 +1,5 @@
+mod can_thaw_freeze_permissionless;
+mod create_config;
+
+pub use can_thaw_freeze_permissionless::*;
+pub use create_config::*;
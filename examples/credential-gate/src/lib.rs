@@ -0,0 +1,109 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, pubkey_data::PubkeyData, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts11111111111111111111111111111111111117");
+
+/// Credential-token gate: token accounts can be thawed permissionlessly only
+/// when their owner holds a credential token, typically a non-transferable
+/// token issued after KYC, and frozen permissionlessly once that credential
+/// has been burned or frozen by its issuer.
+pub struct CredentialGate;
+
+gate_program!(CredentialGate);
+
+impl GateProgram for CredentialGate {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_thaw()
+    }
+
+    fn can_freeze(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_freeze()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        _instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        Ok(vec![
+            // [6] credential config
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::CredentialConfig::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+            // [7] credential mint
+            ExtraAccountMeta::new_with_pubkey_data(
+                &PubkeyData::AccountData {
+                    account_index: 6,
+                    data_index: state::CredentialConfig::CREDENTIAL_MINT_OFFSET,
+                },
+                false,
+                false,
+            )?,
+            // [8] credential token program
+            ExtraAccountMeta::new_with_pubkey_data(
+                &PubkeyData::AccountData {
+                    account_index: 6,
+                    data_index: state::CredentialConfig::CREDENTIAL_TOKEN_PROGRAM_OFFSET,
+                },
+                false,
+                false,
+            )?,
+            // [9] associated token program
+            ExtraAccountMeta::new_with_pubkey(
+                &spl_associated_token_account_interface::program::ID,
+                false,
+                false,
+            )?,
+            // [10] credential ata of the token account owner
+            ExtraAccountMeta::new_external_pda_with_seeds(
+                9,
+                &[
+                    Seed::AccountKey { index: 3 }, // owner
+                    Seed::AccountKey { index: 8 }, // credential token program
+                    Seed::AccountKey { index: 7 }, // credential mint
+                ],
+                false,
+                false,
+            )?,
+        ])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateConfig::SPL_DISCRIMINATOR_SLICE => {
+                CreateConfig::try_from(accounts)?.process(remaining_data)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
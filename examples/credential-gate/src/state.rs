@@ -0,0 +1,45 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+
+use crate::error::CredentialGateError;
+
+/// Per-mint gate configuration, naming the credential mint holders must own.
+///
+/// The extra metas read `credential_mint` and `credential_token_program`
+/// straight from this account to derive the owner's credential ATA, so their
+/// offsets are part of the resolution and must not move.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct CredentialConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub credential_mint: Pubkey,
+    pub credential_token_program: Pubkey,
+}
+
+impl CredentialConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"credential_config";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 32;
+    pub const CREDENTIAL_MINT_OFFSET: u8 = 1 + 1 + 32;
+    pub const CREDENTIAL_TOKEN_PROGRAM_OFFSET: u8 = 1 + 1 + 32 + 32;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+#[inline(always)]
+pub fn load_credential_config(data: &[u8]) -> Result<&CredentialConfig, ProgramError> {
+    bytemuck::try_from_bytes::<CredentialConfig>(data)
+        .map_err(|_| CredentialGateError::InvalidCredentialConfig.into())
+        .and_then(|cfg: &CredentialConfig| {
+            if cfg.discriminator == CredentialConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(CredentialGateError::InvalidCredentialConfig.into())
+            }
+        })
+}
@@ -0,0 +1,16 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RouterError {
+    InvalidRoute,
+    InvalidSubGate,
+    InvalidSubGateExtraMetas,
+    StaleExtraMetas,
+    TooManyExtraMetas,
+}
+
+impl From<RouterError> for ProgramError {
+    fn from(e: RouterError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
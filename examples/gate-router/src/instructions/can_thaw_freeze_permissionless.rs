@@ -0,0 +1,79 @@
+use solana_program::account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::{
+    gate::GateAccounts,
+    onchain::{invoke_can_freeze_permissionless, invoke_can_thaw_permissionless},
+};
+
+use crate::{
+    error::RouterError,
+    state::{load_route, Route},
+};
+
+pub struct CanThawFreezePermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub route: &'a AccountInfo<'a>,
+    pub sub_gate: &'a AccountInfo<'a>,
+    /// The sub-gate's extra metas and the accounts they resolve to.
+    pub sub_gate_accounts: &'a [AccountInfo<'a>],
+}
+
+impl<'a> CanThawFreezePermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [route, sub_gate, sub_gate_accounts @ ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            route,
+            sub_gate,
+            sub_gate_accounts,
+        })
+    }
+
+    /// Checks that the sub-gate the extra metas were built for is still the
+    /// one the mint is routed to.
+    fn check_route(&self) -> ProgramResult {
+        if self.route.owner != &crate::ID {
+            return Err(RouterError::InvalidRoute.into());
+        }
+        let data = self.route.try_borrow_data()?;
+        let route: &Route = load_route(&data)?;
+        if &route.mint != self.gate.mint.key {
+            return Err(RouterError::InvalidRoute.into());
+        }
+        if &route.sub_gate != self.sub_gate.key {
+            return Err(RouterError::StaleExtraMetas.into());
+        }
+        Ok(())
+    }
+
+    pub fn process_thaw(&self) -> ProgramResult {
+        self.check_route()?;
+
+        invoke_can_thaw_permissionless(
+            self.sub_gate.key,
+            self.gate.caller.clone(),
+            self.gate.token_account.clone(),
+            self.gate.mint.clone(),
+            self.gate.token_account_owner.clone(),
+            self.gate.flag_account.clone(),
+            self.sub_gate_accounts,
+        )
+    }
+
+    pub fn process_freeze(&self) -> ProgramResult {
+        self.check_route()?;
+
+        invoke_can_freeze_permissionless(
+            self.sub_gate.key,
+            self.gate.caller.clone(),
+            self.gate.token_account.clone(),
+            self.gate.mint.clone(),
+            self.gate.token_account_owner.clone(),
+            self.gate.flag_account.clone(),
+            self.sub_gate_accounts,
+        )
+    }
+}
@@ -0,0 +1,111 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::{check_token_acl_authority, create_pda_account};
+
+use crate::{error::RouterError, state::Route};
+
+/// Routes a mint to a sub-gate. Only the token-acl authority of the mint can
+/// create the route.
+///
+/// The sub-gate's extra metas must be initialized for the mint before the
+/// router's, which are built from them.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("gate-router:create-route")]
+pub struct CreateRoute<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub route: &'a AccountInfo<'a>,
+    pub sub_gate: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub route_bump: u8,
+}
+
+impl CreateRoute<'_> {
+    pub fn process(&self) -> ProgramResult {
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+        check_sub_gate(self.sub_gate)?;
+
+        let bump_seed = [self.route_bump];
+        create_pda_account(
+            self.payer,
+            self.route,
+            Route::LEN,
+            &crate::ID,
+            &[Route::SEED_PREFIX, self.mint.key.as_ref(), &bump_seed],
+        )?;
+
+        let mut data = self.route.try_borrow_mut_data()?;
+        let route = bytemuck::from_bytes_mut::<Route>(&mut data);
+        route.discriminator = Route::DISCRIMINATOR;
+        route.bump = self.route_bump;
+        route.mint = *self.mint.key;
+        route.sub_gate = *self.sub_gate.key;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateRoute<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, route, sub_gate, system_program] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (route_address, route_bump) = Route::find_pda(mint.key);
+        if route.key != &route_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            route,
+            sub_gate,
+            system_program,
+            route_bump,
+        })
+    }
+}
+
+/// Checks that `sub_gate` is a program other than the router, so calls
+/// cannot loop back into it.
+pub(crate) fn check_sub_gate(sub_gate: &AccountInfo) -> ProgramResult {
+    if !sub_gate.executable || sub_gate.key == &crate::ID {
+        return Err(RouterError::InvalidSubGate.into());
+    }
+    Ok(())
+}
+
+pub fn create_route(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    sub_gate: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(Route::find_pda(mint).0, false),
+            AccountMeta::new_readonly(*sub_gate, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data: CreateRoute::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
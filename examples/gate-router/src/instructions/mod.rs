@@ -0,0 +1,7 @@
+mod can_thaw_freeze_permissionless;
+mod create_route;
+mod set_route;
+
+pub use can_thaw_freeze_permissionless::*;
+pub use create_route::*;
+pub use set_route::*;
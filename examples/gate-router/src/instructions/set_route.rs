@@ -0,0 +1,85 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::check_token_acl_authority;
+
+use crate::{
+    error::RouterError,
+    instructions::check_sub_gate,
+    state::{load_route, Route},
+};
+
+/// Moves a mint to another sub-gate.
+///
+/// The router's extra metas still describe the previous sub-gate, so
+/// permissionless operations fail with `StaleExtraMetas` until
+/// `UpdateExtraAccountMetas` is called, e.g. in the same transaction.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("gate-router:set-route")]
+pub struct SetRoute<'a> {
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub route: &'a AccountInfo<'a>,
+    pub sub_gate: &'a AccountInfo<'a>,
+}
+
+impl SetRoute<'_> {
+    pub fn process(&self) -> ProgramResult {
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+        check_sub_gate(self.sub_gate)?;
+
+        if self.route.owner != &crate::ID {
+            return Err(RouterError::InvalidRoute.into());
+        }
+        let mut data = self.route.try_borrow_mut_data()?;
+        if &load_route(&data)?.mint != self.mint.key {
+            return Err(RouterError::InvalidRoute.into());
+        }
+
+        bytemuck::from_bytes_mut::<Route>(&mut data).sub_gate = *self.sub_gate.key;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for SetRoute<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [authority, mint, mint_config, route, sub_gate] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            mint,
+            mint_config,
+            route,
+            sub_gate,
+        })
+    }
+}
+
+pub fn set_route(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    sub_gate: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(Route::find_pda(mint).0, false),
+            AccountMeta::new_readonly(*sub_gate, false),
+        ],
+        data: SetRoute::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
@@ -0,0 +1,153 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, instruction::AccountMeta,
+    pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{
+    gate::GateAccounts,
+    get_freeze_extra_account_metas_address, get_thaw_extra_account_metas_address,
+    instruction::{
+        CanFreezePermissionlessInstruction, CanThawPermissionlessInstruction,
+        EfficientBlockAllowInstruction,
+    },
+    FREEZE_EXTRA_ACCOUNT_METAS_SEED, THAW_EXTRA_ACCOUNT_METAS_SEED,
+};
+
+pub mod error;
+pub mod instructions;
+pub mod remap;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts1111111111111111111111111111111111111G");
+
+/// Gate router: one deployment serving many mints, each routed to its own
+/// sub-gate. `CanThawPermissionless` and `CanFreezePermissionless` are
+/// forwarded to the sub-gate, whose extra metas are copied into the router's
+/// so token-acl resolves them along with the router's own.
+///
+/// The router's extra metas are built from the route and the sub-gate's
+/// extra metas, passed after the fixed accounts of `InitializeExtraAccountMetas`
+/// and `UpdateExtraAccountMetas` as listed by [`extra_metas_accounts`]. They
+/// must be updated whenever the route or the sub-gate's extra metas change.
+pub struct GateRouter;
+
+gate_program!(GateRouter);
+
+impl GateProgram for GateRouter {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_thaw()
+    }
+
+    fn can_freeze(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_freeze()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        mint: &Pubkey,
+        instruction: EfficientBlockAllowInstruction,
+        accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        let [route, sub_gate_thaw_extra_metas, sub_gate_freeze_extra_metas] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if route.owner != &ID || route.key != &state::Route::find_pda(mint).0 {
+            return Err(error::RouterError::InvalidRoute.into());
+        }
+        let sub_gate = state::load_route(&route.try_borrow_data()?)?.sub_gate;
+
+        let (sub_gate_extra_metas, address, extra_metas_seed) = match instruction {
+            EfficientBlockAllowInstruction::CanThawPermissionless => (
+                sub_gate_thaw_extra_metas,
+                get_thaw_extra_account_metas_address(mint, &sub_gate),
+                THAW_EXTRA_ACCOUNT_METAS_SEED,
+            ),
+            EfficientBlockAllowInstruction::CanFreezePermissionless => (
+                sub_gate_freeze_extra_metas,
+                get_freeze_extra_account_metas_address(mint, &sub_gate),
+                FREEZE_EXTRA_ACCOUNT_METAS_SEED,
+            ),
+        };
+        if sub_gate_extra_metas.key != &address || sub_gate_extra_metas.owner != &sub_gate {
+            return Err(error::RouterError::InvalidSubGateExtraMetas.into());
+        }
+
+        let mut metas = vec![
+            // [6] route
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::Route::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+            // [7] sub-gate, checked against the route so stale metas are rejected
+            ExtraAccountMeta::new_with_pubkey(&sub_gate, false, false)?,
+            // [8] sub-gate extra metas
+            ExtraAccountMeta::new_external_pda_with_seeds(
+                remap::SUB_GATE_INDEX,
+                &[
+                    Seed::Literal {
+                        bytes: extra_metas_seed.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+        ];
+
+        // [9..] sub-gate extra accounts
+        let data = sub_gate_extra_metas.try_borrow_data()?;
+        metas.extend(match instruction {
+            EfficientBlockAllowInstruction::CanThawPermissionless => {
+                remap::remap_list::<CanThawPermissionlessInstruction>(&data)?
+            }
+            EfficientBlockAllowInstruction::CanFreezePermissionless => {
+                remap::remap_list::<CanFreezePermissionlessInstruction>(&data)?
+            }
+        });
+
+        Ok(metas)
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, _remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateRoute::SPL_DISCRIMINATOR_SLICE => CreateRoute::try_from(accounts)?.process(),
+            SetRoute::SPL_DISCRIMINATOR_SLICE => SetRoute::try_from(accounts)?.process(),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Accounts to pass to `InitializeExtraAccountMetas` and
+/// `UpdateExtraAccountMetas` for a mint routed to `sub_gate`.
+pub fn extra_metas_accounts(mint: &Pubkey, sub_gate: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(state::Route::find_pda(mint).0, false),
+        AccountMeta::new_readonly(get_thaw_extra_account_metas_address(mint, sub_gate), false),
+        AccountMeta::new_readonly(
+            get_freeze_extra_account_metas_address(mint, sub_gate),
+            false,
+        ),
+    ]
+}
@@ -0,0 +1,123 @@
+//! Rewrites a sub-gate's extra metas so they resolve from the router's
+//! accounts.
+//!
+//! The sub-gate's metas index into the accounts of its own `CanThaw`/
+//! `CanFreeze` call: the five base accounts, its extra metas account at [5]
+//! and its extra accounts from [6]. In the router's call the base accounts
+//! are unchanged, but the router's own extra accounts come first:
+//!
+//! | router | account                   | sub-gate |
+//! |--------|---------------------------|----------|
+//! | 0..=4  | base accounts             | 0..=4    |
+//! | 5      | router extra metas        | -        |
+//! | 6      | route                     | -        |
+//! | 7      | sub-gate program          | -        |
+//! | 8      | sub-gate extra metas      | 5        |
+//! | 9..    | sub-gate extra accounts   | 6..      |
+//!
+//! PDAs of the sub-gate itself are derived from the program at [7] instead of
+//! the executing program, which is now the router.
+
+use solana_program_error::ProgramError;
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, pubkey_data::PubkeyData, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_type_length_value::state::TlvStateBorrowed;
+
+use crate::error::RouterError;
+
+/// Index of the sub-gate program in the router's accounts.
+pub const SUB_GATE_INDEX: u8 = 7;
+/// Index of the sub-gate's extra metas account in the router's accounts.
+pub const SUB_GATE_EXTRA_METAS_INDEX: u8 = 8;
+
+/// Index of the extra metas account in a gate's accounts.
+const EXTRA_METAS_INDEX: u8 = 5;
+/// Accounts the router adds before the sub-gate's extra accounts.
+const ROUTER_EXTRA_ACCOUNTS: u8 = 3;
+/// Top bit of a discriminator holding the program index of an external PDA.
+const EXTERNAL_PDA_FLAG: u8 = 1 << 7;
+
+/// Maps an account index of the sub-gate's call to the router's call.
+pub fn remap_index(index: u8) -> Result<u8, ProgramError> {
+    match index {
+        _ if index < EXTRA_METAS_INDEX => Ok(index),
+        EXTRA_METAS_INDEX => Ok(SUB_GATE_EXTRA_METAS_INDEX),
+        _ => index
+            .checked_add(ROUTER_EXTRA_ACCOUNTS)
+            .filter(|index| *index < EXTERNAL_PDA_FLAG)
+            .ok_or_else(|| RouterError::TooManyExtraMetas.into()),
+    }
+}
+
+/// Rewrites one extra meta of the sub-gate for the router's list.
+pub fn remap_meta(meta: &ExtraAccountMeta) -> Result<ExtraAccountMeta, ProgramError> {
+    let is_signer = bool::from(meta.is_signer);
+    let is_writable = bool::from(meta.is_writable);
+
+    match meta.discriminator {
+        0 => Ok(*meta),
+        1 => ExtraAccountMeta::new_external_pda_with_seeds(
+            SUB_GATE_INDEX,
+            &remap_seeds(&meta.address_config)?,
+            is_signer,
+            is_writable,
+        ),
+        2 => {
+            let key_data = match PubkeyData::unpack(&meta.address_config)? {
+                PubkeyData::AccountData {
+                    account_index,
+                    data_index,
+                } => PubkeyData::AccountData {
+                    account_index: remap_index(account_index)?,
+                    data_index,
+                },
+                key_data => key_data,
+            };
+            ExtraAccountMeta::new_with_pubkey_data(&key_data, is_signer, is_writable)
+        }
+        discriminator if discriminator >= EXTERNAL_PDA_FLAG => {
+            ExtraAccountMeta::new_external_pda_with_seeds(
+                remap_index(discriminator - EXTERNAL_PDA_FLAG)?,
+                &remap_seeds(&meta.address_config)?,
+                is_signer,
+                is_writable,
+            )
+        }
+        _ => Err(RouterError::InvalidSubGateExtraMetas.into()),
+    }
+}
+
+fn remap_seeds(address_config: &[u8; 32]) -> Result<Vec<Seed>, ProgramError> {
+    Seed::unpack_address_config(address_config)?
+        .into_iter()
+        .map(|seed| {
+            Ok(match seed {
+                Seed::AccountKey { index } => Seed::AccountKey {
+                    index: remap_index(index)?,
+                },
+                Seed::AccountData {
+                    account_index,
+                    data_index,
+                    length,
+                } => Seed::AccountData {
+                    account_index: remap_index(account_index)?,
+                    data_index,
+                    length,
+                },
+                seed => seed,
+            })
+        })
+        .collect()
+}
+
+/// Reads the sub-gate's extra metas list for instruction `T` and rewrites
+/// every entry.
+pub fn remap_list<T: SplDiscriminate>(data: &[u8]) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+    let state = TlvStateBorrowed::unpack(data)?;
+    ExtraAccountMetaList::unpack_with_tlv_state::<T>(&state)?
+        .iter()
+        .map(remap_meta)
+        .collect()
+}
@@ -0,0 +1,38 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+
+use crate::error::RouterError;
+
+/// Sub-gate a mint's permissionless operations are forwarded to.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct Route {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub sub_gate: Pubkey,
+}
+
+impl Route {
+    pub const SEED_PREFIX: &'static [u8] = b"route";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 32 + 32;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+#[inline(always)]
+pub fn load_route(data: &[u8]) -> Result<&Route, ProgramError> {
+    bytemuck::try_from_bytes::<Route>(data)
+        .map_err(|_| RouterError::InvalidRoute.into())
+        .and_then(|route: &Route| {
+            if route.discriminator == Route::DISCRIMINATOR {
+                Ok(route)
+            } else {
+                Err(RouterError::InvalidRoute.into())
+            }
+        })
+}
@@ -0,0 +1,16 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MerkleListError {
+    InvalidListConfig,
+    InvalidProofBuffer,
+    InvalidListAuthority,
+    ProofTooLong,
+    WalletNotListed,
+}
+
+impl From<MerkleListError> for ProgramError {
+    fn from(e: MerkleListError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
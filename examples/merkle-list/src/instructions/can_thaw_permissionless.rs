@@ -0,0 +1,60 @@
+use solana_program::account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::gate::GateAccounts;
+
+use crate::{
+    error::MerkleListError,
+    merkle,
+    state::{load_list_config, load_proof_buffer},
+};
+
+pub struct CanThawPermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    /// Proof buffer of the caller, resolved by token-acl from the extra metas.
+    pub proof_buffer: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawPermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [list_config, proof_buffer, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            list_config,
+            proof_buffer,
+        })
+    }
+
+    /// Succeeds when the caller's proof buffer holds a proof, for the token
+    /// account owner, against the current root of the mint's list.
+    pub fn process(&self) -> ProgramResult {
+        if self.list_config.owner != &crate::ID {
+            return Err(MerkleListError::InvalidListConfig.into());
+        }
+        let data = self.list_config.try_borrow_data()?;
+        let list_config = load_list_config(&data)?;
+        if &list_config.mint != self.gate.mint.key {
+            return Err(MerkleListError::InvalidListConfig.into());
+        }
+
+        if self.proof_buffer.owner != &crate::ID || self.proof_buffer.data_is_empty() {
+            return Err(MerkleListError::WalletNotListed.into());
+        }
+        let data = self.proof_buffer.try_borrow_data()?;
+        let (header, proof) = load_proof_buffer(&data)?;
+        if &header.mint != self.gate.mint.key
+            || &header.caller != self.gate.caller.key
+            || &header.wallet != self.gate.token_account_owner.key
+        {
+            return Err(MerkleListError::WalletNotListed.into());
+        }
+
+        if !merkle::verify(&list_config.root, &header.wallet, proof) {
+            return Err(MerkleListError::WalletNotListed.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::close_account;
+
+use crate::{error::MerkleListError, state::load_proof_buffer, state::ProofBuffer};
+
+/// Closes the caller's proof buffer and returns its rent.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("merkle-list-gate:close-proof")]
+pub struct CloseProof<'a> {
+    pub caller: &'a AccountInfo<'a>,
+    pub proof_buffer: &'a AccountInfo<'a>,
+    pub destination: &'a AccountInfo<'a>,
+}
+
+impl CloseProof<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        if self.proof_buffer.owner != &crate::ID {
+            return Err(MerkleListError::InvalidProofBuffer.into());
+        }
+        {
+            let data = self.proof_buffer.try_borrow_data()?;
+            let (header, _) = load_proof_buffer(&data)?;
+            if !self.caller.is_signer || &header.caller != self.caller.key {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+        }
+
+        close_account(self.proof_buffer, self.destination)
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CloseProof<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [caller, proof_buffer, destination] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            caller,
+            proof_buffer,
+            destination,
+        })
+    }
+}
+
+pub fn close_proof(caller: &Pubkey, mint: &Pubkey, destination: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*caller, true),
+            AccountMeta::new(ProofBuffer::find_pda(mint, caller).0, false),
+            AccountMeta::new(*destination, false),
+        ],
+        data: CloseProof::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
@@ -0,0 +1,11 @@
+mod can_thaw_permissionless;
+mod close_proof;
+mod create_list;
+mod set_root;
+mod write_proof;
+
+pub use can_thaw_permissionless::*;
+pub use close_proof::*;
+pub use create_list::*;
+pub use set_root::*;
+pub use write_proof::*;
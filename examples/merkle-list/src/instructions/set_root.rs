@@ -0,0 +1,70 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{
+    error::MerkleListError,
+    state::{load_list_config, ListConfig},
+};
+
+/// Replaces the root of a Merkle list. Proofs against the previous root stop
+/// verifying immediately.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("merkle-list-gate:set-root")]
+pub struct SetRoot<'a> {
+    pub list_authority: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+}
+
+impl SetRoot<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let root = <[u8; 32]>::try_from(remaining_data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        if self.list_config.owner != &crate::ID {
+            return Err(MerkleListError::InvalidListConfig.into());
+        }
+        let mut data = self.list_config.try_borrow_mut_data()?;
+        let list_config = load_list_config(&data)?;
+        if !self.list_authority.is_signer || &list_config.authority != self.list_authority.key {
+            return Err(MerkleListError::InvalidListAuthority.into());
+        }
+
+        bytemuck::from_bytes_mut::<ListConfig>(&mut data).root = root;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for SetRoot<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [list_authority, list_config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            list_authority,
+            list_config,
+        })
+    }
+}
+
+pub fn set_root(list_authority: &Pubkey, mint: &Pubkey, root: &[u8; 32]) -> Instruction {
+    let mut data = SetRoot::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(root);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*list_authority, true),
+            AccountMeta::new(ListConfig::find_pda(mint).0, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,136 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::create_pda_account;
+
+use crate::{
+    error::MerkleListError,
+    state::{load_list_config, ProofBuffer},
+};
+
+/// Writes the proof of a wallet into the caller's proof buffer, creating or
+/// resizing it as needed. The proof is only checked when thawing.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("merkle-list-gate:write-proof")]
+pub struct WriteProof<'a> {
+    pub caller: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    pub proof_buffer: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+}
+
+impl WriteProof<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let (wallet, proof) = remaining_data
+            .split_at_checked(32)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let wallet = Pubkey::try_from(wallet).map_err(|_| ProgramError::InvalidInstructionData)?;
+        if proof.len() % 32 != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if proof.len() / 32 > ProofBuffer::MAX_PROOF_LEN {
+            return Err(MerkleListError::ProofTooLong.into());
+        }
+
+        if !self.caller.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if self.list_config.owner != &crate::ID {
+            return Err(MerkleListError::InvalidListConfig.into());
+        }
+        let mint = load_list_config(&self.list_config.try_borrow_data()?)?.mint;
+
+        let (proof_buffer_address, bump) = ProofBuffer::find_pda(&mint, self.caller.key);
+        if self.proof_buffer.key != &proof_buffer_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let size = ProofBuffer::LEN + proof.len();
+        if self.proof_buffer.owner == &crate::ID {
+            let missing_lamports = Rent::get()?
+                .minimum_balance(size)
+                .saturating_sub(self.proof_buffer.lamports());
+            if missing_lamports > 0 {
+                let ix = solana_system_interface::instruction::transfer(
+                    self.caller.key,
+                    self.proof_buffer.key,
+                    missing_lamports,
+                );
+                invoke(&ix, &[self.caller.clone(), self.proof_buffer.clone()])?;
+            }
+            self.proof_buffer.resize(size)?;
+        } else {
+            let bump_seed = [bump];
+            create_pda_account(
+                self.caller,
+                self.proof_buffer,
+                size,
+                &crate::ID,
+                &[
+                    ProofBuffer::SEED_PREFIX,
+                    mint.as_ref(),
+                    self.caller.key.as_ref(),
+                    &bump_seed,
+                ],
+            )?;
+        }
+
+        let mut data = self.proof_buffer.try_borrow_mut_data()?;
+        let (header, nodes) = data.split_at_mut(ProofBuffer::LEN);
+        let header = bytemuck::from_bytes_mut::<ProofBuffer>(header);
+        header.discriminator = ProofBuffer::DISCRIMINATOR;
+        header.bump = bump;
+        header.mint = mint;
+        header.caller = *self.caller.key;
+        header.wallet = wallet;
+        nodes.copy_from_slice(proof);
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for WriteProof<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [caller, list_config, proof_buffer, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            caller,
+            list_config,
+            proof_buffer,
+            system_program,
+        })
+    }
+}
+
+pub fn write_proof(
+    caller: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    proof: &[[u8; 32]],
+) -> Instruction {
+    let mut data = WriteProof::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(wallet.as_ref());
+    data.extend_from_slice(proof.as_flattened());
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*caller, true),
+            AccountMeta::new_readonly(crate::state::ListConfig::find_pda(mint).0, false),
+            AccountMeta::new(ProofBuffer::find_pda(mint, caller).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,98 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod error;
+pub mod instructions;
+pub mod merkle;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts11111111111111111111111111111111111119");
+
+/// Merkle-root allow-list gate: the list only stores the root over its
+/// wallets, and a token account can be thawed permissionlessly once its
+/// caller writes a membership proof for the owner into its proof buffer.
+///
+/// Membership proofs cannot show that a wallet was removed, so permissionless
+/// freeze is not supported and freezing stays with the token-acl authority.
+pub struct MerkleList;
+
+gate_program!(MerkleList);
+
+impl GateProgram for MerkleList {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawPermissionless::from_gate_accounts(accounts)?.process()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        if instruction == EfficientBlockAllowInstruction::CanFreezePermissionless {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![
+            // [6] list config
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::ListConfig::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+            // [7] proof buffer of the caller
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::ProofBuffer::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                    Seed::AccountKey { index: 0 }, // caller
+                ],
+                false,
+                false,
+            )?,
+        ])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateList::SPL_DISCRIMINATOR_SLICE => {
+                CreateList::try_from(accounts)?.process(remaining_data)
+            }
+            SetRoot::SPL_DISCRIMINATOR_SLICE => {
+                SetRoot::try_from(accounts)?.process(remaining_data)
+            }
+            WriteProof::SPL_DISCRIMINATOR_SLICE => {
+                WriteProof::try_from(accounts)?.process(remaining_data)
+            }
+            CloseProof::SPL_DISCRIMINATOR_SLICE => {
+                CloseProof::try_from(accounts)?.process(remaining_data)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
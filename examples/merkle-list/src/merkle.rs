@@ -0,0 +1,74 @@
+//! Merkle tree used by the list: sha256 leaves over wallet addresses, with
+//! domain-separated leaves and nodes and sorted pairs, so proofs are plain
+//! lists of sibling hashes.
+//!
+//! [`root`] and [`proof`] build the tree off-chain; the program only calls
+//! [`verify`].
+
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+pub const LEAF_PREFIX: &[u8] = &[0];
+pub const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf_hash(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet.as_ref()]).to_bytes()
+}
+
+pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, lo, hi]).to_bytes()
+}
+
+/// Whether `proof` links the leaf of `wallet` to `root`.
+pub fn verify<'a>(
+    root: &[u8; 32],
+    wallet: &Pubkey,
+    proof: impl IntoIterator<Item = &'a [u8; 32]>,
+) -> bool {
+    let computed = proof
+        .into_iter()
+        .fold(leaf_hash(wallet), |acc, sibling| node_hash(&acc, sibling));
+    &computed == root
+}
+
+/// Root of the tree over `wallets`. An odd node at the end of a level is
+/// carried up unchanged; an empty list has the all-zero root.
+pub fn root(wallets: &[Pubkey]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = wallets.iter().map(leaf_hash).collect();
+    if level.is_empty() {
+        return [0; 32];
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Proof of the wallet at `index` in `wallets`, or `None` if out of bounds.
+pub fn proof(wallets: &[Pubkey], mut index: usize) -> Option<Vec<[u8; 32]>> {
+    let mut level: Vec<[u8; 32]> = wallets.iter().map(leaf_hash).collect();
+    if index >= level.len() {
+        return None;
+    }
+
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    Some(proof)
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => node_hash(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
@@ -0,0 +1,89 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+
+use crate::error::MerkleListError;
+
+/// Per-mint Merkle allow list, holding the root of the listed wallets and the
+/// authority that can replace it.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ListConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub root: [u8; 32],
+}
+
+impl ListConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"merkle_list";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 32;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+/// Header of a caller's proof buffer, followed by the proof nodes.
+///
+/// Buffers are keyed by the caller of the permissionless thaw rather than the
+/// wallet, so only the caller can write the proof token-acl hands to the gate.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ProofBuffer {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub caller: Pubkey,
+    pub wallet: Pubkey,
+}
+
+impl ProofBuffer {
+    pub const SEED_PREFIX: &'static [u8] = b"merkle_proof";
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 32;
+    /// Enough for lists of up to 2^32 wallets.
+    pub const MAX_PROOF_LEN: usize = 32;
+
+    pub fn find_pda(mint: &Pubkey, caller: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED_PREFIX, mint.as_ref(), caller.as_ref()],
+            &crate::ID,
+        )
+    }
+
+    pub fn size_of(proof_len: usize) -> usize {
+        Self::LEN + proof_len * 32
+    }
+}
+
+#[inline(always)]
+pub fn load_list_config(data: &[u8]) -> Result<&ListConfig, ProgramError> {
+    bytemuck::try_from_bytes::<ListConfig>(data)
+        .map_err(|_| MerkleListError::InvalidListConfig.into())
+        .and_then(|cfg: &ListConfig| {
+            if cfg.discriminator == ListConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(MerkleListError::InvalidListConfig.into())
+            }
+        })
+}
+
+/// Splits a proof buffer into its header and proof nodes.
+#[inline(always)]
+pub fn load_proof_buffer(data: &[u8]) -> Result<(&ProofBuffer, &[[u8; 32]]), ProgramError> {
+    let (header, nodes) = data
+        .split_at_checked(ProofBuffer::LEN)
+        .ok_or(MerkleListError::InvalidProofBuffer)?;
+    let header = bytemuck::try_from_bytes::<ProofBuffer>(header)
+        .map_err(|_| MerkleListError::InvalidProofBuffer)?;
+    if header.discriminator != ProofBuffer::DISCRIMINATOR {
+        return Err(MerkleListError::InvalidProofBuffer.into());
+    }
+    let nodes = bytemuck::try_cast_slice::<u8, [u8; 32]>(nodes)
+        .map_err(|_| MerkleListError::InvalidProofBuffer)?;
+    Ok((header, nodes))
+}
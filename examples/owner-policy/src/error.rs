@@ -0,0 +1,21 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OwnerPolicyError {
+    InvalidPolicyConfig,
+    InvalidPolicy,
+    InvalidRegistry,
+    InvalidRegistryAuthority,
+    RegistryFull,
+    ProgramAlreadyApproved,
+    ProgramNotApproved,
+    OwnerNotAllowed,
+    OwnerAllowed,
+    InvalidPdaProof,
+}
+
+impl From<OwnerPolicyError> for ProgramError {
+    fn from(e: OwnerPolicyError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,90 @@
+#[allow(deprecated)]
+use solana_curve25519::edwards::{validate_edwards, PodEdwardsPoint};
+use solana_program::account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::gate::GateAccounts;
+
+use crate::{
+    error::OwnerPolicyError,
+    state::{load_pda_proof, load_policy_config, load_registry, OwnerPolicy},
+};
+
+pub struct CanThawFreezePermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub policy_config: &'a AccountInfo<'a>,
+    /// Registry named by the policy config, resolved by token-acl from its data.
+    pub registry: &'a AccountInfo<'a>,
+    /// PDA proof of the token account owner, resolved by token-acl from the extra metas.
+    pub pda_proof: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawFreezePermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [policy_config, registry, pda_proof, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            policy_config,
+            registry,
+            pda_proof,
+        })
+    }
+
+    /// Whether the token account owner is of a kind the mint's policy accepts.
+    ///
+    /// A PDA is attributed to the program it was proven to derive from, so a
+    /// PDA without a proof is not allowed whatever program owns its account.
+    fn is_allowed(&self) -> Result<bool, ProgramError> {
+        if self.policy_config.owner != &crate::ID {
+            return Err(OwnerPolicyError::InvalidPolicyConfig.into());
+        }
+        let data = self.policy_config.try_borrow_data()?;
+        let config = load_policy_config(&data)?;
+        if &config.mint != self.gate.mint.key {
+            return Err(OwnerPolicyError::InvalidPolicyConfig.into());
+        }
+        let policy = OwnerPolicy::try_from(config.policy)?;
+
+        let owner = self.gate.token_account_owner;
+        // uses the curve syscall on-chain, unlike Pubkey::is_on_curve
+        #[allow(deprecated)]
+        let is_wallet = validate_edwards(&PodEdwardsPoint(owner.key.to_bytes()));
+        if is_wallet {
+            return Ok(policy.allows_wallets());
+        }
+        if !policy.allows_approved_programs() {
+            return Ok(false);
+        }
+
+        if self.registry.owner != &crate::ID || self.registry.key != &config.registry {
+            return Err(OwnerPolicyError::InvalidRegistry.into());
+        }
+        if self.pda_proof.owner != &crate::ID || self.pda_proof.data_is_empty() {
+            return Ok(false);
+        }
+        let data = self.pda_proof.try_borrow_data()?;
+        let pda_proof = load_pda_proof(&data)?;
+        if &pda_proof.pda != owner.key {
+            return Err(OwnerPolicyError::InvalidPdaProof.into());
+        }
+
+        let data = self.registry.try_borrow_data()?;
+        Ok(load_registry(&data)?.is_approved(&pda_proof.program_id))
+    }
+
+    pub fn process_thaw(&self) -> ProgramResult {
+        if !self.is_allowed()? {
+            return Err(OwnerPolicyError::OwnerNotAllowed.into());
+        }
+        Ok(())
+    }
+
+    pub fn process_freeze(&self) -> ProgramResult {
+        if self.is_allowed()? {
+            return Err(OwnerPolicyError::OwnerAllowed.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::{check_token_acl_authority, create_pda_account};
+
+use crate::state::{OwnerPolicy, PolicyConfig};
+
+/// Sets the owner policy of a mint and the registry its approved programs
+/// come from. Only the token-acl authority of the mint can create the config.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("owner-policy-gate:create-config")]
+pub struct CreateConfig<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub policy_config: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub policy_config_bump: u8,
+}
+
+impl CreateConfig<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let [policy, registry @ ..] = remaining_data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let policy = OwnerPolicy::try_from(*policy)?;
+        let registry =
+            Pubkey::try_from(registry).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        let bump_seed = [self.policy_config_bump];
+        create_pda_account(
+            self.payer,
+            self.policy_config,
+            PolicyConfig::LEN,
+            &crate::ID,
+            &[
+                PolicyConfig::SEED_PREFIX,
+                self.mint.key.as_ref(),
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.policy_config.try_borrow_mut_data()?;
+        let config = bytemuck::from_bytes_mut::<PolicyConfig>(&mut data);
+        config.discriminator = PolicyConfig::DISCRIMINATOR;
+        config.bump = self.policy_config_bump;
+        config.policy = policy as u8;
+        config.mint = *self.mint.key;
+        config.registry = registry;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, policy_config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (policy_config_address, policy_config_bump) = PolicyConfig::find_pda(mint.key);
+        if policy_config.key != &policy_config_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            policy_config,
+            system_program,
+            policy_config_bump,
+        })
+    }
+}
+
+pub fn create_config(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    policy: OwnerPolicy,
+    registry: &Pubkey,
+) -> Instruction {
+    let mut data = CreateConfig::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.push(policy as u8);
+    data.extend_from_slice(registry.as_ref());
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(PolicyConfig::find_pda(mint).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
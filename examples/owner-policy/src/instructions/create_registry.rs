@@ -0,0 +1,108 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::create_pda_account;
+
+use crate::{
+    error::OwnerPolicyError,
+    state::{load_registry, ProgramRegistry},
+};
+
+/// Creates an empty program registry for `registry_authority`, which any
+/// number of mints can then refer to.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("owner-policy-gate:create-registry")]
+pub struct CreateRegistry<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub registry_authority: &'a AccountInfo<'a>,
+    pub registry: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub registry_bump: u8,
+}
+
+impl CreateRegistry<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        if !self.registry_authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bump_seed = [self.registry_bump];
+        create_pda_account(
+            self.payer,
+            self.registry,
+            ProgramRegistry::LEN,
+            &crate::ID,
+            &[
+                ProgramRegistry::SEED_PREFIX,
+                self.registry_authority.key.as_ref(),
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.registry.try_borrow_mut_data()?;
+        let registry = bytemuck::from_bytes_mut::<ProgramRegistry>(&mut data);
+        registry.discriminator = ProgramRegistry::DISCRIMINATOR;
+        registry.bump = self.registry_bump;
+        registry.authority = *self.registry_authority.key;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateRegistry<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, registry_authority, registry, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (registry_address, registry_bump) = ProgramRegistry::find_pda(registry_authority.key);
+        if registry.key != &registry_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            registry_authority,
+            registry,
+            system_program,
+            registry_bump,
+        })
+    }
+}
+
+/// Checks that `registry` is a registry of this program and that
+/// `registry_authority` signed as its authority.
+pub(crate) fn check_registry_authority(
+    registry: &AccountInfo,
+    registry_authority: &AccountInfo,
+) -> ProgramResult {
+    if registry.owner != &crate::ID {
+        return Err(OwnerPolicyError::InvalidRegistry.into());
+    }
+    let data = registry.try_borrow_data()?;
+    let registry = load_registry(&data)?;
+    if !registry_authority.is_signer || &registry.authority != registry_authority.key {
+        return Err(OwnerPolicyError::InvalidRegistryAuthority.into());
+    }
+
+    Ok(())
+}
+
+pub fn create_registry(payer: &Pubkey, registry_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*registry_authority, true),
+            AccountMeta::new(ProgramRegistry::find_pda(registry_authority).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data: CreateRegistry::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
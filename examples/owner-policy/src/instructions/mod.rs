@@ -0,0 +1,13 @@
+mod add_program;
+mod can_thaw_freeze_permissionless;
+mod create_config;
+mod create_registry;
+mod prove_pda;
+mod remove_program;
+
+pub use add_program::*;
+pub use can_thaw_freeze_permissionless::*;
+pub use create_config::*;
+pub use create_registry::*;
+pub use prove_pda::*;
+pub use remove_program::*;
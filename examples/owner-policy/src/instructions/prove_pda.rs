@@ -0,0 +1,105 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::create_pda_account;
+
+use crate::state::PdaProof;
+
+/// Proves that a PDA derives from a program by deriving it from the seeds,
+/// bump included, and records the result in the PDA's proof. Anyone can prove
+/// a PDA, whether its program is approved is only checked when thawing.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("owner-policy-gate:prove-pda")]
+pub struct ProvePda<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub pda_proof: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+}
+
+impl ProvePda<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let (program_id, mut seeds_data) = remaining_data
+            .split_at_checked(32)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let program_id =
+            Pubkey::try_from(program_id).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        // each seed is prefixed by its length
+        let mut seeds = Vec::new();
+        while let Some((&len, rest)) = seeds_data.split_first() {
+            let (seed, rest) = rest
+                .split_at_checked(len as usize)
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            seeds.push(seed);
+            seeds_data = rest;
+        }
+        let pda = Pubkey::create_program_address(&seeds, &program_id)
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        let (pda_proof_address, bump) = PdaProof::find_pda(&pda);
+        if self.pda_proof.key != &pda_proof_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let bump_seed = [bump];
+        create_pda_account(
+            self.payer,
+            self.pda_proof,
+            PdaProof::LEN,
+            &crate::ID,
+            &[PdaProof::SEED_PREFIX, pda.as_ref(), &bump_seed],
+        )?;
+
+        let mut data = self.pda_proof.try_borrow_mut_data()?;
+        let pda_proof = bytemuck::from_bytes_mut::<PdaProof>(&mut data);
+        pda_proof.discriminator = PdaProof::DISCRIMINATOR;
+        pda_proof.bump = bump;
+        pda_proof.pda = pda;
+        pda_proof.program_id = program_id;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for ProvePda<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, pda_proof, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            payer,
+            pda_proof,
+            system_program,
+        })
+    }
+}
+
+/// `seeds` derive the PDA from `program_id` and end with its bump.
+pub fn prove_pda(payer: &Pubkey, program_id: &Pubkey, seeds: &[&[u8]]) -> Instruction {
+    // seeds that derive no PDA are rejected on-chain
+    let pda = Pubkey::create_program_address(seeds, program_id).unwrap_or_default();
+
+    let mut data = ProvePda::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(program_id.as_ref());
+    for seed in seeds {
+        data.push(seed.len() as u8);
+        data.extend_from_slice(seed);
+    }
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(PdaProof::find_pda(&pda).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,75 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+
+use crate::{
+    error::OwnerPolicyError,
+    instructions::check_registry_authority,
+    state::{load_registry_mut, ProgramRegistry},
+};
+
+/// Revokes a program's approval. Token accounts owned by its PDAs can then be
+/// frozen permissionlessly.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("owner-policy-gate:remove-program")]
+pub struct RemoveProgram<'a> {
+    pub registry_authority: &'a AccountInfo<'a>,
+    pub registry: &'a AccountInfo<'a>,
+}
+
+impl RemoveProgram<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let program_id =
+            Pubkey::try_from(remaining_data).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        check_registry_authority(self.registry, self.registry_authority)?;
+
+        let mut data = self.registry.try_borrow_mut_data()?;
+        let registry = load_registry_mut(&mut data)?;
+        let index = registry
+            .approved_programs()
+            .iter()
+            .position(|approved| approved == &program_id)
+            .ok_or(OwnerPolicyError::ProgramNotApproved)?;
+
+        let last = registry.num_programs as usize - 1;
+        registry.programs.swap(index, last);
+        registry.programs[last] = Pubkey::default();
+        registry.num_programs -= 1;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for RemoveProgram<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [registry_authority, registry] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            registry_authority,
+            registry,
+        })
+    }
+}
+
+pub fn remove_program(registry_authority: &Pubkey, program_id: &Pubkey) -> Instruction {
+    let mut data = RemoveProgram::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(program_id.as_ref());
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*registry_authority, true),
+            AccountMeta::new(ProgramRegistry::find_pda(registry_authority).0, false),
+        ],
+        data,
+    }
+}
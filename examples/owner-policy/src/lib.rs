@@ -0,0 +1,109 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, pubkey_data::PubkeyData, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts1111111111111111111111111111111111111C");
+
+/// Owner-type policy gate: decides from the token account owner itself
+/// whether it can hold the mint, accepting wallets (owners on the ed25519
+/// curve), PDAs of programs approved in a registry, or both. A PDA is
+/// attributed to its program once its derivation is proven with `ProvePda`.
+///
+/// Owners the policy rejects, e.g. after their program is removed from the
+/// registry, can be frozen permissionlessly.
+pub struct OwnerPolicyGate;
+
+gate_program!(OwnerPolicyGate);
+
+impl GateProgram for OwnerPolicyGate {
+    fn can_thaw(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_thaw()
+    }
+
+    fn can_freeze(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanThawFreezePermissionless::from_gate_accounts(accounts)?.process_freeze()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        _instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        Ok(vec![
+            // [6] policy config
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::PolicyConfig::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                false,
+            )?,
+            // [7] program registry
+            ExtraAccountMeta::new_with_pubkey_data(
+                &PubkeyData::AccountData {
+                    account_index: 6,
+                    data_index: state::PolicyConfig::REGISTRY_OFFSET,
+                },
+                false,
+                false,
+            )?,
+            // [8] PDA proof of the token account owner
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::PdaProof::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 3 }, // token account owner
+                ],
+                false,
+                false,
+            )?,
+        ])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateRegistry::SPL_DISCRIMINATOR_SLICE => {
+                CreateRegistry::try_from(accounts)?.process(remaining_data)
+            }
+            AddProgram::SPL_DISCRIMINATOR_SLICE => {
+                AddProgram::try_from(accounts)?.process(remaining_data)
+            }
+            RemoveProgram::SPL_DISCRIMINATOR_SLICE => {
+                RemoveProgram::try_from(accounts)?.process(remaining_data)
+            }
+            CreateConfig::SPL_DISCRIMINATOR_SLICE => {
+                CreateConfig::try_from(accounts)?.process(remaining_data)
+            }
+            ProvePda::SPL_DISCRIMINATOR_SLICE => {
+                ProvePda::try_from(accounts)?.process(remaining_data)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
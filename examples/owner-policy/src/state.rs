@@ -0,0 +1,165 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+
+use crate::error::OwnerPolicyError;
+
+/// Kinds of token account owners a mint accepts.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OwnerPolicy {
+    /// Owners on the ed25519 curve, i.e. wallets.
+    WalletsOnly = 0,
+    /// PDAs of programs in the registry, proven with a [`PdaProof`].
+    ApprovedProgramsOnly = 1,
+    /// Either of the above.
+    WalletsAndApprovedPrograms = 2,
+}
+
+impl OwnerPolicy {
+    pub fn allows_wallets(self) -> bool {
+        self != OwnerPolicy::ApprovedProgramsOnly
+    }
+
+    pub fn allows_approved_programs(self) -> bool {
+        self != OwnerPolicy::WalletsOnly
+    }
+}
+
+impl TryFrom<u8> for OwnerPolicy {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(OwnerPolicy::WalletsOnly),
+            1 => Ok(OwnerPolicy::ApprovedProgramsOnly),
+            2 => Ok(OwnerPolicy::WalletsAndApprovedPrograms),
+            _ => Err(OwnerPolicyError::InvalidPolicy.into()),
+        }
+    }
+}
+
+/// Per-mint gate configuration: the owner policy and the registry of
+/// approved programs it refers to.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PolicyConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub policy: u8,
+    pub mint: Pubkey,
+    pub registry: Pubkey,
+}
+
+impl PolicyConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"owner_policy";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 1 + 32 + 32;
+    /// Offset of `registry`, read by token-acl to resolve the registry account.
+    pub const REGISTRY_OFFSET: u8 = 1 + 1 + 1 + 32;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+/// Programs whose PDAs may own token accounts, maintained by the registry
+/// authority and shareable between mints.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ProgramRegistry {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub num_programs: u8,
+    pub authority: Pubkey,
+    pub programs: [Pubkey; ProgramRegistry::MAX_PROGRAMS],
+}
+
+impl ProgramRegistry {
+    pub const SEED_PREFIX: &'static [u8] = b"program_registry";
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const MAX_PROGRAMS: usize = 16;
+    pub const LEN: usize = 1 + 1 + 1 + 32 + 32 * Self::MAX_PROGRAMS;
+
+    pub fn find_pda(authority: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, authority.as_ref()], &crate::ID)
+    }
+
+    pub fn approved_programs(&self) -> &[Pubkey] {
+        &self.programs[..self.num_programs as usize]
+    }
+
+    pub fn is_approved(&self, program_id: &Pubkey) -> bool {
+        self.approved_programs().contains(program_id)
+    }
+}
+
+/// Records that `pda` derives from `program_id`, proven once by [`ProvePda`]
+/// from its seeds, so the gate can attribute the PDA to its program.
+///
+/// [`ProvePda`]: crate::ProvePda
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct PdaProof {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub pda: Pubkey,
+    pub program_id: Pubkey,
+}
+
+impl PdaProof {
+    pub const SEED_PREFIX: &'static [u8] = b"pda_proof";
+    pub const DISCRIMINATOR: u8 = 3;
+    pub const LEN: usize = 1 + 1 + 32 + 32;
+
+    pub fn find_pda(pda: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, pda.as_ref()], &crate::ID)
+    }
+}
+
+#[inline(always)]
+pub fn load_policy_config(data: &[u8]) -> Result<&PolicyConfig, ProgramError> {
+    bytemuck::try_from_bytes::<PolicyConfig>(data)
+        .map_err(|_| OwnerPolicyError::InvalidPolicyConfig.into())
+        .and_then(|cfg: &PolicyConfig| {
+            if cfg.discriminator == PolicyConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(OwnerPolicyError::InvalidPolicyConfig.into())
+            }
+        })
+}
+
+#[inline(always)]
+pub fn load_registry(data: &[u8]) -> Result<&ProgramRegistry, ProgramError> {
+    bytemuck::try_from_bytes::<ProgramRegistry>(data)
+        .map_err(|_| OwnerPolicyError::InvalidRegistry.into())
+        .and_then(|registry: &ProgramRegistry| {
+            if registry.discriminator == ProgramRegistry::DISCRIMINATOR
+                && registry.num_programs as usize <= ProgramRegistry::MAX_PROGRAMS
+            {
+                Ok(registry)
+            } else {
+                Err(OwnerPolicyError::InvalidRegistry.into())
+            }
+        })
+}
+
+#[inline(always)]
+pub fn load_registry_mut(data: &mut [u8]) -> Result<&mut ProgramRegistry, ProgramError> {
+    load_registry(data)?;
+    Ok(bytemuck::from_bytes_mut::<ProgramRegistry>(data))
+}
+
+#[inline(always)]
+pub fn load_pda_proof(data: &[u8]) -> Result<&PdaProof, ProgramError> {
+    bytemuck::try_from_bytes::<PdaProof>(data)
+        .map_err(|_| OwnerPolicyError::InvalidPdaProof.into())
+        .and_then(|proof: &PdaProof| {
+            if proof.discriminator == PdaProof::DISCRIMINATOR {
+                Ok(proof)
+            } else {
+                Err(OwnerPolicyError::InvalidPdaProof.into())
+            }
+        })
+}
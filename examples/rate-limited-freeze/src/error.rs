@@ -0,0 +1,16 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RateLimitError {
+    InvalidRateLimitConfig,
+    InvalidLimits,
+    InvalidCallerCounter,
+    MintQuotaExceeded,
+    CallerQuotaExceeded,
+}
+
+impl From<RateLimitError> for ProgramError {
+    fn from(e: RateLimitError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
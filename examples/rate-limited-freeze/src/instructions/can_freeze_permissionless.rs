@@ -0,0 +1,68 @@
+use solana_program::{account_info::AccountInfo, clock::Clock, sysvar::Sysvar};
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::gate::{check_flag_account, GateAccounts};
+
+use crate::{
+    error::RateLimitError,
+    state::{load_caller_counter_mut, load_rate_limit_config_mut, record_freeze},
+};
+
+pub struct CanFreezePermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub rate_limit_config: &'a AccountInfo<'a>,
+    /// Freeze log of the caller, resolved by token-acl from the extra metas.
+    pub caller_counter: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanFreezePermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [rate_limit_config, caller_counter, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            rate_limit_config,
+            caller_counter,
+        })
+    }
+
+    /// Succeeds while both the mint and the caller have quota left in the
+    /// current window, and counts the freeze against both.
+    pub fn process(&self) -> ProgramResult {
+        // Loading the gate accounts already checked the flag account, but the
+        // logs must never be written outside a token-acl freeze, so make the
+        // check explicit here.
+        check_flag_account(self.gate.flag_account, self.gate.token_account.key)?;
+
+        if self.rate_limit_config.owner != &crate::ID {
+            return Err(RateLimitError::InvalidRateLimitConfig.into());
+        }
+        let mut data = self.rate_limit_config.try_borrow_mut_data()?;
+        let config = load_rate_limit_config_mut(&mut data)?;
+        if &config.mint != self.gate.mint.key {
+            return Err(RateLimitError::InvalidRateLimitConfig.into());
+        }
+
+        if self.caller_counter.owner != &crate::ID {
+            return Err(RateLimitError::InvalidCallerCounter.into());
+        }
+        let mut counter_data = self.caller_counter.try_borrow_mut_data()?;
+        let counter = load_caller_counter_mut(&mut counter_data)?;
+        if &counter.mint != self.gate.mint.key || &counter.caller != self.gate.caller.key {
+            return Err(RateLimitError::InvalidCallerCounter.into());
+        }
+
+        let now = Clock::get()?.slot;
+        let window_slots = u64::from(config.window_slots);
+        // a failed check reverts the whole freeze, including the other log
+        if !record_freeze(&mut config.mint_log, config.mint_quota, now, window_slots) {
+            return Err(RateLimitError::MintQuotaExceeded.into());
+        }
+        if !record_freeze(&mut counter.log, config.caller_quota, now, window_slots) {
+            return Err(RateLimitError::CallerQuotaExceeded.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::create_pda_account;
+
+use crate::state::CallerCounter;
+
+/// Registers a caller for a mint by creating its freeze log, paid for by the
+/// caller.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("rate-limited-freeze-gate:create-caller-counter")]
+pub struct CreateCallerCounter<'a> {
+    pub caller: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub caller_counter: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub caller_counter_bump: u8,
+}
+
+impl CreateCallerCounter<'_> {
+    pub fn process(&self, _remaining_data: &[u8]) -> ProgramResult {
+        if !self.caller.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bump_seed = [self.caller_counter_bump];
+        create_pda_account(
+            self.caller,
+            self.caller_counter,
+            CallerCounter::LEN,
+            &crate::ID,
+            &[
+                CallerCounter::SEED_PREFIX,
+                self.mint.key.as_ref(),
+                self.caller.key.as_ref(),
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.caller_counter.try_borrow_mut_data()?;
+        let counter = bytemuck::from_bytes_mut::<CallerCounter>(&mut data);
+        counter.discriminator = CallerCounter::DISCRIMINATOR;
+        counter.bump = self.caller_counter_bump;
+        counter.mint = *self.mint.key;
+        counter.caller = *self.caller.key;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateCallerCounter<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [caller, mint, caller_counter, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (caller_counter_address, caller_counter_bump) =
+            CallerCounter::find_pda(mint.key, caller.key);
+        if caller_counter.key != &caller_counter_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            caller,
+            mint,
+            caller_counter,
+            system_program,
+            caller_counter_bump,
+        })
+    }
+}
+
+pub fn create_caller_counter(caller: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*caller, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(CallerCounter::find_pda(mint, caller).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data: CreateCallerCounter::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    }
+}
@@ -0,0 +1,124 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::{check_token_acl_authority, create_pda_account};
+
+use crate::{
+    error::RateLimitError,
+    state::{RateLimitConfig, MAX_QUOTA},
+};
+
+/// Sets the freeze quotas of a mint: at most `mint_quota` freezes overall and
+/// `caller_quota` per caller within any `window_slots` slots. Only the
+/// token-acl authority of the mint can create the config.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("rate-limited-freeze-gate:create-config")]
+pub struct CreateConfig<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub rate_limit_config: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub rate_limit_config_bump: u8,
+}
+
+impl CreateConfig<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let [w0, w1, w2, w3, w4, w5, w6, w7, mint_quota, caller_quota] = *remaining_data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        let window_slots = u64::from_le_bytes([w0, w1, w2, w3, w4, w5, w6, w7]);
+        if window_slots == 0
+            || !(1..=MAX_QUOTA).contains(&(mint_quota as usize))
+            || !(1..=MAX_QUOTA).contains(&(caller_quota as usize))
+        {
+            return Err(RateLimitError::InvalidLimits.into());
+        }
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        let bump_seed = [self.rate_limit_config_bump];
+        create_pda_account(
+            self.payer,
+            self.rate_limit_config,
+            RateLimitConfig::LEN,
+            &crate::ID,
+            &[
+                RateLimitConfig::SEED_PREFIX,
+                self.mint.key.as_ref(),
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.rate_limit_config.try_borrow_mut_data()?;
+        let config = bytemuck::from_bytes_mut::<RateLimitConfig>(&mut data);
+        config.discriminator = RateLimitConfig::DISCRIMINATOR;
+        config.bump = self.rate_limit_config_bump;
+        config.mint_quota = mint_quota;
+        config.caller_quota = caller_quota;
+        config.mint = *self.mint.key;
+        config.window_slots = window_slots.into();
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, rate_limit_config, system_program] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (rate_limit_config_address, rate_limit_config_bump) =
+            RateLimitConfig::find_pda(mint.key);
+        if rate_limit_config.key != &rate_limit_config_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            rate_limit_config,
+            system_program,
+            rate_limit_config_bump,
+        })
+    }
+}
+
+pub fn create_config(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    window_slots: u64,
+    mint_quota: u8,
+    caller_quota: u8,
+) -> Instruction {
+    let mut data = CreateConfig::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(&window_slots.to_le_bytes());
+    data.push(mint_quota);
+    data.push(caller_quota);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(RateLimitConfig::find_pda(mint).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
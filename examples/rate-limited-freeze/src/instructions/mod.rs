@@ -0,0 +1,7 @@
+mod can_freeze_permissionless;
+mod create_caller_counter;
+mod create_config;
+
+pub use can_freeze_permissionless::*;
+pub use create_caller_counter::*;
+pub use create_config::*;
@@ -0,0 +1,93 @@
+use solana_program::{
+    account_info::AccountInfo, declare_id, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+use solana_program_error::ProgramError;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use token_acl_gate_framework::{gate_program, GateProgram};
+use token_acl_interface::{gate::GateAccounts, instruction::EfficientBlockAllowInstruction};
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+
+declare_id!("Eba1ts1111111111111111111111111111111111111E");
+
+/// Rate-limited freeze gate: any registered caller can freeze token accounts
+/// permissionlessly, but only so many times per sliding window of slots, both
+/// per mint and per caller. This bounds the damage of a policy that is briefly
+/// wrong; a production gate would combine the quotas with its own freeze
+/// condition.
+///
+/// Permissionless thaw is not supported, so thawing stays with the token-acl
+/// authority.
+pub struct RateLimitedFreeze;
+
+gate_program!(RateLimitedFreeze);
+
+impl GateProgram for RateLimitedFreeze {
+    fn can_freeze(_program_id: &Pubkey, accounts: GateAccounts) -> ProgramResult {
+        CanFreezePermissionless::from_gate_accounts(accounts)?.process()
+    }
+
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        if instruction == EfficientBlockAllowInstruction::CanThawPermissionless {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![
+            // [6] rate limit config, holding the mint freeze log
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::RateLimitConfig::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                ],
+                false,
+                true,
+            )?,
+            // [7] freeze log of the caller
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal {
+                        bytes: state::CallerCounter::SEED_PREFIX.to_vec(),
+                    },
+                    Seed::AccountKey { index: 2 }, // mint
+                    Seed::AccountKey { index: 0 }, // caller
+                ],
+                false,
+                true,
+            )?,
+        ])
+    }
+
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let Some((discriminator, remaining_data)) =
+            instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+        else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        match discriminator {
+            CreateConfig::SPL_DISCRIMINATOR_SLICE => {
+                CreateConfig::try_from(accounts)?.process(remaining_data)
+            }
+            CreateCallerCounter::SPL_DISCRIMINATOR_SLICE => {
+                CreateCallerCounter::try_from(accounts)?.process(remaining_data)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use solana_program_error::ProgramError;
+use spl_pod::primitives::PodU64;
+
+use crate::error::RateLimitError;
+
+/// Most freezes a quota can allow per window.
+pub const MAX_QUOTA: usize = 16;
+
+/// Sliding-window log of recent freezes: each entry holds the slot at which a
+/// freeze stops counting against the quota, `0` for an unused entry.
+pub type FreezeLog = [PodU64; MAX_QUOTA];
+
+/// Records a freeze at slot `now` in the first `quota` entries of `log`,
+/// reusing an entry that left the window. Returns `false` when the quota is
+/// used up.
+pub fn record_freeze(log: &mut [PodU64], quota: u8, now: u64, window_slots: u64) -> bool {
+    match log[..quota as usize]
+        .iter_mut()
+        .find(|expiry| u64::from(**expiry) <= now)
+    {
+        Some(entry) => {
+            *entry = now.saturating_add(window_slots).into();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Per-mint gate configuration, along with the mint-wide freeze log.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct RateLimitConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint_quota: u8,
+    pub caller_quota: u8,
+    pub mint: Pubkey,
+    pub window_slots: PodU64,
+    pub mint_log: FreezeLog,
+}
+
+impl RateLimitConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"rate_limit";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 1 + 1 + 32 + 8 + 8 * MAX_QUOTA;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+/// Freeze log of one caller for one mint. Callers register it before they
+/// can freeze, as the gate cannot fund accounts itself.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct CallerCounter {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub caller: Pubkey,
+    pub log: FreezeLog,
+}
+
+impl CallerCounter {
+    pub const SEED_PREFIX: &'static [u8] = b"rate_limit_caller";
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 8 * MAX_QUOTA;
+
+    pub fn find_pda(mint: &Pubkey, caller: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[Self::SEED_PREFIX, mint.as_ref(), caller.as_ref()],
+            &crate::ID,
+        )
+    }
+}
+
+#[inline(always)]
+pub fn load_rate_limit_config_mut(data: &mut [u8]) -> Result<&mut RateLimitConfig, ProgramError> {
+    bytemuck::try_from_bytes_mut::<RateLimitConfig>(data)
+        .map_err(|_| RateLimitError::InvalidRateLimitConfig.into())
+        .and_then(|cfg: &mut RateLimitConfig| {
+            if cfg.discriminator == RateLimitConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(RateLimitError::InvalidRateLimitConfig.into())
+            }
+        })
+}
+
+#[inline(always)]
+pub fn load_caller_counter_mut(data: &mut [u8]) -> Result<&mut CallerCounter, ProgramError> {
+    bytemuck::try_from_bytes_mut::<CallerCounter>(data)
+        .map_err(|_| RateLimitError::InvalidCallerCounter.into())
+        .and_then(|counter: &mut CallerCounter| {
+            if counter.discriminator == CallerCounter::DISCRIMINATOR {
+                Ok(counter)
+            } else {
+                Err(RateLimitError::InvalidCallerCounter.into())
+            }
+        })
+}
@@ -0,0 +1,16 @@
+use solana_program_error::ProgramError;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimeWindowError {
+    InvalidWindowConfig,
+    InvalidWindow,
+    WindowNotOpen,
+    WindowClosed,
+    WindowNotClosed,
+}
+
+impl From<TimeWindowError> for ProgramError {
+    fn from(e: TimeWindowError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
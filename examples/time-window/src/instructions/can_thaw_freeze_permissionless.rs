@@ -0,0 +1,63 @@
+use solana_program::{account_info::AccountInfo, clock::Clock, sysvar::Sysvar};
+use solana_program_error::{ProgramError, ProgramResult};
+use token_acl_interface::gate::GateAccounts;
+
+use crate::{
+    error::TimeWindowError,
+    state::{load_window_config, WindowUnit},
+};
+
+pub struct CanThawFreezePermissionless<'a> {
+    pub gate: GateAccounts<'a>,
+    pub window_config: &'a AccountInfo<'a>,
+}
+
+impl<'a> CanThawFreezePermissionless<'a> {
+    pub fn from_gate_accounts(gate: GateAccounts<'a>) -> Result<Self, ProgramError> {
+        let [window_config, ..] = gate.extra_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            gate,
+            window_config,
+        })
+    }
+
+    /// Returns the current `Clock` value in the unit of the window, along with
+    /// the window bounds.
+    fn window(&self) -> Result<(i64, i64, i64), ProgramError> {
+        if self.window_config.owner != &crate::ID {
+            return Err(TimeWindowError::InvalidWindowConfig.into());
+        }
+        let data = self.window_config.try_borrow_data()?;
+        let config = load_window_config(&data)?;
+        if &config.mint != self.gate.mint.key {
+            return Err(TimeWindowError::InvalidWindowConfig.into());
+        }
+
+        let now = WindowUnit::try_from(config.unit)?.now(&Clock::get()?);
+        Ok((now, config.start.into(), config.end.into()))
+    }
+
+    /// Thawing is allowed from the start of the window until it closes.
+    pub fn process_thaw(&self) -> ProgramResult {
+        let (now, start, end) = self.window()?;
+        if now < start {
+            return Err(TimeWindowError::WindowNotOpen.into());
+        }
+        if now >= end {
+            return Err(TimeWindowError::WindowClosed.into());
+        }
+        Ok(())
+    }
+
+    /// Freezing is allowed once the window has closed.
+    pub fn process_freeze(&self) -> ProgramResult {
+        let (now, _, end) = self.window()?;
+        if now < end {
+            return Err(TimeWindowError::WindowNotClosed.into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::{check_token_acl_authority, create_pda_account};
+
+use crate::{
+    error::TimeWindowError,
+    state::{WindowConfig, WindowUnit},
+};
+
+/// Sets the thaw window of a mint. Only the token-acl authority of the mint
+/// can create the config.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("time-window-gate:create-config")]
+pub struct CreateConfig<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub window_config: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub window_config_bump: u8,
+}
+
+impl CreateConfig<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let (unit, start, end) = unpack_window(remaining_data)?;
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        let bump_seed = [self.window_config_bump];
+        create_pda_account(
+            self.payer,
+            self.window_config,
+            WindowConfig::LEN,
+            &crate::ID,
+            &[
+                WindowConfig::SEED_PREFIX,
+                self.mint.key.as_ref(),
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.window_config.try_borrow_mut_data()?;
+        let config = bytemuck::from_bytes_mut::<WindowConfig>(&mut data);
+        config.discriminator = WindowConfig::DISCRIMINATOR;
+        config.bump = self.window_config_bump;
+        config.unit = unit as u8;
+        config.mint = *self.mint.key;
+        config.start = start.into();
+        config.end = end.into();
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, window_config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let (window_config_address, window_config_bump) = WindowConfig::find_pda(mint.key);
+        if window_config.key != &window_config_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            window_config,
+            system_program,
+            window_config_bump,
+        })
+    }
+}
+
+/// Length of an encoded window: `unit || start || end`.
+const WINDOW_LEN: usize = 1 + 8 + 8;
+
+/// Reads `unit || start || end` and checks that the window is not empty.
+pub(crate) fn unpack_window(data: &[u8]) -> Result<(WindowUnit, i64, i64), ProgramError> {
+    if data.len() != WINDOW_LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let unit = WindowUnit::try_from(data[0])?;
+    let start = i64::from_le_bytes(data[1..9].try_into().unwrap());
+    let end = i64::from_le_bytes(data[9..17].try_into().unwrap());
+
+    if start >= end {
+        return Err(TimeWindowError::InvalidWindow.into());
+    }
+
+    Ok((unit, start, end))
+}
+
+pub(crate) fn pack_window(data: &mut Vec<u8>, unit: WindowUnit, start: i64, end: i64) {
+    data.push(unit as u8);
+    data.extend_from_slice(&start.to_le_bytes());
+    data.extend_from_slice(&end.to_le_bytes());
+}
+
+pub fn create_config(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    unit: WindowUnit,
+    start: i64,
+    end: i64,
+) -> Instruction {
+    let mut data = CreateConfig::SPL_DISCRIMINATOR_SLICE.to_vec();
+    pack_window(&mut data, unit, start, end);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(WindowConfig::find_pda(mint).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
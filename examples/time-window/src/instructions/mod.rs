@@ -0,0 +1,7 @@
+mod can_thaw_freeze_permissionless;
+mod create_config;
+mod set_window;
+
+pub use can_thaw_freeze_permissionless::*;
+pub use create_config::*;
+pub use set_window::*;
@@ -0,0 +1,87 @@
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_program_error::{ProgramError, ProgramResult};
+use spl_discriminator::SplDiscriminate;
+use token_acl_gate_framework::check_token_acl_authority;
+
+use crate::{
+    error::TimeWindowError,
+    instructions::{pack_window, unpack_window},
+    state::{load_window_config, WindowConfig, WindowUnit},
+};
+
+/// Moves the thaw window of a mint, e.g. to extend a sale or change a lockup.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("time-window-gate:set-window")]
+pub struct SetWindow<'a> {
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub window_config: &'a AccountInfo<'a>,
+}
+
+impl SetWindow<'_> {
+    pub fn process(&self, remaining_data: &[u8]) -> ProgramResult {
+        let (unit, start, end) = unpack_window(remaining_data)?;
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        if self.window_config.owner != &crate::ID {
+            return Err(TimeWindowError::InvalidWindowConfig.into());
+        }
+        let mut data = self.window_config.try_borrow_mut_data()?;
+        if &load_window_config(&data)?.mint != self.mint.key {
+            return Err(TimeWindowError::InvalidWindowConfig.into());
+        }
+
+        let config = bytemuck::from_bytes_mut::<WindowConfig>(&mut data);
+        config.unit = unit as u8;
+        config.start = start.into();
+        config.end = end.into();
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for SetWindow<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [authority, mint, mint_config, window_config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            authority,
+            mint,
+            mint_config,
+            window_config,
+        })
+    }
+}
+
+pub fn set_window(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    unit: WindowUnit,
+    start: i64,
+    end: i64,
+) -> Instruction {
+    let mut data = SetWindow::SPL_DISCRIMINATOR_SLICE.to_vec();
+    pack_window(&mut data, unit, start, end);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(WindowConfig::find_pda(mint).0, false),
+        ],
+        data,
+    }
+}
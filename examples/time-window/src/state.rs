@@ -0,0 +1,72 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::{clock::Clock, pubkey::Pubkey};
+use solana_program_error::ProgramError;
+use spl_pod::primitives::PodI64;
+
+use crate::error::TimeWindowError;
+
+/// Clock field a window is measured in.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WindowUnit {
+    UnixTimestamp = 0,
+    Slot = 1,
+}
+
+impl WindowUnit {
+    pub fn now(self, clock: &Clock) -> i64 {
+        match self {
+            WindowUnit::UnixTimestamp => clock.unix_timestamp,
+            WindowUnit::Slot => clock.slot as i64,
+        }
+    }
+}
+
+impl TryFrom<u8> for WindowUnit {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(WindowUnit::UnixTimestamp),
+            1 => Ok(WindowUnit::Slot),
+            _ => Err(TimeWindowError::InvalidWindow.into()),
+        }
+    }
+}
+
+/// Per-mint window during which token accounts can be thawed: from `start`
+/// (inclusive) to `end` (exclusive), both in `unit`. An `end` of `i64::MAX`
+/// leaves the window open forever, e.g. for a lockup that only ends.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct WindowConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub unit: u8,
+    pub mint: Pubkey,
+    pub start: PodI64,
+    pub end: PodI64,
+}
+
+impl WindowConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"time_window";
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 1 + 32 + 8 + 8;
+
+    pub fn find_pda(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &crate::ID)
+    }
+}
+
+#[inline(always)]
+pub fn load_window_config(data: &[u8]) -> Result<&WindowConfig, ProgramError> {
+    bytemuck::try_from_bytes::<WindowConfig>(data)
+        .map_err(|_| TimeWindowError::InvalidWindowConfig.into())
+        .and_then(|cfg: &WindowConfig| {
+            if cfg.discriminator == WindowConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(TimeWindowError::InvalidWindowConfig.into())
+            }
+        })
+}
@@ -0,0 +1,60 @@
+use solana_account_info::AccountInfo;
+use solana_cpi::{invoke, invoke_signed};
+use solana_program_error::ProgramResult;
+use solana_pubkey::Pubkey;
+use solana_rent::Rent;
+use solana_sysvar::Sysvar;
+
+/// Creates a rent-exempt PDA owned by `owner`.
+///
+/// Unlike a plain `create_account`, this also works when the address was
+/// already funded by someone else, so a PDA can't be griefed by a transfer.
+pub fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    if account.lamports() == 0 {
+        let ix = solana_system_interface::instruction::create_account(
+            payer.key,
+            account.key,
+            lamports,
+            space as u64,
+            owner,
+        );
+        return invoke_signed(&ix, &[payer.clone(), account.clone()], &[signer_seeds]);
+    }
+
+    if account.lamports() < lamports {
+        let ix = solana_system_interface::instruction::transfer(
+            payer.key,
+            account.key,
+            lamports - account.lamports(),
+        );
+        invoke(&ix, &[payer.clone(), account.clone()])?;
+    }
+
+    let ix = solana_system_interface::instruction::allocate(account.key, space as u64);
+    invoke_signed(&ix, std::slice::from_ref(account), &[signer_seeds])?;
+
+    let ix = solana_system_interface::instruction::assign(account.key, owner);
+    invoke_signed(&ix, std::slice::from_ref(account), &[signer_seeds])
+}
+
+/// Closes a program owned account, moving its lamports to `destination`.
+pub fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(solana_program_error::ProgramError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.resize(0)?;
+    account.assign(&solana_system_interface::program::ID);
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use solana_account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use solana_pubkey::Pubkey;
+use token_acl_interface::{get_mint_config_address, TOKEN_ACL_ID};
+
+use crate::GateError;
+
+const MINT_CONFIG_DISCRIMINATOR: u8 = 1;
+const MINT_CONFIG_LEN: usize = 100;
+const MINT_CONFIG_MINT_OFFSET: usize = 4;
+const MINT_CONFIG_AUTHORITY_OFFSET: usize = 36;
+
+/// Checks that `authority` signed and is the token-acl authority of `mint`,
+/// as recorded in its token-acl `MintConfig`.
+///
+/// Gates use this to let the issuer, and only the issuer, create per-mint state.
+pub fn check_token_acl_authority(
+    mint_config: &AccountInfo,
+    mint: &Pubkey,
+    authority: &AccountInfo,
+) -> ProgramResult {
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if mint_config.owner != &TOKEN_ACL_ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if mint_config.key != &get_mint_config_address(mint) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let data = mint_config.try_borrow_data()?;
+    if data.len() != MINT_CONFIG_LEN || data[0] != MINT_CONFIG_DISCRIMINATOR {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if &data[MINT_CONFIG_MINT_OFFSET..MINT_CONFIG_MINT_OFFSET + 32] != mint.as_ref() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if &data[MINT_CONFIG_AUTHORITY_OFFSET..MINT_CONFIG_AUTHORITY_OFFSET + 32]
+        != authority.key.as_ref()
+    {
+        return Err(GateError::InvalidAuthority.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use solana_program_error::ProgramError;
+use token_acl_interface::error::{GATE_INVALID_AUTHORITY, GATE_UNSUPPORTED_INSTRUCTION};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GateError {
+    /// The gate does not support the requested permissionless operation.
+    UnsupportedInstruction = GATE_UNSUPPORTED_INSTRUCTION as isize,
+    /// The signer is not the token-acl authority of the mint.
+    InvalidAuthority = GATE_INVALID_AUTHORITY as isize,
+}
+
+impl From<GateError> for ProgramError {
+    fn from(e: GateError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
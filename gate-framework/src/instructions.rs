@@ -0,0 +1,248 @@
+use solana_account_info::AccountInfo;
+use solana_cpi::invoke;
+use solana_program_error::{ProgramError, ProgramResult};
+use solana_pubkey::Pubkey;
+use solana_rent::Rent;
+use solana_sysvar::Sysvar;
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use token_acl_interface::{
+    collect_freeze_extra_account_metas, collect_thaw_extra_account_metas,
+    instruction::{
+        CanFreezePermissionlessInstruction, CanThawPermissionlessInstruction,
+        EfficientBlockAllowInstruction, InitializeExtraAccountMetasInstruction,
+        UpdateExtraAccountMetasInstruction,
+    },
+};
+
+use crate::{check_token_acl_authority, create_pda_account, GateProgram};
+
+/// Creates the thaw and freeze extra metas accounts for a mint.
+///
+/// Implements the standard `InitializeExtraAccountMetas` instruction, see
+/// [`ExtraAccountMetasInstruction`](token_acl_interface::instruction::ExtraAccountMetasInstruction)
+/// for the account layout. Only the token-acl authority of the mint may call
+/// it, and the accounts after the authority are forwarded to
+/// [`GateProgram::extra_metas`].
+pub struct InitializeExtraMetas<'a> {
+    pub program_id: &'a Pubkey,
+    pub payer: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub thaw_extra_metas: &'a AccountInfo<'a>,
+    pub freeze_extra_metas: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub remaining_accounts: &'a [AccountInfo<'a>],
+    pub thaw_bump: u8,
+    pub freeze_bump: u8,
+}
+
+impl<'a> InitializeExtraMetas<'a> {
+    pub const DISCRIMINATOR_SLICE: &'static [u8] =
+        InitializeExtraAccountMetasInstruction::SPL_DISCRIMINATOR_SLICE;
+
+    pub fn try_from_accounts(
+        program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+    ) -> Result<Self, ProgramError> {
+        let [payer, mint, thaw_extra_metas, freeze_extra_metas, system_program, mint_config, authority, remaining_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        check_token_acl_authority(mint_config, mint.key, authority)?;
+
+        let thaw_bump = check_extra_metas_address(
+            thaw_extra_metas,
+            &collect_thaw_extra_account_metas(mint.key),
+            program_id,
+        )?;
+        let freeze_bump = check_extra_metas_address(
+            freeze_extra_metas,
+            &collect_freeze_extra_account_metas(mint.key),
+            program_id,
+        )?;
+
+        Ok(Self {
+            program_id,
+            payer,
+            mint,
+            thaw_extra_metas,
+            freeze_extra_metas,
+            system_program,
+            mint_config,
+            authority,
+            remaining_accounts,
+            thaw_bump,
+            freeze_bump,
+        })
+    }
+
+    pub fn process<G: GateProgram>(&self) -> ProgramResult {
+        self.create::<G, CanThawPermissionlessInstruction>(
+            self.thaw_extra_metas,
+            EfficientBlockAllowInstruction::CanThawPermissionless,
+            &collect_thaw_extra_account_metas(self.mint.key),
+            self.thaw_bump,
+        )?;
+        self.create::<G, CanFreezePermissionlessInstruction>(
+            self.freeze_extra_metas,
+            EfficientBlockAllowInstruction::CanFreezePermissionless,
+            &collect_freeze_extra_account_metas(self.mint.key),
+            self.freeze_bump,
+        )
+    }
+
+    fn create<G: GateProgram, T: SplDiscriminate>(
+        &self,
+        extra_metas: &'a AccountInfo<'a>,
+        instruction: EfficientBlockAllowInstruction,
+        seeds: &[&[u8]],
+        bump: u8,
+    ) -> ProgramResult {
+        let metas = G::extra_metas(
+            self.program_id,
+            self.mint.key,
+            instruction,
+            self.remaining_accounts,
+        )?;
+        let size = ExtraAccountMetaList::size_of(metas.len())?;
+
+        let bump_seed = [bump];
+        create_pda_account(
+            self.payer,
+            extra_metas,
+            size,
+            self.program_id,
+            &[seeds[0], seeds[1], &bump_seed],
+        )?;
+
+        ExtraAccountMetaList::init::<T>(&mut extra_metas.try_borrow_mut_data()?, &metas)
+    }
+}
+
+/// Rewrites the thaw and freeze extra metas accounts of a mint with the
+/// current output of [`GateProgram::extra_metas`], resizing them as needed.
+///
+/// Implements the standard `UpdateExtraAccountMetas` instruction and takes the
+/// same accounts as [`InitializeExtraMetas`], so only the token-acl authority
+/// of the mint may call it. The payer tops up rent when an account grows;
+/// lamports are kept when it shrinks.
+pub struct UpdateExtraMetas<'a> {
+    pub program_id: &'a Pubkey,
+    pub payer: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub thaw_extra_metas: &'a AccountInfo<'a>,
+    pub freeze_extra_metas: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub remaining_accounts: &'a [AccountInfo<'a>],
+}
+
+impl<'a> UpdateExtraMetas<'a> {
+    pub const DISCRIMINATOR_SLICE: &'static [u8] =
+        UpdateExtraAccountMetasInstruction::SPL_DISCRIMINATOR_SLICE;
+
+    pub fn try_from_accounts(
+        program_id: &'a Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+    ) -> Result<Self, ProgramError> {
+        let [payer, mint, thaw_extra_metas, freeze_extra_metas, system_program, mint_config, authority, remaining_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        check_token_acl_authority(mint_config, mint.key, authority)?;
+
+        check_extra_metas_address(
+            thaw_extra_metas,
+            &collect_thaw_extra_account_metas(mint.key),
+            program_id,
+        )?;
+        check_extra_metas_address(
+            freeze_extra_metas,
+            &collect_freeze_extra_account_metas(mint.key),
+            program_id,
+        )?;
+
+        if thaw_extra_metas.owner != program_id || freeze_extra_metas.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(Self {
+            program_id,
+            payer,
+            mint,
+            thaw_extra_metas,
+            freeze_extra_metas,
+            system_program,
+            mint_config,
+            authority,
+            remaining_accounts,
+        })
+    }
+
+    pub fn process<G: GateProgram>(&self) -> ProgramResult {
+        self.update::<G, CanThawPermissionlessInstruction>(
+            self.thaw_extra_metas,
+            EfficientBlockAllowInstruction::CanThawPermissionless,
+        )?;
+        self.update::<G, CanFreezePermissionlessInstruction>(
+            self.freeze_extra_metas,
+            EfficientBlockAllowInstruction::CanFreezePermissionless,
+        )
+    }
+
+    fn update<G: GateProgram, T: SplDiscriminate>(
+        &self,
+        extra_metas: &'a AccountInfo<'a>,
+        instruction: EfficientBlockAllowInstruction,
+    ) -> ProgramResult {
+        let metas = G::extra_metas(
+            self.program_id,
+            self.mint.key,
+            instruction,
+            self.remaining_accounts,
+        )?;
+        let size = ExtraAccountMetaList::size_of(metas.len())?;
+        let original_size = extra_metas.data_len();
+
+        let lamports = Rent::get()?.minimum_balance(size);
+        let missing_lamports = lamports.saturating_sub(extra_metas.lamports());
+        if missing_lamports > 0 {
+            let ix = solana_system_interface::instruction::transfer(
+                self.payer.key,
+                extra_metas.key,
+                missing_lamports,
+            );
+            invoke(&ix, &[self.payer.clone(), extra_metas.clone()])?;
+        }
+
+        // grow before writing, shrink after, so the list always fits the buffer
+        if size > original_size {
+            extra_metas.resize(size)?;
+        }
+        ExtraAccountMetaList::update::<T>(&mut extra_metas.try_borrow_mut_data()?, &metas)?;
+        if size < original_size {
+            extra_metas.resize(size)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_extra_metas_address(
+    extra_metas: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+    if extra_metas.key != &address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(bump)
+}
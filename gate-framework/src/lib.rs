@@ -0,0 +1,124 @@
+use solana_account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use solana_pubkey::Pubkey;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::account::ExtraAccountMeta;
+use token_acl_interface::{
+    gate::GateAccounts,
+    instruction::{
+        CanFreezePermissionlessInstruction, CanThawPermissionlessInstruction,
+        EfficientBlockAllowInstruction,
+    },
+};
+
+pub mod account;
+pub mod authority;
+pub mod error;
+pub mod instructions;
+pub mod wallet_list;
+
+pub use account::{close_account, create_pda_account};
+pub use authority::check_token_acl_authority;
+pub use error::GateError;
+pub use instructions::*;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use {
+        solana_account_info::AccountInfo, solana_program_entrypoint,
+        solana_program_error::ProgramResult, solana_pubkey::Pubkey,
+    };
+}
+
+/// Gate program logic, plugged into the generated entrypoint by [`gate_program!`].
+///
+/// `can_thaw` and `can_freeze` receive accounts that already passed the
+/// [`GateAccounts`] checks. Operations that are not overridden are rejected
+/// with [`GateError::UnsupportedInstruction`].
+pub trait GateProgram {
+    fn can_thaw(_program_id: &Pubkey, _accounts: GateAccounts) -> ProgramResult {
+        Err(GateError::UnsupportedInstruction.into())
+    }
+
+    fn can_freeze(_program_id: &Pubkey, _accounts: GateAccounts) -> ProgramResult {
+        Err(GateError::UnsupportedInstruction.into())
+    }
+
+    /// Extra account metas stored for `instruction` on `mint`.
+    ///
+    /// `accounts` are the accounts passed after the fixed accounts of
+    /// [`InitializeExtraMetas`] and [`UpdateExtraMetas`], for gates whose
+    /// dependencies are read from their own state.
+    fn extra_metas(
+        _program_id: &Pubkey,
+        _mint: &Pubkey,
+        _instruction: EfficientBlockAllowInstruction,
+        _accounts: &[AccountInfo],
+    ) -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+        Ok(Vec::new())
+    }
+
+    /// Handles any instruction the framework does not route itself.
+    fn process_instruction<'a>(
+        _program_id: &Pubkey,
+        _accounts: &'a [AccountInfo<'a>],
+        _instruction_data: &[u8],
+    ) -> ProgramResult {
+        Err(ProgramError::InvalidInstructionData)
+    }
+}
+
+pub fn process_instruction<'a, G: GateProgram>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let Some((discriminator, _remaining)) =
+        instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+    else {
+        return G::process_instruction(program_id, accounts, instruction_data);
+    };
+
+    match discriminator {
+        CanThawPermissionlessInstruction::SPL_DISCRIMINATOR_SLICE => G::can_thaw(
+            program_id,
+            GateAccounts::load_can_thaw(program_id, accounts)?,
+        ),
+        CanFreezePermissionlessInstruction::SPL_DISCRIMINATOR_SLICE => G::can_freeze(
+            program_id,
+            GateAccounts::load_can_freeze(program_id, accounts)?,
+        ),
+        InitializeExtraMetas::DISCRIMINATOR_SLICE => {
+            InitializeExtraMetas::try_from_accounts(program_id, accounts)?.process::<G>()
+        }
+        UpdateExtraMetas::DISCRIMINATOR_SLICE => {
+            UpdateExtraMetas::try_from_accounts(program_id, accounts)?.process::<G>()
+        }
+        _ => G::process_instruction(program_id, accounts, instruction_data),
+    }
+}
+
+/// Declares the program entrypoint and routes instructions to a [`GateProgram`].
+///
+/// ```ignore
+/// struct MyGate;
+/// impl token_acl_gate_framework::GateProgram for MyGate {}
+/// token_acl_gate_framework::gate_program!(MyGate);
+/// ```
+///
+/// The entrypoint is left out when the `no-entrypoint` feature is enabled.
+#[macro_export]
+macro_rules! gate_program {
+    ($gate:ty) => {
+        #[cfg(not(feature = "no-entrypoint"))]
+        $crate::__private::solana_program_entrypoint::entrypoint!(process_instruction);
+
+        pub fn process_instruction<'a>(
+            program_id: &'a $crate::__private::Pubkey,
+            accounts: &'a [$crate::__private::AccountInfo<'a>],
+            instruction_data: &'a [u8],
+        ) -> $crate::__private::ProgramResult {
+            $crate::process_instruction::<$gate>(program_id, accounts, instruction_data)
+        }
+    };
+}
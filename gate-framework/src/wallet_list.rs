@@ -0,0 +1,426 @@
+use bytemuck::{Pod, Zeroable};
+use solana_account_info::AccountInfo;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_program_error::{ProgramError, ProgramResult};
+use solana_pubkey::Pubkey;
+use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+
+use crate::{check_token_acl_authority, close_account, create_pda_account};
+
+/// Per-mint list of wallets kept by a gate, such as an allow or block list.
+///
+/// A wallet is on the list iff its [`ListEntry`] exists. The framework manages
+/// the list accounts, the gate only decides what being listed means.
+pub trait WalletList {
+    /// Program owning the list accounts.
+    const PROGRAM_ID: Pubkey;
+    /// Seed prefix of the [`ListConfig`] PDA.
+    const LIST_SEED_PREFIX: &'static [u8];
+    /// Seed prefix of the [`ListEntry`] PDAs.
+    const ENTRY_SEED_PREFIX: &'static [u8];
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WalletListError {
+    InvalidListConfig,
+    InvalidListEntry,
+    InvalidListAuthority,
+}
+
+impl From<WalletListError> for ProgramError {
+    fn from(e: WalletListError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+/// Per-mint wallet list, holding the authority that manages its entries.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ListConfig {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+}
+
+impl ListConfig {
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const LEN: usize = 1 + 1 + 32 + 32;
+
+    pub fn find_pda<L: WalletList>(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[L::LIST_SEED_PREFIX, mint.as_ref()], &L::PROGRAM_ID)
+    }
+}
+
+/// Puts `wallet` on the list of `mint`.
+///
+/// Entries are resolved by token-acl from the owner of the token account being
+/// thawed or frozen, see [`list_entry_extra_meta`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct ListEntry {
+    pub discriminator: u8,
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+}
+
+impl ListEntry {
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const LEN: usize = 1 + 1 + 32 + 32;
+
+    pub fn find_pda<L: WalletList>(mint: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[L::ENTRY_SEED_PREFIX, mint.as_ref(), wallet.as_ref()],
+            &L::PROGRAM_ID,
+        )
+    }
+}
+
+#[inline(always)]
+pub fn load_list_config(data: &[u8]) -> Result<&ListConfig, ProgramError> {
+    bytemuck::try_from_bytes::<ListConfig>(data)
+        .map_err(|_| WalletListError::InvalidListConfig.into())
+        .and_then(|cfg: &ListConfig| {
+            if cfg.discriminator == ListConfig::DISCRIMINATOR {
+                Ok(cfg)
+            } else {
+                Err(WalletListError::InvalidListConfig.into())
+            }
+        })
+}
+
+#[inline(always)]
+pub fn load_list_entry(data: &[u8]) -> Result<&ListEntry, ProgramError> {
+    bytemuck::try_from_bytes::<ListEntry>(data)
+        .map_err(|_| WalletListError::InvalidListEntry.into())
+        .and_then(|entry: &ListEntry| {
+            if entry.discriminator == ListEntry::DISCRIMINATOR {
+                Ok(entry)
+            } else {
+                Err(WalletListError::InvalidListEntry.into())
+            }
+        })
+}
+
+/// Extra meta resolving the [`ListEntry`] of the token account owner.
+pub fn list_entry_extra_meta<L: WalletList>() -> Result<ExtraAccountMeta, ProgramError> {
+    ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal {
+                bytes: L::ENTRY_SEED_PREFIX.to_vec(),
+            },
+            Seed::AccountKey { index: 2 }, // mint
+            Seed::AccountKey { index: 3 }, // token account owner
+        ],
+        false,
+        false,
+    )
+}
+
+/// Whether `list_entry` is a live entry of `wallet` on the list of `mint`.
+///
+/// token-acl derives the entry address from the extra metas before calling
+/// the gate, so a missing or closed entry means the wallet is not listed.
+pub fn is_listed<L: WalletList>(
+    list_entry: &AccountInfo,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+) -> Result<bool, ProgramError> {
+    if list_entry.owner != &L::PROGRAM_ID || list_entry.data_is_empty() {
+        return Ok(false);
+    }
+
+    let data = list_entry.try_borrow_data()?;
+    let entry = load_list_entry(&data)?;
+    Ok(&entry.mint == mint && &entry.wallet == wallet)
+}
+
+/// Routes the list management instructions of `L`.
+pub fn process_wallet_list_instruction<'a, L: WalletList>(
+    accounts: &'a [AccountInfo<'a>],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let Some((discriminator, remaining_data)) =
+        instruction_data.split_at_checked(ArrayDiscriminator::LENGTH)
+    else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+
+    match discriminator {
+        CreateList::SPL_DISCRIMINATOR_SLICE => {
+            CreateList::try_from(accounts)?.process::<L>(remaining_data)
+        }
+        AddWallet::SPL_DISCRIMINATOR_SLICE => {
+            AddWallet::try_from(accounts)?.process::<L>(remaining_data)
+        }
+        RemoveWallet::SPL_DISCRIMINATOR_SLICE => {
+            RemoveWallet::try_from(accounts)?.process::<L>(remaining_data)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Creates the list of a mint. Only the token-acl authority of the mint
+/// can create it, and it hands list management over to `list_authority`.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("token-acl-gate:create-list")]
+pub struct CreateList<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub authority: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub mint_config: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+}
+
+impl CreateList<'_> {
+    pub fn process<L: WalletList>(&self, remaining_data: &[u8]) -> ProgramResult {
+        let list_authority =
+            Pubkey::try_from(remaining_data).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        check_token_acl_authority(self.mint_config, self.mint.key, self.authority)?;
+
+        let (list_config_address, bump) = ListConfig::find_pda::<L>(self.mint.key);
+        if self.list_config.key != &list_config_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let bump_seed = [bump];
+        create_pda_account(
+            self.payer,
+            self.list_config,
+            ListConfig::LEN,
+            &L::PROGRAM_ID,
+            &[L::LIST_SEED_PREFIX, self.mint.key.as_ref(), &bump_seed],
+        )?;
+
+        let mut data = self.list_config.try_borrow_mut_data()?;
+        let list_config = bytemuck::from_bytes_mut::<ListConfig>(&mut data);
+        list_config.discriminator = ListConfig::DISCRIMINATOR;
+        list_config.bump = bump;
+        list_config.mint = *self.mint.key;
+        list_config.authority = list_authority;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for CreateList<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, authority, mint, mint_config, list_config, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            payer,
+            authority,
+            mint,
+            mint_config,
+            list_config,
+            system_program,
+        })
+    }
+}
+
+/// Adds a wallet to the list by creating its entry.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("token-acl-gate:add-wallet")]
+pub struct AddWallet<'a> {
+    pub payer: &'a AccountInfo<'a>,
+    pub list_authority: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    pub list_entry: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+}
+
+impl AddWallet<'_> {
+    pub fn process<L: WalletList>(&self, remaining_data: &[u8]) -> ProgramResult {
+        let wallet =
+            Pubkey::try_from(remaining_data).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        let mint = check_list_authority::<L>(self.list_config, self.list_authority)?;
+
+        let (list_entry_address, bump) = ListEntry::find_pda::<L>(&mint, &wallet);
+        if self.list_entry.key != &list_entry_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let bump_seed = [bump];
+        create_pda_account(
+            self.payer,
+            self.list_entry,
+            ListEntry::LEN,
+            &L::PROGRAM_ID,
+            &[
+                L::ENTRY_SEED_PREFIX,
+                mint.as_ref(),
+                wallet.as_ref(),
+                &bump_seed,
+            ],
+        )?;
+
+        let mut data = self.list_entry.try_borrow_mut_data()?;
+        let list_entry = bytemuck::from_bytes_mut::<ListEntry>(&mut data);
+        list_entry.discriminator = ListEntry::DISCRIMINATOR;
+        list_entry.bump = bump;
+        list_entry.mint = mint;
+        list_entry.wallet = wallet;
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for AddWallet<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [payer, list_authority, list_config, list_entry, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            payer,
+            list_authority,
+            list_config,
+            list_entry,
+            system_program,
+        })
+    }
+}
+
+/// Removes a wallet from the list by closing its entry.
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("token-acl-gate:remove-wallet")]
+pub struct RemoveWallet<'a> {
+    pub list_authority: &'a AccountInfo<'a>,
+    pub list_config: &'a AccountInfo<'a>,
+    pub list_entry: &'a AccountInfo<'a>,
+    pub destination: &'a AccountInfo<'a>,
+}
+
+impl RemoveWallet<'_> {
+    pub fn process<L: WalletList>(&self, remaining_data: &[u8]) -> ProgramResult {
+        let wallet =
+            Pubkey::try_from(remaining_data).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        let mint = check_list_authority::<L>(self.list_config, self.list_authority)?;
+
+        let (list_entry_address, _) = ListEntry::find_pda::<L>(&mint, &wallet);
+        if self.list_entry.key != &list_entry_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        if self.list_entry.owner != &L::PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        close_account(self.list_entry, self.destination)
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for RemoveWallet<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [list_authority, list_config, list_entry, destination] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            list_authority,
+            list_config,
+            list_entry,
+            destination,
+        })
+    }
+}
+
+/// Checks that `list_authority` signed and manages the list, returning the list's mint.
+fn check_list_authority<L: WalletList>(
+    list_config: &AccountInfo,
+    list_authority: &AccountInfo,
+) -> Result<Pubkey, ProgramError> {
+    if list_config.owner != &L::PROGRAM_ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = list_config.try_borrow_data()?;
+    let config = load_list_config(&data)?;
+
+    if !list_authority.is_signer || &config.authority != list_authority.key {
+        return Err(WalletListError::InvalidListAuthority.into());
+    }
+
+    Ok(config.mint)
+}
+
+pub fn create_list<L: WalletList>(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    mint_config: &Pubkey,
+    list_authority: &Pubkey,
+) -> Instruction {
+    let mut data = CreateList::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(list_authority.as_ref());
+
+    Instruction {
+        program_id: L::PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_config, false),
+            AccountMeta::new(ListConfig::find_pda::<L>(mint).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn add_wallet<L: WalletList>(
+    payer: &Pubkey,
+    list_authority: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+) -> Instruction {
+    let mut data = AddWallet::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(wallet.as_ref());
+
+    Instruction {
+        program_id: L::PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*list_authority, true),
+            AccountMeta::new_readonly(ListConfig::find_pda::<L>(mint).0, false),
+            AccountMeta::new(ListEntry::find_pda::<L>(mint, wallet).0, false),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn remove_wallet<L: WalletList>(
+    list_authority: &Pubkey,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    let mut data = RemoveWallet::SPL_DISCRIMINATOR_SLICE.to_vec();
+    data.extend_from_slice(wallet.as_ref());
+
+    Instruction {
+        program_id: L::PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*list_authority, true),
+            AccountMeta::new_readonly(ListConfig::find_pda::<L>(mint).0, false),
+            AccountMeta::new(ListEntry::find_pda::<L>(mint, wallet).0, false),
+            AccountMeta::new(*destination, false),
+        ],
+        data,
+    }
+}
@@ -1,46 +1,329 @@
+use std::{fmt, sync::Arc};
+
 use num_traits::FromPrimitive;
 use solana_program_error::ProgramError;
-use spl_tlv_account_resolution::error::AccountResolutionError;
+use solana_pubkey::Pubkey;
+use spl_tlv_account_resolution::{error::AccountResolutionError, state::AccountFetchError};
+
+/// Error code gates return for a permissionless operation they do not support.
+pub const GATE_UNSUPPORTED_INSTRUCTION: u32 = 999_999_999;
+/// Error code gates return when the signer is not the token-acl authority of the mint.
+pub const GATE_INVALID_AUTHORITY: u32 = 999_999_998;
+
+/// Reason a gate rejected an operation, decoded from its custom error code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GateRejection {
+    UnsupportedInstruction,
+    InvalidAuthority,
+    /// Code specific to the gate program, see its error enum.
+    Custom(u32),
+}
+
+impl GateRejection {
+    pub fn code(self) -> u32 {
+        match self {
+            GateRejection::UnsupportedInstruction => GATE_UNSUPPORTED_INSTRUCTION,
+            GateRejection::InvalidAuthority => GATE_INVALID_AUTHORITY,
+            GateRejection::Custom(code) => code,
+        }
+    }
+}
+
+impl From<u32> for GateRejection {
+    fn from(code: u32) -> Self {
+        match code {
+            GATE_UNSUPPORTED_INSTRUCTION => GateRejection::UnsupportedInstruction,
+            GATE_INVALID_AUTHORITY => GateRejection::InvalidAuthority,
+            code => GateRejection::Custom(code),
+        }
+    }
+}
+
+impl fmt::Display for GateRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateRejection::UnsupportedInstruction => {
+                write!(f, "the gate does not support this operation")
+            }
+            GateRejection::InvalidAuthority => {
+                write!(f, "the signer is not the token-acl authority of the mint")
+            }
+            GateRejection::Custom(code) => write!(f, "gate error code {code} ({code:#x})"),
+        }
+    }
+}
 
-#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+/// Errors from resolving the accounts of a permissionless thaw or freeze.
+///
+/// Converts losslessly from [`AccountFetchError`], and back through
+/// [`ThawFreezeGateError::into_account_fetch_error`] or the blanket boxing
+/// conversion used by `?`: an error that went through both conversions is
+/// the one it started as.
+#[derive(Clone, Debug, thiserror::Error)]
 pub enum ThawFreezeGateError {
-    /// Incorrect account provided
-    //#[error("Incorrect account provided")]
+    #[error("incorrect account provided")]
     IncorrectAccount,
 
-    //#[error("Missing AccountMeta in instruction")]
+    #[error("instruction is missing an account needed to resolve the extra metas")]
     MissingAccountMeta,
 
-    //#[error("ExtraAccountMeta not found or empty")]
-    MissingExtraAccountMeta,
+    #[error("extra metas account {0} not found")]
+    MissingExtraAccountMeta(Pubkey),
+
+    #[error("failed to resolve the extra metas: {0}")]
+    ResolutionError(AccountResolutionError),
+
+    #[error("gate rejected the operation: {0}")]
+    GateRejected(GateRejection),
+
+    /// Custom code of a program the error was not attributed to, token-acl or
+    /// the gate.
+    #[error("program error code {0} ({0:#x})")]
+    UnknownProgramError(u32),
 
-    //#[error("Resolution error")]
-    ResolutionError(spl_tlv_account_resolution::error::AccountResolutionError),
+    #[error("failed to fetch account data: {0}")]
+    AccountFetch(Arc<AccountFetchError>),
 
+    #[error("program error: {0}")]
     ProgramError(ProgramError),
 
+    #[error("invalid token-acl mint")]
     InvalidTokenMint,
 }
 
-impl std::fmt::Display for ThawFreezeGateError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+impl ThawFreezeGateError {
+    /// Decodes an error known to come from the gate program, custom codes as
+    /// gate rejections.
+    pub fn from_gate_error(e: ProgramError) -> Self {
+        match e {
+            ProgramError::Custom(code) => ThawFreezeGateError::GateRejected(code.into()),
+            e => ThawFreezeGateError::ProgramError(e),
+        }
+    }
+
+    /// Converts back to the error a fetch function returned, when the error
+    /// came from one, and boxes `self` otherwise.
+    pub fn into_account_fetch_error(self) -> AccountFetchError {
+        match self {
+            ThawFreezeGateError::AccountFetch(e) => Arc::try_unwrap(e)
+                .unwrap_or_else(|e| Box::new(ThawFreezeGateError::AccountFetch(e))),
+            e => Box::new(e),
+        }
+    }
+}
+
+impl PartialEq for ThawFreezeGateError {
+    fn eq(&self, other: &Self) -> bool {
+        use ThawFreezeGateError::*;
+
+        match (self, other) {
+            (IncorrectAccount, IncorrectAccount)
+            | (MissingAccountMeta, MissingAccountMeta)
+            | (InvalidTokenMint, InvalidTokenMint) => true,
+            (MissingExtraAccountMeta(a), MissingExtraAccountMeta(b)) => a == b,
+            (ResolutionError(a), ResolutionError(b)) => a == b,
+            (GateRejected(a), GateRejected(b)) => a == b,
+            (UnknownProgramError(a), UnknownProgramError(b)) => a == b,
+            // fetch errors are opaque, so only the same error is equal
+            (AccountFetch(a), AccountFetch(b)) => Arc::ptr_eq(a, b),
+            (ProgramError(a), ProgramError(b)) => a == b,
+            _ => false,
+        }
     }
 }
 
-impl Into<ThawFreezeGateError> for AccountResolutionError {
-    fn into(self) -> ThawFreezeGateError {
-        ThawFreezeGateError::ResolutionError(self)
+impl From<AccountResolutionError> for ThawFreezeGateError {
+    fn from(e: AccountResolutionError) -> Self {
+        ThawFreezeGateError::ResolutionError(e)
     }
 }
 
-impl Into<ThawFreezeGateError> for ProgramError {
-    fn into(self) -> ThawFreezeGateError {
-        match self {
-            ProgramError::Custom(code) => ThawFreezeGateError::ResolutionError(
-                AccountResolutionError::from_u32(code).unwrap(),
-            ),
-            _ => ThawFreezeGateError::ProgramError(self),
+impl From<ProgramError> for ThawFreezeGateError {
+    /// Decodes custom codes as resolution errors when they are one; the
+    /// failing program is unknown, so other codes are not taken as gate
+    /// rejections, see [`ThawFreezeGateError::from_gate_error`].
+    fn from(e: ProgramError) -> Self {
+        match e {
+            ProgramError::Custom(code) => match AccountResolutionError::from_u32(code) {
+                Some(e) => ThawFreezeGateError::ResolutionError(e),
+                None => ThawFreezeGateError::UnknownProgramError(code),
+            },
+            _ => ThawFreezeGateError::ProgramError(e),
+        }
+    }
+}
+
+impl From<AccountFetchError> for ThawFreezeGateError {
+    fn from(e: AccountFetchError) -> Self {
+        let e = match e.downcast::<ThawFreezeGateError>() {
+            Ok(e) => return *e,
+            Err(e) => e,
+        };
+        ThawFreezeGateError::AccountFetch(Arc::new(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use solana_instruction::{AccountMeta, Instruction};
+    use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+
+    use super::*;
+    use crate::{
+        get_thaw_extra_account_metas_address, instruction::CanThawPermissionlessInstruction,
+        offchain::add_extra_account_metas_for_thaw,
+    };
+
+    #[test]
+    fn test_custom_codes_are_decoded() {
+        assert_eq!(
+            ThawFreezeGateError::from(ProgramError::from(AccountResolutionError::IncorrectAccount)),
+            ThawFreezeGateError::ResolutionError(AccountResolutionError::IncorrectAccount)
+        );
+
+        // codes the interface does not know are not blamed on the gate, here
+        // token-acl's PermissionlessThawNotEnabled
+        let code = 6;
+        let e = ThawFreezeGateError::from(ProgramError::Custom(code));
+        assert_eq!(e, ThawFreezeGateError::UnknownProgramError(code));
+        assert_eq!(e.to_string(), "program error code 6 (0x6)");
+        assert_eq!(
+            ThawFreezeGateError::from(ProgramError::Custom(GATE_UNSUPPORTED_INSTRUCTION)),
+            ThawFreezeGateError::UnknownProgramError(GATE_UNSUPPORTED_INSTRUCTION)
+        );
+
+        // unless they are known to come from the gate
+        assert_eq!(
+            ThawFreezeGateError::from_gate_error(ProgramError::Custom(
+                GATE_UNSUPPORTED_INSTRUCTION
+            )),
+            ThawFreezeGateError::GateRejected(GateRejection::UnsupportedInstruction)
+        );
+        let e = ThawFreezeGateError::from_gate_error(ProgramError::Custom(code));
+        assert_eq!(
+            e,
+            ThawFreezeGateError::GateRejected(GateRejection::Custom(code))
+        );
+        assert_eq!(
+            e.to_string(),
+            "gate rejected the operation: gate error code 6 (0x6)"
+        );
+        assert_eq!(
+            ThawFreezeGateError::from_gate_error(ProgramError::InvalidAccountData),
+            ThawFreezeGateError::ProgramError(ProgramError::InvalidAccountData)
+        );
+
+        assert_eq!(
+            ThawFreezeGateError::from(ProgramError::InvalidAccountData),
+            ThawFreezeGateError::ProgramError(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_account_fetch_error_round_trip() {
+        let fetch_error: AccountFetchError = Box::new(io::Error::other("rpc unavailable"));
+        let e = ThawFreezeGateError::from(fetch_error);
+        assert!(matches!(e, ThawFreezeGateError::AccountFetch(_)));
+        assert_eq!(
+            e.to_string(),
+            "failed to fetch account data: rpc unavailable"
+        );
+
+        let fetch_error = e.into_account_fetch_error();
+        let io_error = fetch_error.downcast::<io::Error>().unwrap();
+        assert_eq!(io_error.to_string(), "rpc unavailable");
+
+        let e = ThawFreezeGateError::MissingExtraAccountMeta(Pubkey::new_unique());
+        let fetch_error: AccountFetchError = e.clone().into();
+        assert_eq!(ThawFreezeGateError::from(fetch_error), e);
+        assert_eq!(
+            ThawFreezeGateError::from(e.clone().into_account_fetch_error()),
+            e
+        );
+    }
+
+    fn thaw_instruction(keys: &[Pubkey]) -> Instruction {
+        Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: keys
+                .iter()
+                .map(|key| AccountMeta::new_readonly(*key, false))
+                .collect(),
+            data: vec![],
         }
     }
+
+    #[tokio::test]
+    async fn test_resolution_errors() {
+        let [gate, signer, token_account, mint, owner, flag_account] =
+            std::array::from_fn(|_| Pubkey::new_unique());
+        let mut ix = thaw_instruction(&[gate, signer, token_account, mint, owner, flag_account]);
+
+        let res = add_extra_account_metas_for_thaw(
+            &mut ix,
+            &gate,
+            &signer,
+            &token_account,
+            &mint,
+            &owner,
+            &flag_account,
+            |_| async { Ok(None) },
+        )
+        .await;
+        assert_eq!(
+            res.unwrap_err(),
+            ThawFreezeGateError::MissingExtraAccountMeta(get_thaw_extra_account_metas_address(
+                &mint, &gate
+            ))
+        );
+
+        // the fetch error reaches the caller instead of a generic resolution error
+        let res = add_extra_account_metas_for_thaw(
+            &mut ix,
+            &gate,
+            &signer,
+            &token_account,
+            &mint,
+            &owner,
+            &flag_account,
+            |_| async { Err(Box::new(io::Error::other("rate limited")) as AccountFetchError) },
+        )
+        .await;
+        let fetch_error = res.unwrap_err().into_account_fetch_error();
+        assert_eq!(
+            fetch_error.downcast::<io::Error>().unwrap().to_string(),
+            "rate limited"
+        );
+
+        // same when the fetch fails while resolving the extra metas
+        let extra_metas = get_thaw_extra_account_metas_address(&mint, &gate);
+        let mut extra_metas_data = vec![0; ExtraAccountMetaList::size_of(0).unwrap()];
+        ExtraAccountMetaList::init::<CanThawPermissionlessInstruction>(&mut extra_metas_data, &[])
+            .unwrap();
+        let res = add_extra_account_metas_for_thaw(
+            &mut ix,
+            &gate,
+            &signer,
+            &token_account,
+            &mint,
+            &owner,
+            &flag_account,
+            |pubkey| {
+                let data = extra_metas_data.clone();
+                async move {
+                    if pubkey == extra_metas {
+                        Ok(Some(data))
+                    } else {
+                        Err(Box::new(io::Error::other("rate limited")) as AccountFetchError)
+                    }
+                }
+            },
+        )
+        .await;
+        let e = res.unwrap_err();
+        assert!(matches!(e, ThawFreezeGateError::AccountFetch(_)));
+        assert_eq!(e.to_string(), "failed to fetch account data: rate limited");
+    }
 }
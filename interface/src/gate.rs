@@ -0,0 +1,269 @@
+use solana_account_info::AccountInfo;
+use solana_program_error::{ProgramError, ProgramResult};
+use solana_pubkey::{pubkey, Pubkey};
+
+use crate::{
+    get_flag_account_address, get_freeze_extra_account_metas_address,
+    get_thaw_extra_account_metas_address, TOKEN_ACL_ID,
+};
+
+/// Token programs whose accounts a gate accepts.
+const TOKEN_PROGRAM_IDS: [Pubkey; 2] = [
+    pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+    pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"),
+];
+
+/// Offset of the mint in the base token account layout.
+const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+/// Offset of the owner in the base token account layout.
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+/// Length of the base token account layout, extensions excluded.
+const TOKEN_ACCOUNT_BASE_LEN: usize = 165;
+
+/// Accounts token-acl passes to `CanThawPermissionless` and `CanFreezePermissionless`.
+///
+/// Loading through [`GateAccounts::load_can_thaw`] or [`GateAccounts::load_can_freeze`]
+/// performs the checks every gate needs before trusting the accounts:
+/// - the flag account is token-acl's flag PDA for the token account, owned by
+///   token-acl and set, which proves the instruction is being invoked by
+///   token-acl and not called directly
+/// - the extra metas account is the gate's PDA for the mint and direction
+/// - the token account is owned by a token program and belongs to the mint and
+///   to the token account owner
+pub struct GateAccounts<'a> {
+    pub caller: &'a AccountInfo<'a>,
+    pub token_account: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub token_account_owner: &'a AccountInfo<'a>,
+    pub flag_account: &'a AccountInfo<'a>,
+    pub extra_metas: &'a AccountInfo<'a>,
+    /// Accounts resolved from the extra metas account, in order.
+    pub extra_accounts: &'a [AccountInfo<'a>],
+}
+
+impl<'a> GateAccounts<'a> {
+    pub fn load_can_thaw(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+    ) -> Result<Self, ProgramError> {
+        let gate_accounts = Self::try_from(accounts)?;
+        gate_accounts.validate(&get_thaw_extra_account_metas_address(
+            gate_accounts.mint.key,
+            program_id,
+        ))?;
+        Ok(gate_accounts)
+    }
+
+    pub fn load_can_freeze(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'a>],
+    ) -> Result<Self, ProgramError> {
+        let gate_accounts = Self::try_from(accounts)?;
+        gate_accounts.validate(&get_freeze_extra_account_metas_address(
+            gate_accounts.mint.key,
+            program_id,
+        ))?;
+        Ok(gate_accounts)
+    }
+
+    fn validate(&self, extra_metas_address: &Pubkey) -> ProgramResult {
+        check_flag_account(self.flag_account, self.token_account.key)?;
+
+        if self.extra_metas.key != extra_metas_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        check_token_account(
+            self.token_account,
+            self.mint.key,
+            self.token_account_owner.key,
+        )
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo<'a>]> for GateAccounts<'a> {
+    type Error = ProgramError;
+
+    /// Splits the accounts without validating them.
+    fn try_from(accounts: &'a [AccountInfo<'a>]) -> Result<Self, Self::Error> {
+        let [caller, token_account, mint, token_account_owner, flag_account, extra_metas, extra_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        Ok(Self {
+            caller,
+            token_account,
+            mint,
+            token_account_owner,
+            flag_account,
+            extra_metas,
+            extra_accounts,
+        })
+    }
+}
+
+/// Checks that the flag account is token-acl's flag PDA for `token_account`,
+/// is owned by token-acl and has its data set to `[1]`.
+///
+/// The flag account only holds this state while token-acl is calling into the gate,
+/// so a successful check means the gate was not called directly.
+pub fn check_flag_account(flag_account: &AccountInfo, token_account: &Pubkey) -> ProgramResult {
+    if flag_account.key != &get_flag_account_address(token_account, &TOKEN_ACL_ID) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if flag_account.owner != &TOKEN_ACL_ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if *flag_account.try_borrow_data()? != [1u8] {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Checks that the token account is owned by a token program, belongs to
+/// `mint` and is owned by `owner`.
+pub fn check_token_account(
+    token_account: &AccountInfo,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> ProgramResult {
+    if !TOKEN_PROGRAM_IDS.contains(token_account.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let data = token_account.try_borrow_data()?;
+    if data.len() < TOKEN_ACCOUNT_BASE_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if &data[TOKEN_ACCOUNT_MINT_OFFSET..TOKEN_ACCOUNT_MINT_OFFSET + 32] != mint.as_ref() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if &data[TOKEN_ACCOUNT_OWNER_OFFSET..TOKEN_ACCOUNT_OWNER_OFFSET + 32] != owner.as_ref() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_account_data(mint: &Pubkey, owner: &Pubkey) -> Vec<u8> {
+        let mut data = vec![0; TOKEN_ACCOUNT_BASE_LEN];
+        data[TOKEN_ACCOUNT_MINT_OFFSET..TOKEN_ACCOUNT_MINT_OFFSET + 32]
+            .copy_from_slice(mint.as_ref());
+        data[TOKEN_ACCOUNT_OWNER_OFFSET..TOKEN_ACCOUNT_OWNER_OFFSET + 32]
+            .copy_from_slice(owner.as_ref());
+        data
+    }
+
+    #[test]
+    fn test_check_flag_account() {
+        let token_account = Pubkey::new_unique();
+        let flag_account = get_flag_account_address(&token_account, &TOKEN_ACL_ID);
+        let (mut lamports, mut data) = (0, [1u8]);
+        let info = AccountInfo::new(
+            &flag_account,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &TOKEN_ACL_ID,
+            false,
+        );
+        assert_eq!(check_flag_account(&info, &token_account), Ok(()));
+
+        // the flag account of another token account
+        assert_eq!(
+            check_flag_account(&info, &Pubkey::new_unique()),
+            Err(ProgramError::InvalidSeeds)
+        );
+
+        let owner = Pubkey::new_unique();
+        let (mut lamports, mut data) = (0, [1u8]);
+        let info = AccountInfo::new(
+            &flag_account,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+        );
+        assert_eq!(
+            check_flag_account(&info, &token_account),
+            Err(ProgramError::InvalidAccountOwner)
+        );
+
+        let (mut lamports, mut data) = (0, [0u8]);
+        let info = AccountInfo::new(
+            &flag_account,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &TOKEN_ACL_ID,
+            false,
+        );
+        assert_eq!(
+            check_flag_account(&info, &token_account),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_check_token_account() {
+        let key = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        for token_program in TOKEN_PROGRAM_IDS {
+            let mut lamports = 0;
+            let mut data = token_account_data(&mint, &owner);
+            let info = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                &token_program,
+                false,
+            );
+            assert_eq!(check_token_account(&info, &mint, &owner), Ok(()));
+            assert_eq!(
+                check_token_account(&info, &Pubkey::new_unique(), &owner),
+                Err(ProgramError::InvalidAccountData)
+            );
+            assert_eq!(
+                check_token_account(&info, &mint, &Pubkey::new_unique()),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+
+        // same layout, but not a token account
+        let fake_program = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = token_account_data(&mint, &owner);
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &fake_program,
+            false,
+        );
+        assert_eq!(
+            check_token_account(&info, &mint, &owner),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+}
@@ -3,6 +3,7 @@ use solana_program_error::ProgramError;
 use solana_pubkey::Pubkey;
 use spl_discriminator::{ArrayDiscriminator, SplDiscriminate};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EfficientBlockAllowInstruction {
     CanThawPermissionless,
     CanFreezePermissionless,
@@ -16,6 +17,40 @@ pub struct CanThawPermissionlessInstruction;
 #[discriminator_hash_input("efficient-allow-block-list-standard:can-freeze-permissionless")]
 pub struct CanFreezePermissionlessInstruction;
 
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("efficient-allow-block-list-standard:initialize-extra-account-metas")]
+pub struct InitializeExtraAccountMetasInstruction;
+
+#[derive(SplDiscriminate)]
+#[discriminator_hash_input("efficient-allow-block-list-standard:update-extra-account-metas")]
+pub struct UpdateExtraAccountMetasInstruction;
+
+/// Optional instructions a gate program can implement to let generic tooling
+/// manage its extra metas accounts.
+///
+/// Both take the same accounts:
+/// 0. `[writable, signer]` payer
+/// 1. `[]` mint
+/// 2. `[writable]` thaw extra metas PDA
+/// 3. `[writable]` freeze extra metas PDA
+/// 4. `[]` system program
+/// 5. `[]` token-acl `MintConfig` of the mint
+/// 6. `[signer]` token-acl authority of the mint
+/// 7. ..  gate specific accounts used to compute the extra metas
+///
+/// The instruction data is the discriminator only: the gate computes the extra
+/// metas itself, so they can't be set to arbitrary values by the caller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtraAccountMetasInstruction {
+    /// Creates both extra metas PDAs, sized to the gate's current extra metas.
+    InitializeExtraAccountMetas,
+    /// Rewrites both extra metas PDAs with the gate's current extra metas.
+    ///
+    /// Accounts are reallocated to the new size. The payer funds any rent
+    /// increase; lamports in excess of rent are left in the account when it shrinks.
+    UpdateExtraAccountMetas,
+}
+
 impl EfficientBlockAllowInstruction {
     pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
         if data.len() < ArrayDiscriminator::LENGTH {
@@ -45,6 +80,35 @@ impl EfficientBlockAllowInstruction {
     }
 }
 
+impl ExtraAccountMetasInstruction {
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < ArrayDiscriminator::LENGTH {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (discriminator, _) = data.split_at(ArrayDiscriminator::LENGTH);
+        match discriminator {
+            InitializeExtraAccountMetasInstruction::SPL_DISCRIMINATOR_SLICE => {
+                Ok(Self::InitializeExtraAccountMetas)
+            }
+            UpdateExtraAccountMetasInstruction::SPL_DISCRIMINATOR_SLICE => {
+                Ok(Self::UpdateExtraAccountMetas)
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        match self {
+            Self::InitializeExtraAccountMetas => {
+                InitializeExtraAccountMetasInstruction::SPL_DISCRIMINATOR_SLICE.to_vec()
+            }
+            Self::UpdateExtraAccountMetas => {
+                UpdateExtraAccountMetasInstruction::SPL_DISCRIMINATOR_SLICE.to_vec()
+            }
+        }
+    }
+}
+
 pub fn can_thaw_permissionless(
     program_id: &Pubkey,
     signer: &Pubkey,
@@ -92,3 +156,70 @@ pub fn can_freeze_permissionless(
         data,
     }
 }
+
+pub fn initialize_extra_account_metas(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    additional_accounts: &[AccountMeta],
+) -> Instruction {
+    extra_account_metas_instruction(
+        ExtraAccountMetasInstruction::InitializeExtraAccountMetas,
+        program_id,
+        payer,
+        authority,
+        mint,
+        additional_accounts,
+    )
+}
+
+pub fn update_extra_account_metas(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    additional_accounts: &[AccountMeta],
+) -> Instruction {
+    extra_account_metas_instruction(
+        ExtraAccountMetasInstruction::UpdateExtraAccountMetas,
+        program_id,
+        payer,
+        authority,
+        mint,
+        additional_accounts,
+    )
+}
+
+fn extra_account_metas_instruction(
+    instruction: ExtraAccountMetasInstruction,
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    additional_accounts: &[AccountMeta],
+) -> Instruction {
+    let data = instruction.pack();
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(
+            crate::get_thaw_extra_account_metas_address(mint, program_id),
+            false,
+        ),
+        AccountMeta::new(
+            crate::get_freeze_extra_account_metas_address(mint, program_id),
+            false,
+        ),
+        AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        AccountMeta::new_readonly(crate::get_mint_config_address(mint), false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+    accounts.extend_from_slice(additional_accounts);
+
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    }
+}
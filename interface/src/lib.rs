@@ -1,6 +1,7 @@
 use solana_pubkey::{pubkey, Pubkey};
 
 pub mod error;
+pub mod gate;
 pub mod instruction;
 pub mod offchain;
 pub mod onchain;
@@ -8,6 +9,7 @@ pub mod onchain;
 pub const FREEZE_EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"freeze_extra_account_metas";
 pub const THAW_EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"thaw_extra_account_metas";
 pub const FLAG_ACCOUNT_SEED: &[u8] = b"FLAG_ACCOUNT";
+pub const MINT_CONFIG_SEED: &[u8] = b"MINT_CONFIG";
 
 pub const TOKEN_ACL_ID: Pubkey = pubkey!("TACLkU6CiCdkQN2MjoyDkVg2yAH9zkxiHDsiztQ52TP");
 
@@ -45,6 +47,10 @@ pub fn get_freeze_extra_account_metas_address(mint: &Pubkey, program_id: &Pubkey
     get_freeze_extra_account_metas_address_and_bump_seed(mint, program_id).0
 }
 
+pub fn get_mint_config_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MINT_CONFIG_SEED, mint.as_ref()], &TOKEN_ACL_ID).0
+}
+
 pub fn get_flag_account_address(token_account: &Pubkey, program_id: &Pubkey) -> Pubkey {
     Pubkey::find_program_address(&collect_flag_account(token_account), program_id).0
 }
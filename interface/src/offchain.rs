@@ -1,6 +1,10 @@
 use spl_discriminator::SplDiscriminate;
 pub use spl_tlv_account_resolution::state::{AccountDataResult, AccountFetchError};
 
+mod fetcher;
+
+pub use fetcher::*;
+
 use crate::{
     get_freeze_extra_account_metas_address,
     instruction::{can_freeze_permissionless, CanFreezePermissionlessInstruction},
@@ -15,11 +19,11 @@ use {
     solana_instruction::{AccountMeta, Instruction},
     solana_pubkey::Pubkey,
     spl_tlv_account_resolution::state::ExtraAccountMetaList,
-    std::future::Future,
+    std::{collections::HashMap, sync::Mutex},
 };
 
 #[allow(clippy::too_many_arguments)]
-pub async fn add_extra_account_metas_for_freeze<F, Fut>(
+pub async fn add_extra_account_metas_for_freeze<A>(
     instruction: &mut Instruction,
     program_id: &Pubkey,
     signer_pubkey: &Pubkey,
@@ -27,15 +31,14 @@ pub async fn add_extra_account_metas_for_freeze<F, Fut>(
     mint_pubkey: &Pubkey,
     token_account_owner: &Pubkey,
     flag_account_pubkey: &Pubkey,
-    fetch_account_data_fn: F,
+    fetcher: A,
 ) -> Result<(), ThawFreezeGateError>
 where
-    F: Fn(Pubkey) -> Fut,
-    Fut: Future<Output = AccountDataResult>,
+    A: AccountFetcher,
 {
     let extra_metas_pubkey = get_freeze_extra_account_metas_address(mint_pubkey, program_id);
 
-    add_extra_account_metas_for_permissionless_ix::<_, _, CanFreezePermissionlessInstruction, _>(
+    add_extra_account_metas_for_permissionless_ix::<_, CanFreezePermissionlessInstruction, _>(
         instruction,
         program_id,
         signer_pubkey,
@@ -43,8 +46,8 @@ where
         mint_pubkey,
         token_account_owner,
         &extra_metas_pubkey,
-        &flag_account_pubkey,
-        fetch_account_data_fn,
+        flag_account_pubkey,
+        fetcher,
         |program_id,
          signer_pubkey,
          token_account_pubkey,
@@ -57,15 +60,51 @@ where
                 token_account_pubkey,
                 mint_pubkey,
                 token_account_owner,
-                &flag_account_pubkey,
+                flag_account_pubkey,
             )
         },
     )
     .await
 }
 
+/// Same as [`add_extra_account_metas_for_freeze`], but `supply_account_data_fn`
+/// is asked for every account before `fetcher`, so clients can supply
+/// accounts the RPC does not have yet, e.g. gate state created earlier in the
+/// same transaction.
+#[allow(clippy::too_many_arguments)]
+pub async fn add_extra_account_metas_for_freeze_with_hook<A, H>(
+    instruction: &mut Instruction,
+    program_id: &Pubkey,
+    signer_pubkey: &Pubkey,
+    token_account_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    token_account_owner: &Pubkey,
+    flag_account_pubkey: &Pubkey,
+    fetcher: A,
+    supply_account_data_fn: H,
+) -> Result<(), ThawFreezeGateError>
+where
+    A: AccountFetcher,
+    H: Fn(&Pubkey) -> Option<Vec<u8>>,
+{
+    add_extra_account_metas_for_freeze(
+        instruction,
+        program_id,
+        signer_pubkey,
+        token_account_pubkey,
+        mint_pubkey,
+        token_account_owner,
+        flag_account_pubkey,
+        SupplyHookFetcher {
+            inner: fetcher,
+            supply_account_data_fn,
+        },
+    )
+    .await
+}
+
 #[allow(clippy::too_many_arguments)]
-pub async fn add_extra_account_metas_for_thaw<F, Fut>(
+pub async fn add_extra_account_metas_for_thaw<A>(
     instruction: &mut Instruction,
     program_id: &Pubkey,
     signer_pubkey: &Pubkey,
@@ -73,15 +112,14 @@ pub async fn add_extra_account_metas_for_thaw<F, Fut>(
     mint_pubkey: &Pubkey,
     token_account_owner: &Pubkey,
     flag_account_pubkey: &Pubkey,
-    fetch_account_data_fn: F,
+    fetcher: A,
 ) -> Result<(), ThawFreezeGateError>
 where
-    F: Fn(Pubkey) -> Fut,
-    Fut: Future<Output = AccountDataResult>,
+    A: AccountFetcher,
 {
     let extra_metas_pubkey = get_thaw_extra_account_metas_address(mint_pubkey, program_id);
 
-    add_extra_account_metas_for_permissionless_ix::<_, _, CanThawPermissionlessInstruction, _>(
+    add_extra_account_metas_for_permissionless_ix::<_, CanThawPermissionlessInstruction, _>(
         instruction,
         program_id,
         signer_pubkey,
@@ -89,8 +127,8 @@ where
         mint_pubkey,
         token_account_owner,
         &extra_metas_pubkey,
-        &flag_account_pubkey,
-        fetch_account_data_fn,
+        flag_account_pubkey,
+        fetcher,
         |program_id,
          signer_pubkey,
          token_account_pubkey,
@@ -110,8 +148,44 @@ where
     .await
 }
 
+/// Same as [`add_extra_account_metas_for_thaw`], but `supply_account_data_fn`
+/// is asked for every account before `fetcher`, so clients can supply
+/// accounts the RPC does not have yet, e.g. gate state created earlier in the
+/// same transaction.
+#[allow(clippy::too_many_arguments)]
+pub async fn add_extra_account_metas_for_thaw_with_hook<A, H>(
+    instruction: &mut Instruction,
+    program_id: &Pubkey,
+    signer_pubkey: &Pubkey,
+    token_account_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    token_account_owner: &Pubkey,
+    flag_account_pubkey: &Pubkey,
+    fetcher: A,
+    supply_account_data_fn: H,
+) -> Result<(), ThawFreezeGateError>
+where
+    A: AccountFetcher,
+    H: Fn(&Pubkey) -> Option<Vec<u8>>,
+{
+    add_extra_account_metas_for_thaw(
+        instruction,
+        program_id,
+        signer_pubkey,
+        token_account_pubkey,
+        mint_pubkey,
+        token_account_owner,
+        flag_account_pubkey,
+        SupplyHookFetcher {
+            inner: fetcher,
+            supply_account_data_fn,
+        },
+    )
+    .await
+}
+
 #[allow(clippy::too_many_arguments)]
-async fn add_extra_account_metas_for_permissionless_ix<F, Fut, T, F2>(
+async fn add_extra_account_metas_for_permissionless_ix<A, T, F2>(
     instruction: &mut Instruction,
     program_id: &Pubkey,
     signer_pubkey: &Pubkey,
@@ -120,20 +194,22 @@ async fn add_extra_account_metas_for_permissionless_ix<F, Fut, T, F2>(
     token_account_owner: &Pubkey,
     extra_metas_pubkey: &Pubkey,
     flag_account_pubkey: &Pubkey,
-    fetch_account_data_fn: F,
+    fetcher: A,
     cpi_ix_builder_fn: F2,
 ) -> Result<(), ThawFreezeGateError>
 where
-    F: Fn(Pubkey) -> Fut,
+    A: AccountFetcher,
     F2: Fn(&Pubkey, &Pubkey, &Pubkey, &Pubkey, &Pubkey, &Pubkey) -> Instruction,
-    Fut: Future<Output = AccountDataResult>,
     T: SplDiscriminate,
 {
-    //let validate_state_pubkey = get_thaw_extra_account_metas_address(mint_pubkey, program_id);
-    let validate_state_data = fetch_account_data_fn(*extra_metas_pubkey)
-        .await
-        .map_err(|_| ThawFreezeGateError::MissingExtraAccountMeta)?
-        .ok_or(ThawFreezeGateError::MissingExtraAccountMeta)?;
+    let validate_state_data = fetcher
+        .get_multiple_account_data(&[*extra_metas_pubkey])
+        .await?
+        .pop()
+        .flatten()
+        .ok_or(ThawFreezeGateError::MissingExtraAccountMeta(
+            *extra_metas_pubkey,
+        ))?;
 
     // Check to make sure the provided keys are in the instruction
     if [
@@ -161,13 +237,39 @@ where
         .accounts
         .push(AccountMeta::new_readonly(*extra_metas_pubkey, false));
 
-    ExtraAccountMetaList::add_to_instruction::<T, _, _>(
-        &mut cpi_ix,
-        fetch_account_data_fn,
-        &validate_state_data,
-    )
-    .await
-    .map_err(Into::into)?;
+    // Resolve in rounds against the accounts fetched so far, recording the
+    // ones that are missing, and fetch those in one batch for the next round.
+    // Each round fetches at least one new account, so this ends once every
+    // account the extra metas read is known.
+    let mut accounts = HashMap::from([(*extra_metas_pubkey, Some(validate_state_data.clone()))]);
+    let cpi_ix = loop {
+        let mut round_ix = cpi_ix.clone();
+        let wanted = Mutex::new(Vec::new());
+        let res = ExtraAccountMetaList::add_to_instruction::<T, _, _>(
+            &mut round_ix,
+            |pubkey: Pubkey| {
+                let data = accounts.get(&pubkey).cloned();
+                if data.is_none() {
+                    if let Ok(mut wanted) = wanted.lock() {
+                        wanted.push(pubkey);
+                    }
+                }
+                async move { Ok(data.flatten()) }
+            },
+            &validate_state_data,
+        )
+        .await;
+
+        let mut wanted = wanted.into_inner().unwrap_or_default();
+        if wanted.is_empty() {
+            res?;
+            break round_ix;
+        }
+        wanted.sort();
+        wanted.dedup();
+        let fetched = fetcher.get_multiple_account_data(&wanted).await?;
+        accounts.extend(wanted.into_iter().zip(fetched));
+    };
 
     // Add only the extra accounts resolved from the validation state
     instruction
@@ -176,3 +278,48 @@ where
 
     Ok(())
 }
+
+/// Serves the accounts `supply_account_data_fn` returns before `inner`.
+struct SupplyHookFetcher<A, H> {
+    inner: A,
+    supply_account_data_fn: H,
+}
+
+impl<A, H> AccountFetcher for SupplyHookFetcher<A, H>
+where
+    A: AccountFetcher,
+    H: Fn(&Pubkey) -> Option<Vec<u8>>,
+{
+    async fn get_account_data(&self, pubkey: Pubkey) -> AccountDataResult {
+        match (self.supply_account_data_fn)(&pubkey) {
+            Some(data) => Ok(Some(data)),
+            None => self.inner.get_account_data(pubkey).await,
+        }
+    }
+
+    async fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, AccountFetchError> {
+        let supplied = pubkeys
+            .iter()
+            .map(|pubkey| (self.supply_account_data_fn)(pubkey))
+            .collect::<Vec<_>>();
+        let missing = pubkeys
+            .iter()
+            .zip(&supplied)
+            .filter(|(_, data)| data.is_none())
+            .map(|(pubkey, _)| *pubkey)
+            .collect::<Vec<_>>();
+        let mut fetched = self
+            .inner
+            .get_multiple_account_data(&missing)
+            .await?
+            .into_iter();
+
+        Ok(supplied
+            .into_iter()
+            .map(|data| data.or_else(|| fetched.next().flatten()))
+            .collect())
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use solana_pubkey::Pubkey;
+use spl_tlv_account_resolution::state::{AccountDataResult, AccountFetchError};
+
+/// Source of account data for offchain resolution.
+///
+/// Any `Fn(Pubkey) -> Future<Output = AccountDataResult>` closure is a
+/// fetcher, so resolvers taking one keep accepting closures.
+pub trait AccountFetcher {
+    /// Data of `pubkey`, `None` when the account does not exist.
+    fn get_account_data(&self, pubkey: Pubkey) -> impl Future<Output = AccountDataResult>;
+
+    /// Data of every account in `pubkeys`, in order.
+    ///
+    /// Fetches one account at a time by default; fetchers backed by an RPC
+    /// node should override it with `getMultipleAccounts`.
+    fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> impl Future<Output = Result<Vec<Option<Vec<u8>>>, AccountFetchError>> {
+        async move {
+            let mut accounts = Vec::with_capacity(pubkeys.len());
+            for pubkey in pubkeys {
+                accounts.push(self.get_account_data(*pubkey).await?);
+            }
+            Ok(accounts)
+        }
+    }
+
+    /// Program owning `pubkey`, `None` when the account does not exist.
+    ///
+    /// Closures only return data, so this fails by default; fetchers that
+    /// know the owner of the accounts they fetch should override it.
+    fn get_account_owner(
+        &self,
+        pubkey: Pubkey,
+    ) -> impl Future<Output = Result<Option<Pubkey>, AccountFetchError>> {
+        async move { Err(format!("cannot fetch the owner of {pubkey}").into()) }
+    }
+}
+
+impl<F, Fut> AccountFetcher for F
+where
+    F: Fn(Pubkey) -> Fut,
+    Fut: Future<Output = AccountDataResult>,
+{
+    fn get_account_data(&self, pubkey: Pubkey) -> impl Future<Output = AccountDataResult> {
+        self(pubkey)
+    }
+}
+
+struct CacheEntry {
+    data: Option<Vec<u8>>,
+    fetched_at: Instant,
+}
+
+/// Caches the accounts fetched through `inner` for `ttl`, missing accounts
+/// included.
+///
+/// Resolving many token accounts of a mint then fetches the mint config, the
+/// extra metas and the gate's per-mint state once. Accounts known up front,
+/// such as the token accounts themselves, can be loaded in one batch with
+/// [`CachedAccountFetcher::prefetch`].
+pub struct CachedAccountFetcher<A> {
+    inner: A,
+    ttl: Duration,
+    entries: Mutex<HashMap<Pubkey, CacheEntry>>,
+}
+
+impl<A: AccountFetcher> CachedAccountFetcher<A> {
+    pub fn new(inner: A, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Fetches the accounts of `pubkeys` that are not cached in one batch.
+    pub async fn prefetch(&self, pubkeys: &[Pubkey]) -> Result<(), AccountFetchError> {
+        self.get_multiple_account_data(pubkeys).await.map(|_| ())
+    }
+
+    /// Drops `pubkey` from the cache, e.g. after a transaction changed it.
+    pub fn invalidate(&self, pubkey: &Pubkey) {
+        self.entries().remove(pubkey);
+    }
+
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<Pubkey, CacheEntry>> {
+        // entries are inserted whole, so a panic elsewhere cannot corrupt them
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn cached(&self, pubkey: &Pubkey) -> Option<Option<Vec<u8>>> {
+        self.entries()
+            .get(pubkey)
+            .filter(|entry| entry.fetched_at.elapsed() < self.ttl)
+            .map(|entry| entry.data.clone())
+    }
+
+    fn insert(&self, pubkey: Pubkey, data: Option<Vec<u8>>) {
+        self.entries().insert(
+            pubkey,
+            CacheEntry {
+                data,
+                fetched_at: Instant::now(),
+            },
+        );
+    }
+}
+
+impl<A: AccountFetcher> AccountFetcher for CachedAccountFetcher<A> {
+    async fn get_account_data(&self, pubkey: Pubkey) -> AccountDataResult {
+        if let Some(data) = self.cached(&pubkey) {
+            return Ok(data);
+        }
+
+        let data = self.inner.get_account_data(pubkey).await?;
+        self.insert(pubkey, data.clone());
+        Ok(data)
+    }
+
+    async fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, AccountFetchError> {
+        let mut accounts = pubkeys
+            .iter()
+            .map(|pubkey| self.cached(pubkey))
+            .collect::<Vec<_>>();
+
+        let missing = (0..pubkeys.len())
+            .filter(|&i| accounts[i].is_none())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let missing_pubkeys = missing.iter().map(|&i| pubkeys[i]).collect::<Vec<_>>();
+            let fetched = self
+                .inner
+                .get_multiple_account_data(&missing_pubkeys)
+                .await?;
+            for (i, data) in missing.into_iter().zip(fetched) {
+                self.insert(pubkeys[i], data.clone());
+                accounts[i] = Some(data);
+            }
+        }
+
+        Ok(accounts.into_iter().map(Option::flatten).collect())
+    }
+
+    /// Not cached: owners are only looked up to pick the token program.
+    fn get_account_owner(
+        &self,
+        pubkey: Pubkey,
+    ) -> impl Future<Output = Result<Option<Pubkey>, AccountFetchError>> {
+        self.inner.get_account_owner(pubkey)
+    }
+}
+
+impl<A: AccountFetcher> AccountFetcher for &CachedAccountFetcher<A> {
+    fn get_account_data(&self, pubkey: Pubkey) -> impl Future<Output = AccountDataResult> {
+        (*self).get_account_data(pubkey)
+    }
+
+    fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> impl Future<Output = Result<Vec<Option<Vec<u8>>>, AccountFetchError>> {
+        (*self).get_multiple_account_data(pubkeys)
+    }
+
+    fn get_account_owner(
+        &self,
+        pubkey: Pubkey,
+    ) -> impl Future<Output = Result<Option<Pubkey>, AccountFetchError>> {
+        (*self).get_account_owner(pubkey)
+    }
+}
+
+/// Serves the accounts set on it before falling back to `inner`.
+///
+/// Lets clients resolve against accounts that do not exist yet, e.g. a token
+/// account created earlier in the same transaction, or mock a missing one.
+/// Owners are only overlaid when set with
+/// [`OverlayAccountFetcher::with_owned_account`].
+pub struct OverlayAccountFetcher<'a, A: ?Sized> {
+    inner: &'a A,
+    accounts: HashMap<Pubkey, Option<Vec<u8>>>,
+    owners: HashMap<Pubkey, Pubkey>,
+}
+
+impl<'a, A: AccountFetcher + ?Sized> OverlayAccountFetcher<'a, A> {
+    pub fn new(inner: &'a A) -> Self {
+        Self {
+            inner,
+            accounts: HashMap::new(),
+            owners: HashMap::new(),
+        }
+    }
+
+    pub fn with_account(mut self, pubkey: Pubkey, data: Vec<u8>) -> Self {
+        self.accounts.insert(pubkey, Some(data));
+        self
+    }
+
+    pub fn with_owned_account(mut self, pubkey: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        self.owners.insert(pubkey, owner);
+        self.with_account(pubkey, data)
+    }
+
+    /// Reports `pubkey` as missing, whatever `inner` holds.
+    pub fn with_missing_account(mut self, pubkey: Pubkey) -> Self {
+        self.owners.remove(&pubkey);
+        self.accounts.insert(pubkey, None);
+        self
+    }
+}
+
+impl<A: AccountFetcher + ?Sized> AccountFetcher for OverlayAccountFetcher<'_, A> {
+    async fn get_account_data(&self, pubkey: Pubkey) -> AccountDataResult {
+        match self.accounts.get(&pubkey) {
+            Some(data) => Ok(data.clone()),
+            None => self.inner.get_account_data(pubkey).await,
+        }
+    }
+
+    async fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> Result<Vec<Option<Vec<u8>>>, AccountFetchError> {
+        let missing = pubkeys
+            .iter()
+            .filter(|pubkey| !self.accounts.contains_key(pubkey))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut fetched = self
+            .inner
+            .get_multiple_account_data(&missing)
+            .await?
+            .into_iter();
+
+        Ok(pubkeys
+            .iter()
+            .map(|pubkey| match self.accounts.get(pubkey) {
+                Some(data) => data.clone(),
+                None => fetched.next().flatten(),
+            })
+            .collect())
+    }
+
+    async fn get_account_owner(&self, pubkey: Pubkey) -> Result<Option<Pubkey>, AccountFetchError> {
+        match (self.owners.get(&pubkey), self.accounts.get(&pubkey)) {
+            (Some(owner), _) => Ok(Some(*owner)),
+            (None, Some(None)) => Ok(None),
+            _ => self.inner.get_account_owner(pubkey).await,
+        }
+    }
+}
+
+impl<A: AccountFetcher + ?Sized> AccountFetcher for &OverlayAccountFetcher<'_, A> {
+    fn get_account_data(&self, pubkey: Pubkey) -> impl Future<Output = AccountDataResult> {
+        (*self).get_account_data(pubkey)
+    }
+
+    fn get_multiple_account_data(
+        &self,
+        pubkeys: &[Pubkey],
+    ) -> impl Future<Output = Result<Vec<Option<Vec<u8>>>, AccountFetchError>> {
+        (*self).get_multiple_account_data(pubkeys)
+    }
+
+    fn get_account_owner(
+        &self,
+        pubkey: Pubkey,
+    ) -> impl Future<Output = Result<Option<Pubkey>, AccountFetchError>> {
+        (*self).get_account_owner(pubkey)
+    }
+}
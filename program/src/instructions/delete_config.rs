@@ -31,11 +31,8 @@ impl DeleteConfig<'_> {
         let mint_data = self.mint.data.borrow_mut();
         let mint = PodStateWithExtensions::<PodMint>::unpack(&mint_data);
         let set_freeze_authority = mint
-            .and_then(|mint| {
-                Ok(
-                    mint.base.freeze_authority.unwrap_or(Pubkey::default())
-                        == *self.mint_config.key,
-                )
+            .map(|mint| {
+                mint.base.freeze_authority.unwrap_or(Pubkey::default()) == *self.mint_config.key
             })
             .unwrap_or(false);
         drop(mint_data);
@@ -51,15 +51,13 @@ impl ThawPermissionless<'_> {
                 return Err(TokenAclError::InvalidTokenAccountOwner.into());
             }
 
-            if is_idempotent {
-                if ta.base.state != AccountState::Frozen {
-                    // thaw CPI enforces ta.base.mint == self.mint.key, but we're returning early
-                    // so we need to check it to enforce same behaviour regardless of idempotency
-                    if ta.base.mint != *self.mint.key {
-                        return Err(TokenAclError::InvalidTokenMint.into());
-                    }
-                    return Ok(());
+            if is_idempotent && ta.base.state != AccountState::Frozen {
+                // thaw CPI enforces ta.base.mint == self.mint.key, but we're returning early
+                // so we need to check it to enforce same behaviour regardless of idempotency
+                if ta.base.mint != *self.mint.key {
+                    return Err(TokenAclError::InvalidTokenMint.into());
                 }
+                return Ok(());
             }
         }
 
@@ -71,7 +69,7 @@ impl ThawPermissionless<'_> {
         ];
 
         // allocate, assign and initialize flag account
-        let ix = solana_system_interface::instruction::allocate(self.flag_account.key, 1 as u64);
+        let ix = solana_system_interface::instruction::allocate(self.flag_account.key, 1);
         invoke_signed(
             &ix,
             &[self.authority.clone(), self.flag_account.clone()],